@@ -0,0 +1,140 @@
+// --------------------------------------------------------
+// Actor library - Actor handler attribute
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::collections::BTreeSet;
+use syn::visit_mut::{self, VisitMut};
+use syn::{Expr, ExprPath, ImplItem, ImplItemFn, ItemImpl, LitStr, Member, ReturnType};
+
+/// Kind tells how a method is rewritten.
+enum Kind {
+    Send,
+    Ask,
+}
+
+/// Expands the `actor_handler` attribute for the given arguments and impl block.
+pub(crate) fn expand(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let actor = parse_actor_field(attr)?;
+    let mut item: ItemImpl = syn::parse2(item)?;
+
+    for impl_item in item.items.iter_mut() {
+        if let ImplItem::Fn(method) = impl_item {
+            if let Some(kind) = take_kind(method) {
+                rewrite(method, kind, &actor)?;
+            }
+        }
+    }
+
+    Ok(quote! { #item })
+}
+
+/// Parses `actor_field = "self.actor"` into the expression of the field.
+fn parse_actor_field(attr: TokenStream) -> syn::Result<Expr> {
+    let mut actor = None;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("actor_field") {
+            let value: LitStr = meta.value()?.parse()?;
+            actor = Some(value.parse::<Expr>()?);
+            Ok(())
+        } else {
+            Err(meta.error("expected `actor_field = \"...\"`"))
+        }
+    });
+    syn::parse::Parser::parse2(parser, attr)?;
+    Ok(actor.unwrap_or_else(|| syn::parse_quote! { self.actor }))
+}
+
+/// Removes the `#[send]` or `#[ask]` attribute of a method and returns its kind.
+fn take_kind(method: &mut ImplItemFn) -> Option<Kind> {
+    let mut kind = None;
+    method.attrs.retain(|attr| {
+        if attr.path().is_ident("send") {
+            kind = Some(Kind::Send);
+            false
+        } else if attr.path().is_ident("ask") {
+            kind = Some(Kind::Ask);
+            false
+        } else {
+            true
+        }
+    });
+    kind
+}
+
+/// Rewrites the method into an async one running its body inside the actor.
+fn rewrite(method: &mut ImplItemFn, kind: Kind, actor: &Expr) -> syn::Result<()> {
+    if method.sig.asyncness.is_some() {
+        return Err(syn::Error::new_spanned(
+            &method.sig,
+            "actor handler methods must not be async",
+        ));
+    }
+
+    // Replace all accesses to fields of self by captured clones.
+    let mut captures = FieldCaptures::default();
+    let mut body = method.block.clone();
+    captures.visit_block_mut(&mut body);
+    let clones = captures.fields.iter().map(|field| {
+        quote! { let #field = ::std::clone::Clone::clone(&self.#field); }
+    });
+
+    let output = match &method.sig.output {
+        ReturnType::Default => quote! { () },
+        ReturnType::Type(_, ty) => quote! { #ty },
+    };
+    let (output, call) = match kind {
+        Kind::Send => (
+            quote! { ::actor::TaskId },
+            quote! {
+                #actor.send(move || {
+                    #body;
+                    Ok(())
+                })
+            },
+        ),
+        Kind::Ask => (output, quote! { #actor.ask(move || #body) }),
+    };
+
+    method.sig.asyncness = Some(Default::default());
+    method.sig.output = syn::parse_quote! {
+        -> ::std::result::Result<#output, ::actor::ActorError>
+    };
+    method.block = syn::parse_quote! {{
+        #(#clones)*
+        #call.await
+    }};
+    Ok(())
+}
+
+/// FieldCaptures replaces `self.field` expressions by `field` and collects the
+/// names of the fields.
+#[derive(Default)]
+struct FieldCaptures {
+    fields: BTreeSet<syn::Ident>,
+}
+
+impl VisitMut for FieldCaptures {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        if let Expr::Field(field) = expr {
+            if let (Expr::Path(base), Member::Named(name)) = (&*field.base, &field.member) {
+                if base.path.is_ident("self") {
+                    self.fields.insert(name.clone());
+                    *expr = Expr::Path(ExprPath {
+                        attrs: Vec::new(),
+                        qself: None,
+                        path: name.clone().into(),
+                    });
+                    return;
+                }
+            }
+        }
+        visit_mut::visit_expr_mut(self, expr);
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,159 @@
+// --------------------------------------------------------
+// Actor library - Derive macros
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident};
+
+mod handler;
+
+/// Derives typed send methods for an enum of actor messages. The macro generates
+/// the trait `<Enum>Sender` implemented for `actor::TypedActor<Enum, S>`. Each
+/// variant becomes a method `send_<variant>` taking the fields of the variant
+/// as arguments, wrapping them into the enum and telling it the actor.
+///
+/// ```ignore
+/// #[derive(ActorMessages)]
+/// enum CounterMsg {
+///     Increment,
+///     Decrement(i32),
+/// }
+///
+/// actor.send_increment().await?;
+/// actor.send_decrement(3).await?;
+/// ```
+#[proc_macro_derive(ActorMessages)]
+pub fn derive_actor_messages(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return syn::Error::new_spanned(
+                &input.ident,
+                "ActorMessages can only be derived for enums",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let sender = format_ident!("{}Sender", name);
+    let mut signatures = Vec::new();
+    let mut methods = Vec::new();
+
+    for variant in &data.variants {
+        let variant_name = &variant.ident;
+        let method = Ident::new(
+            &format!("send_{}", snake_case(&variant_name.to_string())),
+            Span::call_site(),
+        );
+        let (args, message) = match &variant.fields {
+            Fields::Unit => (Vec::new(), quote! { #name::#variant_name }),
+            Fields::Unnamed(fields) => {
+                let args: Vec<_> = fields
+                    .unnamed
+                    .iter()
+                    .enumerate()
+                    .map(|(i, field)| (format_ident!("arg{}", i), &field.ty))
+                    .collect();
+                let idents = args.iter().map(|(ident, _)| ident);
+                let message = quote! { #name::#variant_name(#(#idents),*) };
+                (args, message)
+            }
+            Fields::Named(fields) => {
+                let args: Vec<_> = fields
+                    .named
+                    .iter()
+                    .map(|field| (field.ident.clone().unwrap(), &field.ty))
+                    .collect();
+                let idents = args.iter().map(|(ident, _)| ident);
+                let message = quote! { #name::#variant_name { #(#idents),* } };
+                (args, message)
+            }
+        };
+        let params = args.iter().map(|(ident, ty)| quote! { #ident: #ty });
+        let signature = quote! {
+            fn #method(&self #(, #params)*)
+                -> impl ::std::future::Future<Output = ::std::result::Result<(), ::actor::ActorError>> + Send
+        };
+
+        signatures.push(quote! { #signature; });
+        methods.push(quote! {
+            #signature {
+                self.tell(#message)
+            }
+        });
+    }
+
+    let expanded = quote! {
+        /// Typed send methods for the variants of the messages.
+        #vis trait #sender {
+            #(#signatures)*
+        }
+
+        impl<S: Send + 'static> #sender for ::actor::TypedActor<#name, S> {
+            #(#methods)*
+        }
+    };
+    expanded.into()
+}
+
+/// Rewrites the methods of an impl block to run inside of an actor. Methods
+/// annotated with `#[send]` become async methods sending their body as task via
+/// `send()` and returning its task ID, methods annotated with `#[ask]` use
+/// `ask()` and return the value of their body. Fields of `self` accessed in the
+/// body are cloned and captured by the task. The actor is found using
+/// `actor_field`, by default `self.actor`.
+///
+/// ```ignore
+/// #[actor_handler(actor_field = "self.actor")]
+/// impl AsyncCounter {
+///     #[send]
+///     fn incr(&self) {
+///         *self.value.lock().unwrap() += 1;
+///     }
+///
+///     #[ask]
+///     fn read_value(&self) -> i32 {
+///         *self.value.lock().unwrap()
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn actor_handler(attr: TokenStream, item: TokenStream) -> TokenStream {
+    match handler::expand(attr.into(), item.into()) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Converts a variant name in camel case into snake case. A run of capitals
+/// like in `HTTPRequest` is one word, so it becomes `http_request`.
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let after_lower = i > 0 && !chars[i - 1].is_uppercase();
+            let ends_run = i > 0
+                && chars[i - 1].is_uppercase()
+                && chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if after_lower || ends_run {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(*c);
+        }
+    }
+    snake
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,79 @@
+// --------------------------------------------------------
+// Actor library - Derive macro tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, TypedActor};
+use actor_derive::ActorMessages;
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+// Test the generated send methods for all kinds of variants.
+async fn test_derive_actor_messages() {
+    let value = Arc::new(Mutex::new(0));
+    let handler_value = value.clone();
+    let actor = TypedActor::new((), move |_, msg: CounterMsg| {
+        let mut value = handler_value.lock().unwrap();
+        match msg {
+            CounterMsg::Increment => *value += 1,
+            CounterMsg::Decrement(n) => *value -= n,
+            CounterMsg::SetValue { value: n } => *value = n,
+        }
+        Ok(())
+    });
+
+    assert_eq!(actor.send_set_value(10).await, Ok(()));
+    assert_eq!(actor.send_increment().await, Ok(()));
+    assert_eq!(actor.send_decrement(3).await, Ok(()));
+    let _ = actor.stop().await;
+
+    // Wait a bit to ensure that the actor has processed all messages.
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    assert_eq!(*value.lock().unwrap(), 8);
+    assert_eq!(actor.send_increment().await, Err(ActorError::Stopped));
+}
+
+#[tokio::test]
+// Test the method names of variants with runs of capitals.
+async fn test_derive_actor_messages_capitals() {
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let handler_requests = requests.clone();
+    let actor = TypedActor::new((), move |_, msg: ProtocolMsg| {
+        let request = match msg {
+            ProtocolMsg::HTTPRequest(path) => path,
+            ProtocolMsg::ResetTCP => "reset".to_string(),
+        };
+        handler_requests.lock().unwrap().push(request);
+        Ok(())
+    });
+
+    assert_eq!(actor.send_http_request("/".to_string()).await, Ok(()));
+    assert_eq!(actor.send_reset_tcp().await, Ok(()));
+    let _ = actor.stop().await;
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    assert_eq!(*requests.lock().unwrap(), vec!["/", "reset"]);
+}
+
+// --------------------------------------------------------
+// TEST HELPER
+// --------------------------------------------------------
+
+#[derive(ActorMessages)]
+enum CounterMsg {
+    Increment,
+    Decrement(i32),
+    SetValue { value: i32 },
+}
+
+#[derive(ActorMessages)]
+#[allow(clippy::upper_case_acronyms)]
+enum ProtocolMsg {
+    HTTPRequest(String),
+    ResetTCP,
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
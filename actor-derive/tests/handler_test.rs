@@ -0,0 +1,70 @@
+// --------------------------------------------------------
+// Actor library - Actor handler attribute tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::AsyncActor;
+use actor_derive::actor_handler;
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+// Test the methods rewritten to send and ask.
+async fn test_actor_handler() {
+    let counter = AsyncCounter::new();
+
+    counter.incr().await.unwrap();
+    counter.incr().await.unwrap();
+    counter.add(5).await.unwrap();
+    counter.decr().await.unwrap();
+
+    assert_eq!(counter.read_value().await, Ok(6));
+    assert_eq!(counter.name(), "counter");
+}
+
+// --------------------------------------------------------
+// TEST HELPER
+// --------------------------------------------------------
+
+// AsyncCounter is the counter of the actor tests built with the
+// actor handler attribute.
+struct AsyncCounter {
+    actor: Arc<AsyncActor>,
+    value: Arc<Mutex<i32>>,
+}
+
+#[actor_handler(actor_field = "self.actor")]
+impl AsyncCounter {
+    fn new() -> Self {
+        let actor = AsyncActor::new();
+        let value = Arc::new(Mutex::new(0));
+        AsyncCounter { actor, value }
+    }
+
+    #[send]
+    fn incr(&self) {
+        *self.value.lock().unwrap() += 1;
+    }
+
+    #[send]
+    fn decr(&self) {
+        *self.value.lock().unwrap() -= 1;
+    }
+
+    #[send]
+    fn add(&self, n: i32) {
+        *self.value.lock().unwrap() += n;
+    }
+
+    #[ask]
+    fn read_value(&self) -> i32 {
+        *self.value.lock().unwrap()
+    }
+
+    fn name(&self) -> &'static str {
+        "counter"
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,142 @@
+// --------------------------------------------------------
+// Actor library - Benchmarks
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorState, AsyncActor};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// CountingAlloc counts the heap allocations of the benchmarks.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const TASKS_PER_SENDER: usize = 10_000;
+
+#[tokio::main]
+async fn main() {
+    for senders in [1, 4, 16] {
+        let elapsed = concurrent_senders(senders).await;
+        report(
+            "concurrent senders",
+            senders,
+            senders * TASKS_PER_SENDER,
+            elapsed,
+        );
+    }
+    for batch_size in [1, 8, 32] {
+        let elapsed = batched_loop(batch_size).await;
+        report("batch size", batch_size, TASKS_PER_SENDER, elapsed);
+    }
+    for (name, allocations) in [
+        ("send closure", task_allocations(false).await),
+        ("send fn pointer", task_allocations(true).await),
+    ] {
+        println!(
+            "{:<24}      {:>8.2} allocations per task",
+            name,
+            allocations as f64 / TASKS_PER_SENDER as f64
+        );
+    }
+}
+
+// concurrent_senders lets multiple senders check the state and send tasks
+// at the same time, so reading the state is under contention.
+async fn concurrent_senders(senders: usize) -> Duration {
+    let actor = AsyncActor::builder().capacity(1024).build();
+    let start = Instant::now();
+
+    let handles: Vec<_> = (0..senders)
+        .map(|_| {
+            let actor: Arc<AsyncActor> = actor.clone();
+            tokio::spawn(async move {
+                for _ in 0..TASKS_PER_SENDER {
+                    assert_eq!(actor.state(), ActorState::Running);
+                    actor.send(|| Ok(())).await.unwrap();
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.await.unwrap();
+    }
+    actor.ask(|| ()).await.unwrap();
+
+    start.elapsed()
+}
+
+// batched_loop fills the queue of an actor taking batches of the given size
+// and measures the time until all tasks are processed.
+async fn batched_loop(batch_size: usize) -> Duration {
+    let actor = AsyncActor::builder()
+        .capacity(TASKS_PER_SENDER)
+        .batch_size(batch_size)
+        .build();
+    let start = Instant::now();
+
+    for _ in 0..TASKS_PER_SENDER {
+        actor.send(|| Ok(())).await.unwrap();
+    }
+    actor.ask(|| ()).await.unwrap();
+
+    start.elapsed()
+}
+
+// task_allocations counts the heap allocations while sending tasks either as
+// closures capturing a value or as function pointers.
+async fn task_allocations(fn_ptr: bool) -> usize {
+    fn task() -> Result<(), String> {
+        Ok(())
+    }
+
+    let actor = AsyncActor::builder().capacity(TASKS_PER_SENDER).build();
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+
+    for i in 0..TASKS_PER_SENDER {
+        if fn_ptr {
+            actor.send_fn(task).await.unwrap();
+        } else {
+            actor
+                .send(move || {
+                    std::hint::black_box(i);
+                    task()
+                })
+                .await
+                .unwrap();
+        }
+    }
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    actor.ask(|| ()).await.unwrap();
+
+    allocations
+}
+
+// report prints the throughput of a benchmark run.
+fn report(name: &str, param: usize, tasks: usize, elapsed: Duration) {
+    let per_sec = tasks as f64 / elapsed.as_secs_f64();
+    println!(
+        "{:<24} {:>4}: {:>8} tasks in {:>10.3?} ({:>12.0} tasks/s)",
+        name, param, tasks, elapsed, per_sec
+    );
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,70 @@
+// --------------------------------------------------------
+// Actor library - Counter example
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+//! A counter using an AsyncActor as field. All changes of the value run on
+//! the actor, so they are applied one after another.
+
+use actor::{ActorError, AsyncActor};
+use std::sync::{Arc, Mutex};
+
+/// AsyncCounter keeps its value behind the actor.
+struct AsyncCounter {
+    actor: Arc<AsyncActor>,
+    value: Arc<Mutex<i32>>,
+}
+
+impl AsyncCounter {
+    fn new() -> Self {
+        Self {
+            actor: AsyncActor::builder().name("counter").build(),
+            value: Arc::new(Mutex::new(0)),
+        }
+    }
+
+    async fn incr(&self) -> Result<(), ActorError> {
+        let value = self.value.clone();
+        self.actor
+            .send(move || {
+                *value.lock().unwrap() += 1;
+                Ok(())
+            })
+            .await
+            .map(|_| ())
+    }
+
+    async fn decr(&self) -> Result<(), ActorError> {
+        let value = self.value.clone();
+        self.actor
+            .send(move || {
+                *value.lock().unwrap() -= 1;
+                Ok(())
+            })
+            .await
+            .map(|_| ())
+    }
+
+    /// Reads the value after all changes sent before.
+    async fn value(&self) -> Result<i32, ActorError> {
+        let value = self.value.clone();
+        self.actor.ask(move || *value.lock().unwrap()).await
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), ActorError> {
+    let counter = AsyncCounter::new();
+    for _ in 0..3 {
+        counter.incr().await?;
+    }
+    counter.decr().await?;
+
+    println!("counter value: {}", counter.value().await?);
+    println!("processed tasks: {}", counter.actor.stats().tasks_processed);
+    counter.actor.stop().await
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,38 @@
+// --------------------------------------------------------
+// Actor library - Pipeline example
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+//! A three-stage pipeline parsing lines, squaring the numbers, and formatting
+//! them. Each stage runs on its own actor, the outputs are consumed as stream.
+
+use actor::{ActorError, PipelineActor, StreamExt};
+
+#[tokio::main]
+async fn main() -> Result<(), ActorError> {
+    let (sink, output) = PipelineActor::new()
+        .stage(|line: String| {
+            line.trim()
+                .parse::<i64>()
+                .map_err(|err| ActorError::Task(format!("invalid line {:?}: {}", line, err)))
+        })
+        .stage(|n| Ok(n * n))
+        .stage(|n| Ok(format!("squared: {}", n)))
+        .build();
+
+    for line in ["1", " 2", "3 ", "4"] {
+        sink.send(line.to_string()).await?;
+    }
+    // Dropping the sink ends the stream after the last output.
+    drop(sink);
+
+    let mut outputs = output.into_stream();
+    while let Some(output) = outputs.next().await {
+        println!("{}", output?);
+    }
+    Ok(())
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,46 @@
+// --------------------------------------------------------
+// Actor library - Actor pool example
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+//! Distributing work over a pool of actors. Independent work is mapped in
+//! parallel and reduced, work per key keeps its order on one actor.
+
+use actor::{ActorError, AsyncActorPool};
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() -> Result<(), ActorError> {
+    let pool = AsyncActorPool::new(4);
+
+    // Sum the squares of the numbers, mapped in parallel on the actors.
+    let sum = pool
+        .map_reduce(
+            (1..=10u64).collect(),
+            |n| n * n,
+            |squares| squares.iter().sum(),
+        )
+        .await?;
+    println!("sum of squares: {}", sum);
+
+    // The tasks of each account are processed in order on the same actor.
+    for (account, amount) in [("alice", 10), ("bob", 5), ("alice", -3), ("bob", 7)] {
+        pool.send_sticky(
+            move || {
+                println!("booking {} for {}", amount, account);
+                Ok(())
+            },
+            account,
+        )
+        .await?;
+    }
+
+    for result in pool.drain_and_stop(Duration::from_secs(1)).await {
+        result?;
+    }
+    Ok(())
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,46 @@
+// --------------------------------------------------------
+// Actor library - Supervised actor example
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+//! An actor whose failing tasks end it, restarted by a SupervisedActor. The
+//! tasks waiting at the failure are preserved for the restarted actor.
+
+use actor::{ActorError, AsyncActor, MailboxPreservation, SupervisedActor};
+
+// A single thread lets all jobs be sent before the first one runs. Otherwise
+// jobs sent while the restart is running may fail.
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<(), ActorError> {
+    let supervised = SupervisedActor::with_preservation(
+        || AsyncActor::builder().name("worker"),
+        MailboxPreservation::Preserve,
+    );
+
+    for job in 1..=5 {
+        supervised
+            .send(move || {
+                if job == 3 {
+                    return Err(format!("job {} failed", job));
+                }
+                println!("job {} done", job);
+                Ok(())
+            })
+            .await?;
+    }
+
+    // Wait until the restarted actor has processed the preserved jobs.
+    while supervised.restarts() == 0 {
+        tokio::task::yield_now().await;
+    }
+    supervised.actor().ask(|| ()).await?;
+    println!("restarts: {}", supervised.restarts());
+
+    supervised.stop().await?;
+    supervised.actor().join().await;
+    Ok(())
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,86 @@
+// --------------------------------------------------------
+// Actor library - Fuzzing of actor command sequences
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+#![no_main]
+
+use actor::{ActorState, AsyncActor, TaskId};
+use libfuzzer_sys::fuzz_target;
+
+// ActorCommand is one step of a fuzzed command sequence.
+#[derive(Debug)]
+enum ActorCommand {
+    Send,
+    SendFailing,
+    TrySend,
+    Cancel(u8),
+    Stop,
+    StopImmediately,
+    StopAndWait,
+}
+
+impl ActorCommand {
+    // Decodes a command from one byte of the fuzz input.
+    fn decode(byte: u8) -> Self {
+        match byte % 7 {
+            0 => ActorCommand::Send,
+            1 => ActorCommand::SendFailing,
+            2 => ActorCommand::TrySend,
+            3 => ActorCommand::Cancel(byte / 7),
+            4 => ActorCommand::Stop,
+            5 => ActorCommand::StopImmediately,
+            _ => ActorCommand::StopAndWait,
+        }
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        let actor = AsyncActor::builder().capacity(8).build();
+        for &byte in data {
+            // Errors of the commands are expected, only panics are findings.
+            match ActorCommand::decode(byte) {
+                ActorCommand::Send => {
+                    let _ = actor.send(|| Ok(())).await;
+                }
+                ActorCommand::SendFailing => {
+                    let _ = actor.send(|| Err("fuzzed".to_string())).await;
+                }
+                ActorCommand::TrySend => {
+                    let _ = actor.try_send(|| Ok(()));
+                }
+                ActorCommand::Cancel(id) => {
+                    let _ = actor.cancel(TaskId(u64::from(id)));
+                }
+                ActorCommand::Stop => {
+                    let _ = actor.stop().await;
+                }
+                ActorCommand::StopImmediately => {
+                    let _ = actor.stop_immediately();
+                }
+                ActorCommand::StopAndWait => {
+                    let _ = actor.stop().await;
+                    actor.join().await;
+                }
+            }
+            tokio::task::yield_now().await;
+        }
+
+        // Only an actor halted by a failed task has an error message.
+        let state = actor.state();
+        assert!(matches!(
+            state,
+            ActorState::Running | ActorState::Stopped | ActorState::Error
+        ));
+        assert_eq!(actor.message().is_some(), state == ActorState::Error);
+    });
+});
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,1359 @@
+// --------------------------------------------------------
+// Actor library - Async actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicU8, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, oneshot, watch, OwnedSemaphorePermit, Semaphore};
+
+use crate::events::BroadcastStream;
+use crate::health::escape_json;
+use crate::mailbox::{Mailbox, PushError};
+use crate::rate_limit::RateLimiter;
+use crate::runtime;
+use crate::sampling::TaskSampler;
+use crate::shedding::LatencyShedder;
+use crate::stats::AtomicStats;
+use crate::{
+    ActorError, ActorHandle, ActorHealth, ActorId, ActorSink, ActorStats, AsyncActorBuilder,
+    CancellationToken, ChainedActor, FusedActorHandle, SenderId, Stream, TaskEvent, TaskEventKind,
+    WeakActorRef,
+};
+
+/// Task is a function or closure taking no arguments and returning a Result<(), String>.
+pub type Task = Box<dyn FnOnce() -> Result<(), String> + Send>;
+
+/// TaskId identifies a task sent to an AsyncActor. The IDs of an actor are
+/// increasing in the order the tasks are sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TaskId(pub u64);
+
+impl fmt::Display for TaskId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "task-{}", self.0)
+    }
+}
+
+/// TaskLocation identifies a task by the place in the source it is created
+/// at, e.g. as key in logs. It's created by the `task_id!()` macro, which
+/// counts the calls per location starting at 1, and displayed as
+/// `file:line:call`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TaskLocation {
+    pub file: &'static str,
+    pub line: u32,
+    pub call: u32,
+}
+
+impl fmt::Display for TaskLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.call)
+    }
+}
+
+/// FutureTask is a future awaited by the actor loop as a task.
+pub type FutureTask = Pin<Box<dyn Future<Output = Result<(), ActorError>> + Send>>;
+
+/// ActorTask is a task as it is queued by the actor. Function pointers are
+/// queued as they are, only closures and futures need a heap allocation.
+pub enum ActorTask {
+    /// A plain function, e.g. sent via `send_fn()`.
+    FnPtr(fn() -> Result<(), String>),
+    /// A boxed closure, e.g. sent via `send()`.
+    Closure(Task),
+    /// A boxed future, e.g. sent via `send_future()`, with its own timeout
+    /// overriding the one of the actor.
+    Future(FutureTask, Option<Duration>),
+    /// A boxed closure only run if started before the deadline, e.g. sent
+    /// via `deadline_send()`.
+    Deadline(Instant, Task),
+}
+
+impl ActorTask {
+    /// Runs the task. Futures are awaited, so following tasks wait until they
+    /// are done, or until the timeout has passed.
+    async fn run(self, timeout: Option<Duration>) -> Result<(), ActorError> {
+        match self {
+            ActorTask::FnPtr(f) => f().map_err(ActorError::Task),
+            ActorTask::Closure(task) | ActorTask::Deadline(_, task) => {
+                task().map_err(ActorError::Task)
+            }
+            ActorTask::Future(future, own_timeout) => match own_timeout.or(timeout) {
+                Some(timeout) => runtime::timeout(timeout, future)
+                    .await
+                    .unwrap_or(Err(ActorError::TaskTimeout { elapsed: timeout })),
+                None => future.await,
+            },
+        }
+    }
+
+    /// Returns the heap memory in bytes used by the task.
+    fn heap_size(&self) -> usize {
+        match self {
+            ActorTask::FnPtr(_) => 0,
+            ActorTask::Closure(task) | ActorTask::Deadline(_, task) => {
+                std::mem::size_of_val(&**task)
+            }
+            ActorTask::Future(future, _) => std::mem::size_of_val(&**future),
+        }
+    }
+}
+
+/// ActorState represents the current state of the actor.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActorState {
+    Running,
+    Stopped,
+    Error,
+}
+
+impl ActorState {
+    fn to_u8(&self) -> u8 {
+        match self {
+            ActorState::Running => 0,
+            ActorState::Stopped => 1,
+            ActorState::Error => 2,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => ActorState::Running,
+            1 => ActorState::Stopped,
+            _ => ActorState::Error,
+        }
+    }
+}
+
+/// AtomicState holds the ActorState without locking.
+pub(crate) struct AtomicState(AtomicU8);
+
+impl AtomicState {
+    pub(crate) fn new(state: ActorState) -> Self {
+        Self(AtomicU8::new(state.to_u8()))
+    }
+
+    /// Loads the current state.
+    pub(crate) fn load(&self) -> ActorState {
+        ActorState::from_u8(self.0.load(Ordering::SeqCst))
+    }
+
+    /// Changes the state from `current` to `new`. Returns false if the state
+    /// has not been `current`.
+    pub(crate) fn transition(&self, current: ActorState, new: ActorState) -> bool {
+        self.0
+            .compare_exchange(
+                current.to_u8(),
+                new.to_u8(),
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            )
+            .is_ok()
+    }
+}
+
+/// StopReason tells why an actor ended.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StopReason {
+    /// The actor has been stopped by calling `stop()`.
+    Requested,
+    /// A task failed with the contained error.
+    Error(ActorError),
+    /// The actor has been stopped by calling `stop_immediately()`.
+    Forced,
+    /// The actor ran out of its time.
+    Timeout,
+}
+
+/// ErrorStrategy defines how the actor reacts on a task returning an error.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ErrorStrategy {
+    /// The actor stops processing tasks and changes into the error state.
+    #[default]
+    Halt,
+    /// The actor ignores the error and continues with the next task.
+    Continue,
+}
+
+/// OverflowPolicy defines what happens when a task is sent while the queue of
+/// the actor is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum OverflowPolicy {
+    /// Sending waits until there is room in the queue.
+    #[default]
+    Block,
+    /// The oldest task in the queue is dropped to make room.
+    DropOldest,
+    /// The sent task is dropped.
+    DropNewest,
+    /// Sending fails with `ActorError::Full`.
+    Error,
+}
+
+/// OverflowInfo describes a task dropped due to a full queue. It's passed to
+/// the callback set with `AsyncActorBuilder::on_overflow()`.
+#[derive(Debug, Clone)]
+pub struct OverflowInfo {
+    /// When the task has been dropped.
+    pub dropped_at: Instant,
+    /// The number of tasks in the queue at that time.
+    pub queue_depth: usize,
+    /// The name of the actor if one has been set.
+    pub actor_name: Option<String>,
+}
+
+/// OverflowCallback is called with the info about each dropped task.
+pub(crate) type OverflowCallback = Arc<dyn Fn(OverflowInfo) + Send + Sync>;
+
+/// NEXT_ACTOR_ID is the ID of the next created AsyncActor.
+static NEXT_ACTOR_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Message is passed from the actor to its loop. A task holds its
+/// backpressure permit until the message is dropped.
+enum Message {
+    Task(TaskId, ActorTask, usize, Option<OwnedSemaphorePermit>),
+    Stop(StopReason),
+}
+
+/// CurrentTask tells which task is running and when it has been started. Its
+/// token can be cancelled to ask the task to end early, a cancelled token is
+/// renewed for the next task.
+struct CurrentTask {
+    task_id: Option<TaskId>,
+    started: Option<Instant>,
+    token: CancellationToken,
+}
+
+/// ActorInner contains everything shared between the actor and its loop.
+struct ActorInner {
+    mailbox: Mailbox<Message>,
+    state: AtomicState,
+    message: Mutex<Option<String>>,
+    stop_reason: Mutex<Option<StopReason>>,
+    last_error: Mutex<Option<ActorError>>,
+    started: Instant,
+    queue_memory: AtomicUsize,
+    stats: AtomicStats,
+    events: broadcast::Sender<TaskEvent>,
+    errors: broadcast::Sender<ActorError>,
+    terminal: watch::Sender<ActorState>,
+    preserved: Option<Mutex<Vec<ActorTask>>>,
+    current: Mutex<CurrentTask>,
+    task_timeout: Option<Duration>,
+    shedder: Option<LatencyShedder>,
+    sampler: Option<TaskSampler>,
+    #[cfg(feature = "callgraph")]
+    id: ActorId,
+    #[cfg(feature = "registry")]
+    owner: (ActorId, Option<String>),
+    #[cfg(feature = "task-metrics")]
+    monitor: Arc<crate::metrics::TaskMonitor>,
+}
+
+impl ActorInner {
+    /// Samples the health of the actor.
+    fn health(&self) -> ActorHealth {
+        let queue_depth = self.mailbox.len();
+        ActorHealth {
+            state: self.state.load(),
+            queue_depth,
+            last_error: self.last_error.lock().unwrap().clone(),
+            uptime: self.started.elapsed(),
+            tasks_processed: self.stats.tasks_processed.load(Ordering::Relaxed),
+            is_idle: queue_depth == 0,
+        }
+    }
+
+    /// Emits a task event. Having no subscribers is fine.
+    fn emit(&self, task_id: TaskId, kind: TaskEventKind) {
+        #[cfg(feature = "registry")]
+        crate::registry::TaskRegistry::global().track(
+            self.owner.0,
+            self.owner.1.as_deref(),
+            task_id,
+            &kind,
+        );
+        let _ = self.events.send(TaskEvent { task_id, kind });
+    }
+
+    /// Releases a task which will not be processed.
+    fn release(&self, msg: Message) {
+        if let Message::Task(task_id, _, size, _) = msg {
+            self.queue_memory.fetch_sub(size, Ordering::SeqCst);
+            self.emit(task_id, TaskEventKind::Dropped);
+        }
+    }
+}
+
+/// AsyncActor helps to run tasks asynchronously. Tasks are enqueued and processed
+/// by the actor loop. The actor can be stopped at any time ensuring that all
+/// tasks in the queue are processed before stopping.
+///
+/// Tasks are functions and closures taking no arguments and return a Result<(), String>.
+/// The actor will stop processing tasks if an error is returned. All logical errors
+/// have to be handled by the task itself or in the calling code, e.g. by using the
+/// individual closure's error handling.
+pub struct AsyncActor {
+    id: ActorId,
+    inner: Arc<ActorInner>,
+    overflow_policy: OverflowPolicy,
+    memory_budget: Option<usize>,
+    rate_limiter: Option<RateLimiter>,
+    next_task_id: AtomicU64,
+    next_sender_id: AtomicU64,
+    dependencies: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+    name: Option<String>,
+    on_overflow: Option<OverflowCallback>,
+    backpressure: Option<(Arc<Semaphore>, usize)>,
+}
+
+impl AsyncActor {
+    /// Creates a new AsyncActor with the default configuration.
+    pub fn new() -> Arc<Self> {
+        AsyncActorBuilder::new().build()
+    }
+
+    /// Returns a builder for an individually configured AsyncActor.
+    pub fn builder() -> AsyncActorBuilder {
+        AsyncActorBuilder::new()
+    }
+
+    /// Creates the actor configured by the builder and spawns its loop.
+    pub(crate) fn start(builder: AsyncActorBuilder) -> Arc<Self> {
+        // Entering a configured runtime lets the loop and the watchers spawn
+        // on it and registers the signal handlers with its driver.
+        let _runtime = builder.runtime.as_ref().map(|handle| handle.enter());
+        let (events, _) = broadcast::channel(builder.event_capacity);
+        let (errors, _) = broadcast::channel(builder.event_capacity);
+        let id = ActorId(NEXT_ACTOR_ID.fetch_add(1, Ordering::Relaxed));
+        let inner = Arc::new(ActorInner {
+            mailbox: Mailbox::new(builder.capacity),
+            state: AtomicState::new(ActorState::Running),
+            message: Mutex::new(None),
+            stop_reason: Mutex::new(None),
+            last_error: Mutex::new(None),
+            started: Instant::now(),
+            queue_memory: AtomicUsize::new(0),
+            stats: AtomicStats::default(),
+            events,
+            errors,
+            terminal: watch::channel(ActorState::Running).0,
+            preserved: builder.preserve_on_error.then(|| Mutex::new(Vec::new())),
+            current: Mutex::new(CurrentTask {
+                task_id: None,
+                started: None,
+                token: CancellationToken::new(),
+            }),
+            task_timeout: builder.task_timeout,
+            shedder: builder.shed_latency.map(LatencyShedder::new),
+            sampler: builder.sample_rate.map(TaskSampler::new),
+            #[cfg(feature = "callgraph")]
+            id,
+            #[cfg(feature = "registry")]
+            owner: (id, builder.name.clone()),
+            #[cfg(feature = "task-metrics")]
+            monitor: crate::metrics::TaskMonitor::new(),
+        });
+
+        let actor_loop = run(
+            inner.clone(),
+            builder.error_strategy,
+            builder.yield_every,
+            builder.batch_size,
+        );
+        #[cfg(feature = "task-metrics")]
+        let actor_loop = inner.monitor.instrument(actor_loop);
+        runtime::spawn(actor_loop);
+
+        let actor = Arc::new(Self {
+            id,
+            inner,
+            overflow_policy: builder.overflow_policy,
+            memory_budget: builder.memory_budget,
+            rate_limiter: builder
+                .rate_limit
+                .map(|(max, window)| RateLimiter::new(max, window, builder.slow_lane_depth)),
+            next_task_id: AtomicU64::new(1),
+            next_sender_id: AtomicU64::new(1),
+            dependencies: builder.dependencies,
+            name: builder.name,
+            on_overflow: builder.on_overflow,
+            backpressure: builder.backpressure.clone(),
+        });
+
+        if builder.shutdown_on_signal {
+            // A failing registration leaves the actor running as without.
+            if let Ok(signal) = runtime::shutdown_signal() {
+                let joined = actor.join();
+                let actor = Arc::downgrade(&actor);
+                runtime::spawn(async move {
+                    tokio::select! {
+                        _ = signal => {
+                            if let Some(actor) = actor.upgrade() {
+                                let _ = actor.stop().await;
+                            }
+                        }
+                        _ = joined => {}
+                    }
+                });
+            }
+        }
+
+        if let Some(token) = &builder.cancel_on {
+            let cancelled = token.cancelled();
+            let joined = actor.join();
+            let actor = Arc::downgrade(&actor);
+            runtime::spawn(async move {
+                tokio::select! {
+                    _ = cancelled => {
+                        if let Some(actor) = actor.upgrade() {
+                            let _ = actor.stop_immediately();
+                        }
+                    }
+                    _ = joined => {}
+                }
+            });
+        }
+
+        actor
+    }
+
+    /// Sends a task to the AsyncActor. The returned ID allows to correlate the
+    /// task with its events or to cancel it.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send<F>(&self, task: F) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        let task_id = self.next_task_id();
+        self.enqueue(task_id, ActorTask::Closure(Box::new(task)))
+            .await
+            .map(|_| task_id)
+    }
+
+    /// Sends a task to the AsyncActor, which has to be started before the
+    /// deadline. Otherwise it is dropped with a `TaskEventKind::Expired` event
+    /// and counted as `tasks_expired`.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn deadline_send<F>(&self, task: F, deadline: Instant) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        let task_id = self.next_task_id();
+        self.enqueue(task_id, ActorTask::Deadline(deadline, Box::new(task)))
+            .await
+            .map(|_| task_id)
+    }
+
+    /// Sends a task to the AsyncActor without waiting. A full queue rejects
+    /// the task with `ActorError::Full` unless the overflow policy drops
+    /// tasks. So it can be used inside of running tasks.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub fn try_send<F>(&self, task: F) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        let task_id = self.next_task_id();
+        self.try_enqueue(task_id, ActorTask::Closure(Box::new(task)))
+            .map(|_| task_id)
+    }
+
+    /// Sends a task to the AsyncActor without waiting and drops it if the
+    /// queue is full or the actor is stopped. Returns the ID unless the task
+    /// has been rejected.
+    pub fn send_or_drop<F>(&self, task: F) -> Option<TaskId>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        self.try_send(task).ok()
+    }
+
+    /// Sends multiple tasks to the AsyncActor at once. They are enqueued in a
+    /// row without tasks of other senders in between, or not at all. The IDs
+    /// are returned in the order of the tasks.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send_batch(&self, tasks: Vec<Task>) -> Result<Vec<TaskId>, ActorError> {
+        let tasks = tasks.into_iter().map(ActorTask::Closure).collect();
+        self.enqueue_batch(tasks).await
+    }
+
+    /// Sends tasks taking a sequence number at once like `send_batch()`. The
+    /// tasks get the numbers `base_seq`, `base_seq + 1`, and so on in their
+    /// order. As the batch is enqueued as a whole or not at all, there's
+    /// nothing to roll back when it is rejected. Tasks already processed are
+    /// not rolled back when a later one of the batch fails.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send_ordered_batch<F>(
+        &self,
+        tasks: Vec<F>,
+        base_seq: u64,
+    ) -> Result<(), ActorError>
+    where
+        F: FnOnce(u64) -> Result<(), String> + Send + 'static,
+    {
+        let tasks = (base_seq..)
+            .zip(tasks)
+            .map(|(seq, task)| ActorTask::Closure(Box::new(move || task(seq))))
+            .collect();
+        self.enqueue_batch(tasks).await.map(|_| ())
+    }
+
+    /// Sends the tasks one after another from synchronous code, waiting while
+    /// the queue is full. It stops at the first task which can't be sent. It
+    /// must not be called from async code, as it blocks the thread.
+    pub fn extend_blocking<I>(&self, tasks: I) -> Result<Vec<TaskId>, ActorError>
+    where
+        I: IntoIterator<Item = Task>,
+    {
+        tasks
+            .into_iter()
+            .map(|task| {
+                let task_id = self.next_task_id();
+                runtime::block_on(self.enqueue(task_id, ActorTask::Closure(task))).map(|_| task_id)
+            })
+            .collect()
+    }
+
+    /// Sends a future to the AsyncActor. The actor loop awaits it like a task,
+    /// so the following tasks start after it is done.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send_future<Fut>(&self, future: Fut) -> Result<TaskId, ActorError>
+    where
+        Fut: Future<Output = Result<(), ActorError>> + Send + 'static,
+    {
+        let task_id = self.next_task_id();
+        self.enqueue(task_id, ActorTask::Future(Box::pin(future), None))
+            .await
+            .map(|_| task_id)
+    }
+
+    /// Sends a future to the AsyncActor like `send_future()`, but with an own
+    /// timeout overriding the task timeout of the actor.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send_with_task_timeout<Fut>(
+        &self,
+        future: Fut,
+        timeout: Duration,
+    ) -> Result<TaskId, ActorError>
+    where
+        Fut: Future<Output = Result<(), ActorError>> + Send + 'static,
+    {
+        let task_id = self.next_task_id();
+        self.enqueue(task_id, ActorTask::Future(Box::pin(future), Some(timeout)))
+            .await
+            .map(|_| task_id)
+    }
+
+    /// Sends a function pointer to the AsyncActor. Other than with `send()`
+    /// the task is queued without a heap allocation.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send_fn(&self, f: fn() -> Result<(), String>) -> Result<TaskId, ActorError> {
+        let task_id = self.next_task_id();
+        self.enqueue(task_id, ActorTask::FnPtr(f))
+            .await
+            .map(|_| task_id)
+    }
+
+    /// Sends a task like `send()` which is never skipped by the sample rate,
+    /// e.g. as someone waits for its answer.
+    pub(crate) async fn send_unsampled<F>(&self, task: F) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        let task_id = self.next_task_id();
+        if let Some(sampler) = &self.inner.sampler {
+            sampler.exempt(task_id);
+        }
+        let result = self
+            .enqueue(task_id, ActorTask::Closure(Box::new(task)))
+            .await;
+        if let (Err(_), Some(sampler)) = (&result, &self.inner.sampler) {
+            sampler.unexempt(task_id);
+        }
+        result.map(|_| task_id)
+    }
+
+    /// Sends a function to the AsyncActor and waits for its result.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn ask<F, R>(&self, f: F) -> Result<R, ActorError>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (responder, response) = oneshot::channel();
+        self.send_unsampled(move || {
+            let _ = responder.send(f());
+            Ok(())
+        })
+        .await?;
+        // The responder is dropped unanswered if the actor ends before.
+        response.await.map_err(|_| match self.state() {
+            ActorState::Error => ActorError::Task(self.message().unwrap_or_default()),
+            _ => ActorError::Stopped,
+        })
+    }
+
+    /// Returns a new handle for sending tasks to the actor. Each handle gets its
+    /// own sender ID, clones of the handle share it.
+    pub fn handle(self: &Arc<Self>) -> ActorHandle {
+        let sender_id = SenderId(self.next_sender_id.fetch_add(1, Ordering::Relaxed));
+        ActorHandle::new(self.clone(), sender_id)
+    }
+
+    /// Returns a handle sending tasks to the actor, which stops the actor after
+    /// `n` of them completed successfully.
+    pub fn fuse(self: &Arc<Self>, n: usize) -> FusedActorHandle {
+        FusedActorHandle::new(self.clone(), n)
+    }
+
+    /// Returns a weak reference to the actor, which doesn't keep it alive.
+    pub fn downgrade(self: &Arc<Self>) -> WeakActorRef {
+        WeakActorRef::new(Arc::downgrade(self))
+    }
+
+    /// Cancels the task if it is still waiting in the queue. Returns false if
+    /// the task already started or is unknown.
+    pub fn cancel(&self, task_id: TaskId) -> bool {
+        let removed = self
+            .inner
+            .mailbox
+            .remove_first(|msg| matches!(msg, Message::Task(id, ..) if *id == task_id));
+        match removed {
+            Some(Message::Task(_, _, size, _)) => {
+                self.inner.queue_memory.fetch_sub(size, Ordering::SeqCst);
+                AtomicStats::incr(&self.inner.stats.tasks_cancelled);
+                self.inner.emit(task_id, TaskEventKind::Cancelled);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Removes up to `n` of the newest tasks waiting in the queue with their
+    /// IDs, e.g. to move them to another actor.
+    pub(crate) fn take_queued(&self, n: usize) -> Vec<(TaskId, ActorTask)> {
+        let taken = self
+            .inner
+            .mailbox
+            .take_newest(n, |msg| matches!(msg, Message::Task(..)));
+        taken
+            .into_iter()
+            .filter_map(|msg| match msg {
+                Message::Task(task_id, task, size, _) => {
+                    self.inner.queue_memory.fetch_sub(size, Ordering::SeqCst);
+                    self.inner.emit(task_id, TaskEventKind::Moved);
+                    Some((task_id, task))
+                }
+                Message::Stop(_) => None,
+            })
+            .collect()
+    }
+
+    /// Counts an accepted task which got lost, e.g. when moving or forwarding
+    /// it to the queue failed.
+    pub(crate) fn drop_lost(&self, task_id: TaskId) {
+        AtomicStats::incr(&self.inner.stats.tasks_dropped);
+        self.inner.emit(task_id, TaskEventKind::Dropped);
+    }
+
+    /// Takes the tasks kept after the loop ended with an error, if the actor
+    /// has been built to preserve them.
+    pub(crate) fn take_preserved(&self) -> Vec<ActorTask> {
+        match &self.inner.preserved {
+            Some(preserved) => std::mem::take(&mut *preserved.lock().unwrap()),
+            None => Vec::new(),
+        }
+    }
+
+    /// Allocates the ID for the next task.
+    pub(crate) fn next_task_id(&self) -> TaskId {
+        TaskId(self.next_task_id.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Enqueues a task with its ID into the actor loop.
+    pub(crate) async fn enqueue(&self, task_id: TaskId, task: ActorTask) -> Result<(), ActorError> {
+        let permit = self.acquire_permits(1).await?;
+        if self.overflow_policy != OverflowPolicy::Block {
+            return self.push_now(task_id, task, permit);
+        }
+        let msg = self.admit(task_id, task, permit)?;
+        let result = self.inner.mailbox.push(msg).await;
+        self.settle(result)
+    }
+
+    /// Enqueues a task with its ID into the actor loop without waiting. With
+    /// `OverflowPolicy::Block` a full queue rejects the task like with
+    /// `OverflowPolicy::Error`.
+    pub(crate) fn try_enqueue(&self, task_id: TaskId, task: ActorTask) -> Result<(), ActorError> {
+        let permit = match &self.backpressure {
+            Some((semaphore, _)) => Some(semaphore.clone().try_acquire_owned().map_err(|_| {
+                self.overflowed();
+                ActorError::Full
+            })?),
+            None => None,
+        };
+        self.push_now(task_id, task, permit)
+    }
+
+    /// Waits for `n` backpressure permits if the actor has a semaphore. More
+    /// permits than the semaphore has are rejected with `ActorError::Full`.
+    async fn acquire_permits(&self, n: usize) -> Result<Option<OwnedSemaphorePermit>, ActorError> {
+        match &self.backpressure {
+            Some((_, permits)) if n > *permits => Err(ActorError::Full),
+            Some((semaphore, _)) => semaphore
+                .clone()
+                .acquire_many_owned(n as u32)
+                .await
+                .map(Some)
+                .map_err(|_| ActorError::Stopped),
+            None => Ok(None),
+        }
+    }
+
+    /// Pushes a task into the mailbox without waiting as defined by the
+    /// overflow policy.
+    fn push_now(
+        &self,
+        task_id: TaskId,
+        task: ActorTask,
+        permit: Option<OwnedSemaphorePermit>,
+    ) -> Result<(), ActorError> {
+        let inner = &self.inner;
+        let msg = self.admit(task_id, task, permit)?;
+        let result = match self.overflow_policy {
+            OverflowPolicy::DropOldest => {
+                match inner
+                    .mailbox
+                    .push_evicting(msg, |msg| matches!(msg, Message::Task(..)))
+                {
+                    Ok(Some(evicted)) => {
+                        self.dropped(evicted);
+                        Ok(())
+                    }
+                    Ok(None) => Ok(()),
+                    Err(err) => Err(err),
+                }
+            }
+            OverflowPolicy::DropNewest => match inner.mailbox.try_push(msg) {
+                Err(PushError::Full(msg)) => {
+                    self.dropped(msg);
+                    return Ok(());
+                }
+                result => result,
+            },
+            OverflowPolicy::Block | OverflowPolicy::Error => inner.mailbox.try_push(msg),
+        };
+        self.settle(result)
+    }
+
+    /// Checks the state and reserves the memory for a new task. It returns the
+    /// message to push into the mailbox.
+    fn admit(
+        &self,
+        task_id: TaskId,
+        task: ActorTask,
+        permit: Option<OwnedSemaphorePermit>,
+    ) -> Result<Message, ActorError> {
+        self.check_state()?;
+        self.check_latency()?;
+
+        // Account the heap memory of the boxed task, it's released again by the
+        // actor loop when starting the task.
+        let size = task.heap_size();
+        self.reserve_memory(size)?;
+
+        // The event is emitted before sending the task to the actor loop so
+        // that it always precedes the events of the loop.
+        self.inner.emit(task_id, TaskEventKind::Enqueued);
+        Ok(Message::Task(task_id, task, size, permit))
+    }
+
+    /// Counts a pushed task or releases a rejected one. Rejecting it due to a
+    /// full queue calls the overflow callback.
+    fn settle(&self, result: Result<(), PushError<Message>>) -> Result<(), ActorError> {
+        let inner = &self.inner;
+        match result {
+            Ok(()) => {
+                AtomicStats::incr(&inner.stats.tasks_enqueued);
+                #[cfg(feature = "callgraph")]
+                crate::callgraph::CallGraphRecorder::global().record(self.id);
+                Ok(())
+            }
+            Err(PushError::Full(msg)) => {
+                inner.release(msg);
+                self.overflowed();
+                Err(ActorError::Full)
+            }
+            Err(PushError::Closed(msg)) => {
+                inner.release(msg);
+                Err(ActorError::Stopped)
+            }
+        }
+    }
+
+    /// Enqueues the tasks in a row, so that no task of another sender gets in
+    /// between. With `OverflowPolicy::Block` it waits until the queue has room
+    /// for all of them, with the other policies a full queue rejects the whole
+    /// batch with `ActorError::Full`.
+    async fn enqueue_batch(&self, tasks: Vec<ActorTask>) -> Result<Vec<TaskId>, ActorError> {
+        let inner = &self.inner;
+        self.check_state()?;
+        self.check_latency()?;
+
+        // The permits of the whole batch are released with its last task.
+        let mut permit = self.acquire_permits(tasks.len()).await?;
+        let sizes: Vec<usize> = tasks.iter().map(ActorTask::heap_size).collect();
+        self.reserve_memory(sizes.iter().sum())?;
+
+        let mut task_ids = Vec::with_capacity(tasks.len());
+        let mut msgs = Vec::with_capacity(tasks.len());
+        let last = tasks.len().saturating_sub(1);
+        for (i, (task, size)) in tasks.into_iter().zip(sizes).enumerate() {
+            let task_id = self.next_task_id();
+            inner.emit(task_id, TaskEventKind::Enqueued);
+            task_ids.push(task_id);
+            let permit = if i == last { permit.take() } else { None };
+            msgs.push(Message::Task(task_id, task, size, permit));
+        }
+        let result = match self.overflow_policy {
+            OverflowPolicy::Block => inner.mailbox.push_all(msgs).await,
+            _ => inner.mailbox.try_push_all(msgs),
+        };
+        match result {
+            Ok(()) => {
+                for _ in &task_ids {
+                    AtomicStats::incr(&inner.stats.tasks_enqueued);
+                    #[cfg(feature = "callgraph")]
+                    crate::callgraph::CallGraphRecorder::global().record(self.id);
+                }
+                Ok(task_ids)
+            }
+            Err(PushError::Full(msgs)) => {
+                msgs.into_iter().for_each(|msg| inner.release(msg));
+                Err(ActorError::Full)
+            }
+            Err(PushError::Closed(msgs)) => {
+                msgs.into_iter().for_each(|msg| inner.release(msg));
+                Err(ActorError::Stopped)
+            }
+        }
+    }
+
+    /// Drops the oldest waiting task like `OverflowPolicy::DropOldest` does.
+    /// Returns false if no task is waiting.
+    pub(crate) fn drop_oldest(&self) -> bool {
+        match self
+            .inner
+            .mailbox
+            .remove_first(|msg| matches!(msg, Message::Task(..)))
+        {
+            Some(msg) => {
+                self.dropped(msg);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Checks if the actor sheds new tasks due to slow ones.
+    fn check_latency(&self) -> Result<(), ActorError> {
+        match &self.inner.shedder {
+            Some(shedder) => shedder.check(),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks the current state before enqueuing new tasks.
+    fn check_state(&self) -> Result<(), ActorError> {
+        match self.inner.state.load() {
+            ActorState::Running => {}
+            ActorState::Stopped => return Err(ActorError::Stopped),
+            ActorState::Error => {
+                if let Some(msg) = &*self.inner.message.lock().unwrap() {
+                    return Err(ActorError::Task(msg.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Releases a task dropped due to the overflow policy.
+    fn dropped(&self, msg: Message) {
+        AtomicStats::incr(&self.inner.stats.tasks_dropped);
+        self.inner.release(msg);
+        self.overflowed();
+    }
+
+    /// Calls the overflow callback if one is set.
+    fn overflowed(&self) {
+        if let Some(on_overflow) = &self.on_overflow {
+            on_overflow(OverflowInfo {
+                dropped_at: Instant::now(),
+                queue_depth: self.inner.mailbox.len(),
+                actor_name: self.name.clone(),
+            });
+        }
+    }
+
+    /// Adds the size of a task to the queue memory if it fits into the budget.
+    fn reserve_memory(&self, size: usize) -> Result<(), ActorError> {
+        let budget = self.memory_budget.unwrap_or(usize::MAX);
+        self.inner
+            .queue_memory
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |used| {
+                used.checked_add(size).filter(|total| *total <= budget)
+            })
+            .map(|_| ())
+            .map_err(|_| ActorError::MemoryBudgetExceeded)
+    }
+
+    /// Returns the per-sender rate limiter if one is configured.
+    pub(crate) fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    /// Retrieves the ID of the AsyncActor.
+    pub fn id(&self) -> ActorId {
+        self.id
+    }
+
+    /// Retrieves the name of the AsyncActor if one has been set.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Retrieves the dependency of the given type registered with
+    /// `AsyncActorBuilder::with_dependency()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no dependency of this type has been registered.
+    pub fn dependency<D: Send + Sync + 'static>(&self) -> Arc<D> {
+        self.dependencies
+            .get(&TypeId::of::<D>())
+            .cloned()
+            .and_then(|dep| dep.downcast::<D>().ok())
+            .unwrap_or_else(|| {
+                panic!(
+                    "no dependency of type {} registered",
+                    std::any::type_name::<D>()
+                )
+            })
+    }
+
+    /// Retrieves the current state of the AsyncActor.
+    pub fn state(&self) -> ActorState {
+        self.inner.state.load()
+    }
+
+    /// Retrieves the ID of the currently running task, e.g. for a task to log
+    /// its own ID. Returns `None` between tasks.
+    pub fn current_task_id(&self) -> Option<TaskId> {
+        self.inner.current.lock().unwrap().task_id
+    }
+
+    /// Retrieves when the currently running task has been started. Returns
+    /// `None` between tasks.
+    pub fn current_task_start(&self) -> Option<Instant> {
+        self.inner.current.lock().unwrap().started
+    }
+
+    /// Retrieves the cancellation token of the currently running task. It's
+    /// cancelled e.g. by a WatchdogActor, long running tasks can check it
+    /// to end early.
+    pub fn current_task_token(&self) -> CancellationToken {
+        self.inner.current.lock().unwrap().token.clone()
+    }
+
+    /// Cancels the token of the currently running task.
+    pub(crate) fn cancel_current_task(&self) {
+        self.inner.current.lock().unwrap().token.cancel();
+    }
+
+    /// Publishes that the currently running task is stuck.
+    pub(crate) fn report_stuck(&self, running: Duration) {
+        if let Some(task_id) = self.current_task_id() {
+            self.inner.emit(task_id, TaskEventKind::Stuck { running });
+        }
+    }
+
+    /// Retrieves the error message of the failed task if the AsyncActor is in
+    /// error state.
+    pub fn message(&self) -> Option<String> {
+        self.inner.message.lock().unwrap().clone()
+    }
+
+    /// Retrieves why the AsyncActor ended. Returns `None` while it's running.
+    pub fn stop_reason(&self) -> Option<StopReason> {
+        self.inner.stop_reason.lock().unwrap().clone()
+    }
+
+    /// Counts a task started after its latency budget.
+    pub(crate) fn count_sla_violation(&self) {
+        AtomicStats::incr(&self.inner.stats.sla_violations);
+    }
+
+    /// Retrieves a snapshot of the statistics of the AsyncActor.
+    pub fn stats(&self) -> ActorStats {
+        self.inner.stats.snapshot()
+    }
+
+    /// Polls the statistics every interval until the predicate holds for them
+    /// and returns them, e.g. in tests waiting for a number of processed
+    /// tasks. Fails with `ActorError::Timeout` if it doesn't hold in time.
+    pub async fn await_condition<P>(
+        &self,
+        pred: P,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<ActorStats, ActorError>
+    where
+        P: Fn(&ActorStats) -> bool + Send + 'static,
+    {
+        runtime::timeout(timeout, async {
+            loop {
+                let stats = self.stats();
+                if pred(&stats) {
+                    return stats;
+                }
+                runtime::sleep(poll_interval).await;
+            }
+        })
+        .await
+        .ok_or(ActorError::Timeout)
+    }
+
+    /// Retrieves a snapshot of how the runtime polled the actor loop.
+    #[cfg(feature = "task-metrics")]
+    pub fn task_metrics(&self) -> crate::TaskMetrics {
+        self.inner.monitor.metrics()
+    }
+
+    /// Samples the health of the AsyncActor. While running the sample is taken
+    /// from within the actor loop, so it's consistent with the processed tasks.
+    pub async fn health(&self) -> ActorHealth {
+        let inner = self.inner.clone();
+        match self.ask(move || inner.health()).await {
+            Ok(health) => health,
+            Err(_) => self.inner.health(),
+        }
+    }
+
+    /// Renders the diagnostics of the actor as a JSON object, e.g. for a debug
+    /// endpoint of a service. Other than `health()` the values are sampled
+    /// directly without passing the actor loop, so they are not a consistent
+    /// snapshot.
+    pub fn diagnostic_json(&self) -> String {
+        let inner = &self.inner;
+        let stats = inner.stats.snapshot();
+        let string_or_null = |value: Option<String>| match value {
+            Some(value) => format!("\"{}\"", escape_json(&value)),
+            None => "null".to_string(),
+        };
+        let last_error = inner.last_error.lock().unwrap().clone();
+        format!(
+            "{{\"id\":{},\"name\":{},\"state\":\"{:?}\",\"uptime_secs\":{:.3},\
+             \"queue_depth\":{},\"capacity\":{},\"tasks_total\":{},\"tasks_failed\":{},\
+             \"tasks_cancelled\":{},\"last_error\":{}}}",
+            self.id.0,
+            string_or_null(self.name.clone()),
+            inner.state.load(),
+            inner.started.elapsed().as_secs_f64(),
+            inner.mailbox.len(),
+            inner.mailbox.capacity(),
+            stats.tasks_enqueued,
+            stats.tasks_failed,
+            stats.tasks_cancelled,
+            string_or_null(last_error.map(|err| err.to_string())),
+        )
+    }
+
+    /// Retrieves the number of tasks waiting in the queue.
+    pub fn queue_len(&self) -> usize {
+        self.inner.mailbox.len()
+    }
+
+    /// Retrieves the number of tasks the queue can hold.
+    pub fn capacity(&self) -> usize {
+        self.inner.mailbox.capacity()
+    }
+
+    /// Retrieves the number of tasks dropped due to the overflow policy.
+    pub fn tasks_dropped(&self) -> u64 {
+        self.inner.stats.tasks_dropped.load(Ordering::Relaxed)
+    }
+
+    /// Retrieves the approximate heap memory in bytes used by the tasks waiting
+    /// in the queue.
+    pub fn queue_memory_bytes(&self) -> usize {
+        self.inner.queue_memory.load(Ordering::SeqCst)
+    }
+
+    /// Turns the actor into a sink of tasks, e.g. as the last stage of a
+    /// stream pipeline.
+    pub fn into_sink<F>(self: Arc<Self>) -> ActorSink<F>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        ActorSink::new(self)
+    }
+
+    /// Returns a stream of the lifecycle events of all tasks sent after this call.
+    /// The stream ends when the actor and its loop are gone.
+    pub fn task_stream(&self) -> impl Stream<Item = TaskEvent> + Unpin {
+        BroadcastStream::new(self.inner.events.subscribe())
+    }
+
+    /// Returns a receiver for the errors of all tasks failing after this call.
+    /// The errors are broadcast independent of the error strategy, under
+    /// `ErrorStrategy::Halt` before the actor changes into the error state.
+    pub fn error_stream(&self) -> broadcast::Receiver<ActorError> {
+        self.inner.errors.subscribe()
+    }
+
+    /// Returns a future resolving to the terminal state `Stopped` or `Error`
+    /// once the actor loop has ended. Other than `stop()` it doesn't initiate
+    /// the end, any number of callers may wait concurrently.
+    pub fn join(&self) -> impl Future<Output = ActorState> + Send + 'static {
+        let mut terminal = self.inner.terminal.subscribe();
+        async move {
+            let state = terminal
+                .wait_for(|state| *state != ActorState::Running)
+                .await
+                .map(|state| state.clone());
+            state.unwrap_or_else(|_| terminal.borrow().clone())
+        }
+    }
+
+    /// Returns a ChainedActor sending to this actor first. Once it ended the
+    /// continuation is called with the reason and the returned actor takes over.
+    pub fn then<C>(self: &Arc<Self>, continuation: C) -> Arc<ChainedActor>
+    where
+        C: Fn(StopReason) -> Arc<AsyncActor> + Send + Sync + 'static,
+    {
+        ChainedActor::new(self.clone(), continuation)
+    }
+
+    /// Stops the actor. This method will return immediately while the actor will
+    /// continue processing the remaining tasks in the queue before stopping.
+    /// Tasks sent afterwards are rejected with `ActorError::Stopped`.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn stop(&self) -> Result<(), ActorError> {
+        self.inner
+            .mailbox
+            .push(Message::Stop(StopReason::Requested))
+            .await
+            .map_err(|_| ActorError::Stopped)?;
+        // Tasks sent after stopping would never be processed.
+        self.inner.mailbox.close();
+        Ok(())
+    }
+
+    /// Stops the actor without processing the remaining tasks in the queue,
+    /// they are dropped. Only the currently running task is completed.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub fn stop_immediately(&self) -> Result<(), ActorError> {
+        self.stop_now(StopReason::Forced)
+    }
+
+    /// Stops the actor without processing the remaining tasks for the given
+    /// reason.
+    pub(crate) fn stop_now(&self, reason: StopReason) -> Result<(), ActorError> {
+        if self.state() != ActorState::Running {
+            return Err(ActorError::Stopped);
+        }
+        self.inner.mailbox.push_front_closing(Message::Stop(reason));
+        Ok(())
+    }
+
+    /// Closes the queue without waiting. The loop ends after processing the
+    /// remaining tasks like after `stop()`.
+    pub(crate) fn close_queue(&self) {
+        self.inner.mailbox.close();
+    }
+
+    /// Returns true if the actor still accepts new tasks.
+    pub(crate) fn is_accepting(&self) -> bool {
+        self.state() == ActorState::Running && !self.inner.mailbox.is_closed()
+    }
+}
+
+/// Extending an actor sends the tasks like `send_or_drop()`, so tasks not
+/// fitting into the queue are dropped. As `Extend` takes `&mut self`, it's
+/// implemented for the reference: `(&*actor).extend(tasks)`.
+impl Extend<Task> for &AsyncActor {
+    fn extend<I: IntoIterator<Item = Task>>(&mut self, tasks: I) {
+        for task in tasks {
+            let _ = self.send_or_drop(task);
+        }
+    }
+}
+
+impl Drop for AsyncActor {
+    fn drop(&mut self) {
+        // Let the loop end after the remaining tasks.
+        self.inner.mailbox.close();
+    }
+}
+
+/// Runs the actor loop processing the tasks of the mailbox.
+async fn run(
+    inner: Arc<ActorInner>,
+    error_strategy: ErrorStrategy,
+    yield_every: usize,
+    batch_size: usize,
+) {
+    let mut unyielded: usize = 0;
+    'batches: while let Some(first) = inner.mailbox.pop().await {
+        // Give other Tokio tasks the chance to run between two batches.
+        if yield_every > 0 && unyielded >= yield_every {
+            runtime::yield_now().await;
+            unyielded = 0;
+        }
+
+        // Drain up to batch_size tasks without waiting for the mailbox.
+        let mut next = Some(first);
+        let mut taken = 1;
+        while let Some(msg) = next.take() {
+            unyielded += 1;
+            if !process(&inner, msg, error_strategy).await {
+                break 'batches;
+            }
+            if taken < batch_size {
+                next = inner.mailbox.try_pop();
+                taken += 1;
+            }
+        }
+    }
+
+    // Tasks still waiting in the queue will never be processed. After an
+    // error they may be kept for a restarted actor.
+    inner.mailbox.close();
+    let preserved = inner
+        .preserved
+        .as_ref()
+        .filter(|_| inner.state.load() == ActorState::Error);
+    while let Some(msg) = inner.mailbox.try_pop() {
+        match (msg, preserved) {
+            (Message::Task(task_id, task, size, _), Some(preserved)) => {
+                inner.queue_memory.fetch_sub(size, Ordering::SeqCst);
+                inner.emit(task_id, TaskEventKind::Moved);
+                preserved.lock().unwrap().push(task);
+            }
+            (msg, _) => inner.release(msg),
+        }
+    }
+
+    // A loop ended by dropping the actor counts as stopped.
+    inner
+        .stop_reason
+        .lock()
+        .unwrap()
+        .get_or_insert(StopReason::Requested);
+    inner
+        .state
+        .transition(ActorState::Running, ActorState::Stopped);
+    inner.terminal.send_replace(inner.state.load());
+}
+
+/// Processes one message of the mailbox. Returns false if the loop has to end.
+async fn process(inner: &ActorInner, msg: Message, error_strategy: ErrorStrategy) -> bool {
+    // The permit is kept until the task is done.
+    let (task_id, task, _permit) = match msg {
+        Message::Task(task_id, task, size, permit) => {
+            inner.queue_memory.fetch_sub(size, Ordering::SeqCst);
+            (task_id, task, permit)
+        }
+        Message::Stop(reason) => {
+            inner.stop_reason.lock().unwrap().get_or_insert(reason);
+            inner
+                .state
+                .transition(ActorState::Running, ActorState::Stopped);
+            return false;
+        }
+    };
+
+    let started = Instant::now();
+    if let ActorTask::Deadline(deadline, _) = task {
+        if started > deadline {
+            AtomicStats::incr(&inner.stats.tasks_expired);
+            inner.emit(task_id, TaskEventKind::Expired);
+            return true;
+        }
+    }
+    if inner
+        .sampler
+        .as_ref()
+        .is_some_and(|sampler| !sampler.keep(task_id))
+    {
+        AtomicStats::incr(&inner.stats.tasks_sampled_out);
+        inner.emit(task_id, TaskEventKind::Dropped);
+        return true;
+    }
+    {
+        let mut current = inner.current.lock().unwrap();
+        current.task_id = Some(task_id);
+        current.started = Some(started);
+        if current.token.is_cancelled() {
+            current.token = CancellationToken::new();
+        }
+    }
+    inner.emit(task_id, TaskEventKind::Started);
+    // Sends of the task are recorded as coming from this actor.
+    #[cfg(feature = "callgraph")]
+    let result =
+        crate::callgraph::CallGraphRecorder::scope(inner.id, task.run(inner.task_timeout)).await;
+    #[cfg(not(feature = "callgraph"))]
+    let result = task.run(inner.task_timeout).await;
+    {
+        let mut current = inner.current.lock().unwrap();
+        current.task_id = None;
+        current.started = None;
+    }
+    let duration = started.elapsed();
+    AtomicStats::observe(&inner.stats.max_observed_duration, duration);
+    if let Some(shedder) = &inner.shedder {
+        shedder.observe(duration);
+    }
+    match result {
+        Ok(()) => {
+            AtomicStats::incr(&inner.stats.tasks_processed);
+            inner.emit(task_id, TaskEventKind::Completed { duration });
+            true
+        }
+        Err(error) => {
+            if let ActorError::TaskTimeout { .. } = error {
+                AtomicStats::incr(&inner.stats.tasks_timed_out);
+            }
+            AtomicStats::incr(&inner.stats.tasks_failed);
+            *inner.last_error.lock().unwrap() = Some(error.clone());
+            let _ = inner.errors.send(error.clone());
+            inner.emit(
+                task_id,
+                TaskEventKind::Failed {
+                    error: error.clone(),
+                },
+            );
+            if error_strategy == ErrorStrategy::Continue {
+                return true;
+            }
+            *inner.message.lock().unwrap() = Some(error.to_string());
+            *inner.stop_reason.lock().unwrap() = Some(StopReason::Error(error));
+            inner
+                .state
+                .transition(ActorState::Running, ActorState::Error);
+            false
+        }
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,113 @@
+// --------------------------------------------------------
+// Actor library - Backoff sender
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::runtime;
+use crate::{ActorError, AsyncActor, CancellationToken, TaskId};
+
+/// BackoffSender sends tasks to an AsyncActor and retries them with a jittered
+/// exponential backoff while the queue is full. This only happens for actors
+/// with an overflow policy rejecting tasks, i.e. `OverflowPolicy::Error`.
+pub struct BackoffSender {
+    actor: Arc<AsyncActor>,
+    initial: Duration,
+    max: Duration,
+    multiplier: f64,
+    jitter: f64,
+    cancel: Option<CancellationToken>,
+}
+
+impl BackoffSender {
+    /// Creates a new BackoffSender starting with 10 ms, doubling the delay up
+    /// to one second, with a jitter of 10 percent.
+    pub fn new(actor: Arc<AsyncActor>) -> Self {
+        Self {
+            actor,
+            initial: Duration::from_millis(10),
+            max: Duration::from_secs(1),
+            multiplier: 2.0,
+            jitter: 0.1,
+            cancel: None,
+        }
+    }
+
+    /// Sets the delay before the first retry.
+    pub fn initial(mut self, initial: Duration) -> Self {
+        self.initial = initial;
+        self
+    }
+
+    /// Sets the maximum delay between two retries.
+    pub fn max(mut self, max: Duration) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Sets the factor the delay grows with after each retry.
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// Sets the jitter. Each delay is multiplied with a random factor between
+    /// `1.0 - jitter` and `1.0 + jitter`.
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Stops retrying when the token is cancelled.
+    pub fn cancel_on(mut self, token: CancellationToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Sends the task and retries on `ActorError::Full`. Each attempt sends a
+    /// clone of the task. All other errors are returned immediately, as well
+    /// as the last `ActorError::Full` if the token is cancelled.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send<F>(&self, task: F) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Clone + Send + 'static,
+    {
+        let mut delay = self.initial;
+        loop {
+            match self.actor.send(task.clone()).await {
+                Err(ActorError::Full) => {}
+                result => return result,
+            }
+            let pause = self.jittered(delay);
+            match &self.cancel {
+                Some(token) => {
+                    if token.is_cancelled() {
+                        return Err(ActorError::Full);
+                    }
+                    tokio::select! {
+                        _ = token.cancelled() => return Err(ActorError::Full),
+                        _ = runtime::sleep(pause) => {}
+                    }
+                }
+                None => runtime::sleep(pause).await,
+            }
+            delay = delay.mul_f64(self.multiplier).min(self.max);
+        }
+    }
+
+    /// Multiplies the delay with a random factor in the range of the jitter.
+    fn jittered(&self, delay: Duration) -> Duration {
+        // Each RandomState is seeded randomly, which is enough for jitter.
+        let random = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+        let factor = 1.0 - self.jitter + 2.0 * self.jitter * random;
+        delay.mul_f64(factor).min(self.max)
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
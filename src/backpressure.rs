@@ -0,0 +1,78 @@
+// --------------------------------------------------------
+// Actor library - Backpressure monitor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+use crate::runtime;
+use crate::{ActorState, AsyncActor};
+
+/// BackpressureState is the sampled queue depth of an actor.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BackpressureState {
+    pub queue_depth: usize,
+    pub capacity: usize,
+    /// Part of the capacity in use, between 0.0 and 1.0.
+    pub fill_ratio: f64,
+}
+
+impl BackpressureState {
+    /// Samples the current state of the actor.
+    fn sample(actor: &AsyncActor) -> Self {
+        let queue_depth = actor.queue_len();
+        let capacity = actor.capacity();
+        let fill_ratio = if capacity == 0 {
+            0.0
+        } else {
+            queue_depth as f64 / capacity as f64
+        };
+        Self {
+            queue_depth,
+            capacity,
+            fill_ratio,
+        }
+    }
+}
+
+/// BackpressureMonitor publishes the queue depth of an actor to a watch
+/// channel, so producers can slow down when the queue fills up without
+/// the actor loop being involved.
+pub struct BackpressureMonitor;
+
+impl BackpressureMonitor {
+    /// Samples the actor every interval and publishes changed states. The
+    /// monitor ends when the actor is no longer running or all receivers
+    /// are dropped.
+    pub fn attach(
+        actor: Arc<AsyncActor>,
+        interval: Duration,
+    ) -> watch::Receiver<BackpressureState> {
+        let (sender, receiver) = watch::channel(BackpressureState::sample(&actor));
+        let weak = Arc::downgrade(&actor);
+        runtime::spawn(async move {
+            loop {
+                runtime::sleep(interval).await;
+                let Some(actor) = weak.upgrade() else {
+                    return;
+                };
+                let sampled = BackpressureState::sample(&actor);
+                sender.send_if_modified(|state| {
+                    let modified = *state != sampled;
+                    *state = sampled;
+                    modified
+                });
+                if sender.is_closed() || actor.state() != ActorState::Running {
+                    return;
+                }
+            }
+        });
+        receiver
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,99 @@
+// --------------------------------------------------------
+// Actor library - Blocking actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::sync::{Arc, Mutex};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::runtime;
+use crate::{ActorError, ActorState};
+
+/// BlockingTask is a CPU-bound or blocking function or closure returning a
+/// Result<(), ActorError>.
+pub type BlockingTask = Box<dyn FnOnce() -> Result<(), ActorError> + Send>;
+
+/// BlockingActor runs tasks sequentially like the AsyncActor, but each one on
+/// a thread reserved for blocking work. So CPU-intensive tasks don't block the
+/// Tokio worker threads. Sending waits for the task and returns its result.
+pub struct BlockingActor {
+    sender: mpsc::Sender<(BlockingTask, oneshot::Sender<Result<(), ActorError>>)>,
+    state: Arc<Mutex<ActorState>>,
+    error: Arc<Mutex<Option<ActorError>>>,
+}
+
+impl BlockingActor {
+    /// Creates a new BlockingActor.
+    pub fn new() -> Arc<Self> {
+        let (sender, mut receiver) =
+            mpsc::channel::<(BlockingTask, oneshot::Sender<Result<(), ActorError>>)>(32);
+        let state = Arc::new(Mutex::new(ActorState::Running));
+        let error = Arc::new(Mutex::new(None));
+
+        let actor = Arc::new(Self {
+            sender,
+            state: state.clone(),
+            error: error.clone(),
+        });
+
+        runtime::spawn(async move {
+            while let Some((task, responder)) = receiver.recv().await {
+                let result = runtime::spawn_blocking(task)
+                    .await
+                    .unwrap_or_else(|| Err(ActorError::Task("Task panicked".to_string())));
+                let failed = result.is_err();
+                if let Err(err) = &result {
+                    *state.lock().unwrap() = ActorState::Error;
+                    *error.lock().unwrap() = Some(err.clone());
+                }
+                let _ = responder.send(result);
+                if failed {
+                    return;
+                }
+            }
+            *state.lock().unwrap() = ActorState::Stopped;
+        });
+
+        actor
+    }
+
+    /// Sends a task to the BlockingActor and waits for its result.
+    pub async fn send<F>(&self, task: F) -> Result<(), ActorError>
+    where
+        F: FnOnce() -> Result<(), ActorError> + Send + 'static,
+    {
+        match *self.state.lock().unwrap() {
+            ActorState::Running => {}
+            ActorState::Stopped => return Err(ActorError::Stopped),
+            ActorState::Error => {
+                if let Some(err) = &*self.error.lock().unwrap() {
+                    return Err(err.clone());
+                }
+            }
+        }
+
+        let (responder, response) = oneshot::channel();
+        self.sender
+            .send((Box::new(task), responder))
+            .await
+            .map_err(|err| ActorError::Send(err.to_string()))?;
+        // The responder is dropped unanswered if an earlier task failed.
+        response
+            .await
+            .unwrap_or_else(|_| Err(self.error().unwrap_or(ActorError::Stopped)))
+    }
+
+    /// Retrieves the current state of the BlockingActor.
+    pub fn state(&self) -> ActorState {
+        self.state.lock().unwrap().clone()
+    }
+
+    /// Retrieves the error of the failed task if the actor is in error state.
+    pub fn error(&self) -> Option<ActorError> {
+        self.error.lock().unwrap().clone()
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
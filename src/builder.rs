@@ -0,0 +1,239 @@
+// --------------------------------------------------------
+// Actor library - Async actor builder
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+
+use crate::async_actor::OverflowCallback;
+use crate::{AsyncActor, CancellationToken, ErrorStrategy, OverflowInfo, OverflowPolicy};
+
+/// AsyncActorBuilder configures and creates an AsyncActor.
+pub struct AsyncActorBuilder {
+    pub(crate) capacity: usize,
+    pub(crate) event_capacity: usize,
+    pub(crate) error_strategy: ErrorStrategy,
+    pub(crate) rate_limit: Option<(usize, Duration)>,
+    pub(crate) slow_lane_depth: usize,
+    pub(crate) memory_budget: Option<usize>,
+    pub(crate) overflow_policy: OverflowPolicy,
+    pub(crate) yield_every: usize,
+    pub(crate) batch_size: usize,
+    pub(crate) shutdown_on_signal: bool,
+    pub(crate) cancel_on: Option<CancellationToken>,
+    pub(crate) dependencies: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+    pub(crate) name: Option<String>,
+    pub(crate) runtime: Option<tokio::runtime::Handle>,
+    pub(crate) on_overflow: Option<OverflowCallback>,
+    pub(crate) preserve_on_error: bool,
+    pub(crate) backpressure: Option<(Arc<Semaphore>, usize)>,
+    pub(crate) task_timeout: Option<Duration>,
+    pub(crate) shed_latency: Option<Duration>,
+    pub(crate) sample_rate: Option<f64>,
+}
+
+impl AsyncActorBuilder {
+    /// Creates a new builder with the default configuration.
+    pub fn new() -> Self {
+        Self {
+            capacity: 32,
+            event_capacity: 64,
+            error_strategy: ErrorStrategy::Halt,
+            rate_limit: None,
+            slow_lane_depth: 32,
+            memory_budget: None,
+            overflow_policy: OverflowPolicy::Block,
+            yield_every: 1,
+            batch_size: 1,
+            shutdown_on_signal: false,
+            cancel_on: None,
+            dependencies: HashMap::new(),
+            name: None,
+            runtime: None,
+            on_overflow: None,
+            preserve_on_error: false,
+            backpressure: None,
+            task_timeout: None,
+            shed_latency: None,
+            sample_rate: None,
+        }
+    }
+
+    /// Sets the number of tasks which can be enqueued before sending waits.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Sets the number of tasks which can be enqueued before sending waits as a
+    /// constant. Other than with `capacity()` a capacity of `0` is rejected at
+    /// compile time.
+    pub fn fixed_capacity<const N: usize>(mut self) -> Self {
+        const { assert!(N > 0, "the capacity of an actor must not be zero") };
+        self.capacity = N;
+        self
+    }
+
+    /// Sets the number of task events and errors buffered for each task and error
+    /// stream. Lagging consumers miss the oldest ones.
+    pub fn event_capacity(mut self, event_capacity: usize) -> Self {
+        self.event_capacity = event_capacity;
+        self
+    }
+
+    /// Sets how the actor reacts on failing tasks. The default is to halt.
+    pub fn error_strategy(mut self, error_strategy: ErrorStrategy) -> Self {
+        self.error_strategy = error_strategy;
+        self
+    }
+
+    /// Limits each sender to `max` tasks per sliding `window`. Tasks sent via an
+    /// `ActorHandle` beyond the limit are delayed in a slow lane of that sender,
+    /// other senders are unaffected. Direct sends to the actor are not limited.
+    pub fn per_sender_rate_limit(mut self, max: usize, window: Duration) -> Self {
+        self.rate_limit = Some((max, window));
+        self
+    }
+
+    /// Sets the number of tasks a rate limited sender can have waiting in its
+    /// slow lane. Further tasks are rejected with `ActorError::RateLimited`.
+    pub fn slow_lane_depth(mut self, depth: usize) -> Self {
+        self.slow_lane_depth = depth;
+        self
+    }
+
+    /// Limits the approximate heap memory in bytes the enqueued tasks may use.
+    /// Sending a task exceeding the budget fails with
+    /// `ActorError::MemoryBudgetExceeded`.
+    pub fn memory_budget(mut self, bytes: usize) -> Self {
+        self.memory_budget = Some(bytes);
+        self
+    }
+
+    /// Sets what happens when a task is sent while the queue is full. The
+    /// default is to wait for room in the queue.
+    pub fn overflow_policy(mut self, overflow_policy: OverflowPolicy) -> Self {
+        self.overflow_policy = overflow_policy;
+        self
+    }
+
+    /// Lets the actor loop yield to other Tokio tasks after every `n` processed
+    /// tasks. `1` yields after each task for maximum fairness, which is the
+    /// default. `0` disables yielding for maximum throughput.
+    pub fn yield_every(mut self, n: usize) -> Self {
+        self.yield_every = n;
+        self
+    }
+
+    /// Lets the actor loop take up to `n` queued tasks at once and process them
+    /// without yielding in between. The yielding configured by `yield_every`
+    /// only happens between two batches. The default of `1` takes one task at
+    /// a time.
+    pub fn batch_size(mut self, n: usize) -> Self {
+        self.batch_size = n.max(1);
+        self
+    }
+
+    /// Lets the actor stop when the process receives SIGTERM or SIGINT, e.g.
+    /// during a graceful termination in Kubernetes. On platforms without Unix
+    /// signals only Ctrl-C is handled.
+    pub fn shutdown_on_signal(mut self) -> Self {
+        self.shutdown_on_signal = true;
+        self
+    }
+
+    /// Lets the actor stop immediately when the token is cancelled. So the
+    /// lifetime of the actor can be bound to the scope owning the token.
+    pub fn cancel_on(mut self, token: CancellationToken) -> Self {
+        self.cancel_on = Some(token);
+        self
+    }
+
+    /// Registers a dependency the tasks can retrieve by its type via
+    /// `AsyncActor::dependency()`. A second dependency of the same type
+    /// replaces the first one.
+    pub fn with_dependency<D: Send + Sync + 'static>(mut self, dep: D) -> Self {
+        self.dependencies.insert(TypeId::of::<D>(), Arc::new(dep));
+        self
+    }
+
+    /// Sets a name identifying the actor in diagnostics.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets a callback called for each task dropped due to a full queue, i.e.
+    /// by the overflow policy or by rejecting it with `ActorError::Full` when
+    /// sending. It's called synchronously by the sender.
+    pub fn on_overflow<C>(mut self, callback: C) -> Self
+    where
+        C: Fn(OverflowInfo) + Send + Sync + 'static,
+    {
+        self.on_overflow = Some(Arc::new(callback));
+        self
+    }
+
+    /// Limits the tasks in flight, queued or running, to `permits`. Sending
+    /// waits for a permit, which is released when the task is done, so a
+    /// producer can't get ahead of the actor by more tasks. A non-waiting
+    /// send without a free permit fails with `ActorError::Full`.
+    pub fn backpressure_semaphore(mut self, permits: usize) -> Self {
+        self.backpressure = Some((Arc::new(Semaphore::new(permits)), permits));
+        self
+    }
+
+    /// Limits the time the async tasks sent via `send_future()` may run. A task
+    /// exceeding it fails with `ActorError::TaskTimeout`. Synchronous tasks
+    /// can't be interrupted and so have no timeout.
+    pub fn task_timeout(mut self, timeout: Duration) -> Self {
+        self.task_timeout = Some(timeout);
+        self
+    }
+
+    /// Sheds load while the 99th percentile of the recent task durations is
+    /// above the target. New tasks are rejected with `ActorError::LoadShedding`
+    /// until it's below 90% of the target again. The window holds the last
+    /// 100 durations of at most 10 seconds ago.
+    pub fn shed_under_latency(mut self, target_p99: Duration) -> Self {
+        self.shed_latency = Some(target_p99);
+        self
+    }
+
+    /// Processes only a random part of the tasks, e.g. for telemetry where
+    /// approximate results are fine. At 0.0 all tasks are skipped, at 1.0,
+    /// the default, all are processed. Skipped tasks emit a `Dropped` event
+    /// and are counted as `tasks_sampled_out`.
+    pub fn sample_rate(mut self, rate: f64) -> Self {
+        self.sample_rate = (rate < 1.0).then_some(rate);
+        self
+    }
+
+    /// Lets the actor run its loop on the runtime of the handle instead of the
+    /// current one, e.g. to isolate tenants or to scope the actor to a test
+    /// runtime. The runtime has to be kept running as long as the actor is
+    /// used.
+    pub fn runtime(mut self, handle: tokio::runtime::Handle) -> Self {
+        self.runtime = Some(handle);
+        self
+    }
+
+    /// Creates the AsyncActor and starts its loop.
+    pub fn build(self) -> Arc<AsyncActor> {
+        AsyncActor::start(self)
+    }
+}
+
+impl Default for AsyncActorBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,81 @@
+// --------------------------------------------------------
+// Actor library - Bulkhead actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::runtime;
+use crate::{ActorError, AsyncActor, TaskId};
+
+/// BulkheadActor forwards tasks to an AsyncActor only with a permit of a
+/// semaphore. The permit is held until the task has been processed or dropped.
+/// Sharing the semaphore between multiple BulkheadActors caps the number of
+/// concurrent tasks of a whole subsystem.
+pub struct BulkheadActor {
+    inner: Arc<AsyncActor>,
+    semaphore: Arc<Semaphore>,
+    timeout: Duration,
+}
+
+impl BulkheadActor {
+    /// Creates a new BulkheadActor failing immediately if no permit is free.
+    pub fn new(inner: Arc<AsyncActor>, semaphore: Arc<Semaphore>) -> Arc<Self> {
+        Self::with_timeout(inner, semaphore, Duration::ZERO)
+    }
+
+    /// Creates a new BulkheadActor waiting up to the timeout for a permit.
+    pub fn with_timeout(
+        inner: Arc<AsyncActor>,
+        semaphore: Arc<Semaphore>,
+        timeout: Duration,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            inner,
+            semaphore,
+            timeout,
+        })
+    }
+
+    /// Sends a task to the inner actor after acquiring a permit. Returns
+    /// `ActorError::BulkheadFull` if none is available in time.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send<F>(&self, task: F) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        let permit = self.acquire().await?;
+        self.inner
+            .send(move || {
+                let result = task();
+                drop(permit);
+                result
+            })
+            .await
+    }
+
+    /// Returns the actor the tasks are forwarded to.
+    pub fn inner(&self) -> &Arc<AsyncActor> {
+        &self.inner
+    }
+
+    /// Acquires a permit within the timeout.
+    async fn acquire(&self) -> Result<OwnedSemaphorePermit, ActorError> {
+        let semaphore = self.semaphore.clone();
+        if self.timeout.is_zero() {
+            return semaphore
+                .try_acquire_owned()
+                .map_err(|_| ActorError::BulkheadFull);
+        }
+        match runtime::timeout(self.timeout, semaphore.acquire_owned()).await {
+            Some(Ok(permit)) => Ok(permit),
+            _ => Err(ActorError::BulkheadFull),
+        }
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
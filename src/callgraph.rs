@@ -0,0 +1,103 @@
+// --------------------------------------------------------
+// Actor library - Call graph
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use crate::ActorId;
+
+tokio::task_local! {
+    static CALLER: ActorId;
+}
+
+/// CallGraphRecorder counts the tasks sent between actors while it is enabled.
+/// Sends from the tasks of an actor or inside of `CallGraphRecorder::scope()`
+/// are recorded for that actor, all others as coming from outside. The result
+/// can be rendered as Graphviz DOT, e.g. to document the architecture based on
+/// a test run.
+pub struct CallGraphRecorder {
+    enabled: AtomicBool,
+    calls: Mutex<HashMap<(Option<ActorId>, ActorId), u64>>,
+}
+
+impl CallGraphRecorder {
+    /// Returns the process wide recorder.
+    pub fn global() -> &'static CallGraphRecorder {
+        static RECORDER: OnceLock<CallGraphRecorder> = OnceLock::new();
+        RECORDER.get_or_init(|| CallGraphRecorder {
+            enabled: AtomicBool::new(false),
+            calls: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Starts recording the sends.
+    pub fn enable() {
+        Self::global().enabled.store(true, Ordering::SeqCst);
+    }
+
+    /// Stops recording the sends. The recorded calls are kept.
+    pub fn disable() {
+        Self::global().enabled.store(false, Ordering::SeqCst);
+    }
+
+    /// Runs the future with all its sends recorded as coming from the caller.
+    pub async fn scope<F: Future>(caller: ActorId, future: F) -> F::Output {
+        CALLER.scope(caller, future).await
+    }
+
+    /// Returns the number of calls from the caller to the callee.
+    pub fn calls(&self, caller: Option<ActorId>, callee: ActorId) -> u64 {
+        let calls = self.calls.lock().unwrap();
+        calls.get(&(caller, callee)).copied().unwrap_or(0)
+    }
+
+    /// Renders the recorded calls as Graphviz DOT.
+    pub fn to_dot(&self) -> String {
+        let mut calls: Vec<_> = self
+            .calls
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(edge, count)| (*edge, *count))
+            .collect();
+        calls.sort();
+
+        let mut dot = String::from("digraph actors {\n");
+        for ((caller, callee), count) in calls {
+            let caller = match caller {
+                Some(ActorId(id)) => format!("actor-{}", id),
+                None => "external".to_string(),
+            };
+            let _ = writeln!(
+                dot,
+                "    \"{}\" -> \"actor-{}\" [label=\"{}\"];",
+                caller, callee.0, count
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Records a send to the callee if the recorder is enabled.
+    pub(crate) fn record(&self, callee: ActorId) {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return;
+        }
+        let caller = CALLER.try_with(|caller| *caller).ok();
+        *self
+            .calls
+            .lock()
+            .unwrap()
+            .entry((caller, callee))
+            .or_insert(0) += 1;
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,104 @@
+// --------------------------------------------------------
+// Actor library - Cancellation
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::watch;
+
+struct TokenInner {
+    cancelled: watch::Sender<bool>,
+    children: Mutex<Vec<CancellationToken>>,
+}
+
+/// CancellationToken signals the cancellation of a scope to everything bound
+/// to it, e.g. actors created with `AsyncActorBuilder::cancel_on()`. Clones
+/// share the cancellation, child tokens are cancelled together with their
+/// parent but not the other way round.
+#[derive(Clone)]
+pub struct CancellationToken {
+    inner: Arc<TokenInner>,
+}
+
+impl CancellationToken {
+    /// Creates a new token which is not cancelled.
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(TokenInner {
+                cancelled: watch::channel(false).0,
+                children: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Creates a child token cancelled together with this one.
+    pub fn child_token(&self) -> CancellationToken {
+        let child = CancellationToken::new();
+        let mut children = self.inner.children.lock().unwrap();
+        if self.is_cancelled() {
+            child.cancel();
+        } else {
+            children.push(child.clone());
+        }
+        child
+    }
+
+    /// Cancels the token and all its children.
+    pub fn cancel(&self) {
+        self.inner.cancelled.send_replace(true);
+        let children = std::mem::take(&mut *self.inner.children.lock().unwrap());
+        for child in children {
+            child.cancel();
+        }
+    }
+
+    /// Returns true if the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        *self.inner.cancelled.borrow()
+    }
+
+    /// Returns a future resolving once the token is cancelled.
+    pub fn cancelled(&self) -> impl Future<Output = ()> + Send + 'static {
+        let mut cancelled = self.inner.cancelled.subscribe();
+        async move {
+            let _ = cancelled.wait_for(|cancelled| *cancelled).await;
+        }
+    }
+
+    /// Returns a guard cancelling the token when it's dropped, e.g. at the end
+    /// of the scope owning it.
+    pub fn drop_guard(self) -> DropGuard {
+        DropGuard { token: Some(self) }
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// DropGuard cancels its token when dropped.
+pub struct DropGuard {
+    token: Option<CancellationToken>,
+}
+
+impl DropGuard {
+    /// Returns the token without cancelling it.
+    pub fn disarm(mut self) -> CancellationToken {
+        self.token.take().unwrap()
+    }
+}
+
+impl Drop for DropGuard {
+    fn drop(&mut self) {
+        if let Some(token) = self.token.take() {
+            token.cancel();
+        }
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,106 @@
+// --------------------------------------------------------
+// Actor library - Chained actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::sync::{Arc, Mutex};
+use tokio::sync::watch;
+
+use crate::runtime;
+use crate::{ActorError, AsyncActor, StopReason, TaskId};
+
+/// ChainedActor passes its tasks to a first AsyncActor until that one ends.
+/// Then a continuation is called with the reason, and the actor it returns
+/// takes over all future tasks. This allows to process in sequential phases,
+/// e.g. a loading phase followed by a serving phase.
+pub struct ChainedActor {
+    current: Arc<Mutex<Arc<AsyncActor>>>,
+    switched: watch::Receiver<bool>,
+}
+
+impl ChainedActor {
+    /// Creates a new ChainedActor starting with the first actor.
+    pub(crate) fn new<C>(first: Arc<AsyncActor>, continuation: C) -> Arc<Self>
+    where
+        C: Fn(StopReason) -> Arc<AsyncActor> + Send + Sync + 'static,
+    {
+        let (switch, switched) = watch::channel(false);
+        let current = Arc::new(Mutex::new(first.clone()));
+        let joined = first.join();
+
+        // Only weak references are kept, so dropping the ChainedActor also
+        // drops the first actor.
+        let first = Arc::downgrade(&first);
+        let next = Arc::downgrade(&current);
+        runtime::spawn(async move {
+            joined.await;
+            let Some(next) = next.upgrade() else {
+                return;
+            };
+            let reason = first
+                .upgrade()
+                .and_then(|first| first.stop_reason())
+                .unwrap_or(StopReason::Requested);
+            let actor = continuation(reason);
+            *next.lock().unwrap() = actor;
+            let _ = switch.send(true);
+        });
+
+        Arc::new(Self { current, switched })
+    }
+
+    /// Sends a task to the actor currently in charge. If the first actor ended
+    /// already, sending waits until the continuation took over.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send<F>(&self, task: F) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        if !self.current().is_accepting() {
+            self.wait_switched().await;
+        }
+        let actor = self.current();
+        // The first actor may end between the check and the send. Then the
+        // task is rejected unprocessed with `ActorError::Stopped`, or with
+        // `ActorError::Task` if the actor ended with an error, and sent once
+        // more to the continuation.
+        let pending = Arc::new(Mutex::new(Some(task)));
+        let sent = pending.clone();
+        let result = actor
+            .send(move || match sent.lock().unwrap().take() {
+                Some(task) => task(),
+                None => Ok(()),
+            })
+            .await;
+        if !matches!(result, Err(ActorError::Stopped) | Err(ActorError::Task(_))) {
+            return result;
+        }
+        self.wait_switched().await;
+        let next = self.current();
+        let task = pending.lock().unwrap().take();
+        match task {
+            Some(task) if !Arc::ptr_eq(&next, &actor) => next.send(task).await,
+            _ => result,
+        }
+    }
+
+    /// Returns the actor currently in charge.
+    pub fn current(&self) -> Arc<AsyncActor> {
+        self.current.lock().unwrap().clone()
+    }
+
+    /// Returns true once the continuation took over.
+    pub fn is_switched(&self) -> bool {
+        *self.switched.borrow()
+    }
+
+    /// Waits until the continuation took over.
+    async fn wait_switched(&self) {
+        let mut switched = self.switched.clone();
+        let _ = switched.wait_for(|switched| *switched).await;
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
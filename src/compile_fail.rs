@@ -0,0 +1,120 @@
+// --------------------------------------------------------
+// Actor library - Compile-fail tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+//! Doctests asserting that tasks must be `Send`, that a unique handle can't be
+//! cloned or used after a move, that a typed actor only takes its message
+//! type, that a fixed capacity can't be zero, and that the results of sending
+//! can't be ignored silently.
+//!
+//! Sending a closure which isn't `Send`:
+//!
+//! ```compile_fail
+//! # async fn run(actor: std::sync::Arc<actor::AsyncActor>) {
+//! let shared = std::rc::Rc::new(42);
+//! let _ = actor.send(move || {
+//!     drop(shared);
+//!     Ok(())
+//! }).await;
+//! # }
+//! ```
+//!
+//! Sending a closure capturing `Send` values compiles fine:
+//!
+//! ```
+//! # async fn run(actor: std::sync::Arc<actor::AsyncActor>) {
+//! let shared = std::sync::Arc::new(42);
+//! let _ = actor.send(move || {
+//!     drop(shared);
+//!     Ok(())
+//! }).await;
+//! # }
+//! ```
+//!
+//! Sending via a `UniqueActorHandle` after moving it:
+//!
+//! ```compile_fail
+//! # async fn run(handle: actor::UniqueActorHandle) {
+//! let owner = handle;
+//! let _ = handle.send(|| Ok(())).await;
+//! # }
+//! ```
+//!
+//! Telling a `TypedActor` a message of another type:
+//!
+//! ```compile_fail
+//! # async fn run(actor: std::sync::Arc<actor::TypedActor<i32>>) {
+//! let _ = actor.tell("42").await;
+//! # }
+//! ```
+//!
+//! Telling it its own message type compiles fine:
+//!
+//! ```
+//! # async fn run(actor: std::sync::Arc<actor::TypedActor<i32>>) {
+//! let _ = actor.tell(42).await;
+//! # }
+//! ```
+//!
+//! Cloning a `UniqueActorHandle`:
+//!
+//! ```compile_fail
+//! # fn run(handle: actor::UniqueActorHandle) {
+//! let copy = handle.clone();
+//! # }
+//! ```
+//!
+//! Building an actor with a fixed capacity of zero:
+//!
+//! ```compile_fail
+//! let builder = actor::AsyncActor::builder().fixed_capacity::<0>();
+//! ```
+//!
+//! A fixed capacity above zero compiles fine:
+//!
+//! ```
+//! let builder = actor::AsyncActor::builder().fixed_capacity::<64>();
+//! ```
+//!
+//! Ignoring the result of `send()`:
+//!
+//! ```compile_fail
+//! #![deny(unused_must_use)]
+//! # async fn run(actor: std::sync::Arc<actor::AsyncActor>) {
+//! actor.send(|| Ok(())).await;
+//! # }
+//! ```
+//!
+//! Ignoring the result of `try_send()`:
+//!
+//! ```compile_fail
+//! #![deny(unused_must_use)]
+//! # fn run(actor: std::sync::Arc<actor::AsyncActor>) {
+//! actor.try_send(|| Ok(()));
+//! # }
+//! ```
+//!
+//! Ignoring the result of `ask()`:
+//!
+//! ```compile_fail
+//! #![deny(unused_must_use)]
+//! # async fn run(actor: std::sync::Arc<actor::AsyncActor>) {
+//! actor.ask(|| 42).await;
+//! # }
+//! ```
+//!
+//! Discarding the results explicitly compiles fine:
+//!
+//! ```
+//! #![deny(unused_must_use)]
+//! # async fn run(actor: std::sync::Arc<actor::AsyncActor>) {
+//! let _ = actor.send(|| Ok(())).await;
+//! let _ = actor.try_send(|| Ok(()));
+//! let _ = actor.ask(|| 42).await;
+//! # }
+//! ```
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
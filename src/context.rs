@@ -0,0 +1,105 @@
+// --------------------------------------------------------
+// Actor library - Context actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+
+use crate::runtime;
+use crate::{ActorError, ActorState};
+
+/// ContextTask is a function or closure taking a context and returning a
+/// Result<(), ActorError>.
+pub type ContextTask<C> = Box<dyn FnOnce(C) -> Result<(), ActorError> + Send>;
+
+/// Message is passed from the actor to its loop.
+enum Message<C> {
+    Task(ContextTask<C>, C),
+    Stop,
+}
+
+/// ContextActor runs tasks sequentially like the AsyncActor. Additionally each
+/// task receives a context value passed alongside the task when sending it, e.g.
+/// a request ID, a tenant ID, or an authentication token. So closures don't
+/// need to capture this per-task data themselves.
+pub struct ContextActor<C: Clone + Send + 'static> {
+    sender: mpsc::Sender<Message<C>>,
+    state: Arc<Mutex<ActorState>>,
+    error: Arc<Mutex<Option<ActorError>>>,
+}
+
+impl<C: Clone + Send + 'static> ContextActor<C> {
+    /// Creates a new ContextActor.
+    pub fn new() -> Arc<Self> {
+        let (sender, mut receiver) = mpsc::channel::<Message<C>>(32);
+        let state = Arc::new(Mutex::new(ActorState::Running));
+        let error = Arc::new(Mutex::new(None));
+
+        let actor = Arc::new(Self {
+            sender,
+            state: state.clone(),
+            error: error.clone(),
+        });
+
+        runtime::spawn(async move {
+            while let Some(msg) = receiver.recv().await {
+                let (task, ctx) = match msg {
+                    Message::Task(task, ctx) => (task, ctx),
+                    Message::Stop => break,
+                };
+                if let Err(err) = task(ctx) {
+                    *state.lock().unwrap() = ActorState::Error;
+                    *error.lock().unwrap() = Some(err);
+                    return;
+                }
+            }
+            *state.lock().unwrap() = ActorState::Stopped;
+        });
+
+        actor
+    }
+
+    /// Sends a task together with its context to the ContextActor.
+    pub async fn send_with_context<F>(&self, task: F, ctx: C) -> Result<(), ActorError>
+    where
+        F: FnOnce(C) -> Result<(), ActorError> + Send + 'static,
+    {
+        match *self.state.lock().unwrap() {
+            ActorState::Running => {}
+            ActorState::Stopped => return Err(ActorError::Stopped),
+            ActorState::Error => {
+                if let Some(err) = &*self.error.lock().unwrap() {
+                    return Err(err.clone());
+                }
+            }
+        }
+
+        self.sender
+            .send(Message::Task(Box::new(task), ctx))
+            .await
+            .map_err(|err| ActorError::Send(err.to_string()))
+    }
+
+    /// Retrieves the current state of the ContextActor.
+    pub fn state(&self) -> ActorState {
+        self.state.lock().unwrap().clone()
+    }
+
+    /// Retrieves the error of the failed task if the actor is in error state.
+    pub fn error(&self) -> Option<ActorError> {
+        self.error.lock().unwrap().clone()
+    }
+
+    /// Stops the actor after the tasks already in the queue are processed.
+    pub async fn stop(&self) -> Result<(), ActorError> {
+        self.sender
+            .send(Message::Stop)
+            .await
+            .map_err(|err| ActorError::Send(err.to_string()))
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,81 @@
+// --------------------------------------------------------
+// Actor library - Dual actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::sync::Arc;
+
+use crate::{ActorError, StateActor};
+
+/// DualActor keeps the states of two StateActors consistent. Coordinated tasks
+/// run on both actors concurrently and are either applied on both or undone
+/// by their compensating tasks.
+pub struct DualActor<A: Send + 'static, B: Send + 'static> {
+    a: Arc<StateActor<A>>,
+    b: Arc<StateActor<B>>,
+}
+
+impl<A: Send + 'static, B: Send + 'static> DualActor<A, B> {
+    /// Creates a new DualActor coordinating the two actors.
+    pub fn new(a: Arc<StateActor<A>>, b: Arc<StateActor<B>>) -> Arc<Self> {
+        Arc::new(Self { a, b })
+    }
+
+    /// Runs the tasks on their actors. A failing task has to leave its state
+    /// unchanged and doesn't stop its actor. If one task fails, the
+    /// compensating task of the other one is sent to its actor to undo it.
+    /// Returns the error of the failed task, the one of `task_a` if both
+    /// failed.
+    pub async fn send_coordinated<FA, CA, FB, CB>(
+        &self,
+        task_a: FA,
+        compensate_a: CA,
+        task_b: FB,
+        compensate_b: CB,
+    ) -> Result<(), ActorError>
+    where
+        FA: FnOnce(&mut A) -> Result<(), ActorError> + Send + 'static,
+        CA: FnOnce(&mut A) + Send + 'static,
+        FB: FnOnce(&mut B) -> Result<(), ActorError> + Send + 'static,
+        CB: FnOnce(&mut B) + Send + 'static,
+    {
+        let (a, b) = tokio::join!(self.a.ask(task_a), self.b.ask(task_b));
+        match (a.and_then(|a| a), b.and_then(|b| b)) {
+            (Ok(()), Ok(())) => Ok(()),
+            (Ok(()), Err(err)) => {
+                let _ = self.a.send(compensate(compensate_a)).await;
+                Err(err)
+            }
+            (Err(err), Ok(())) => {
+                let _ = self.b.send(compensate(compensate_b)).await;
+                Err(err)
+            }
+            (Err(err), Err(_)) => Err(err),
+        }
+    }
+
+    /// Returns the first actor.
+    pub fn a(&self) -> &Arc<StateActor<A>> {
+        &self.a
+    }
+
+    /// Returns the second actor.
+    pub fn b(&self) -> &Arc<StateActor<B>> {
+        &self.b
+    }
+}
+
+/// Turns the compensation into a task of a StateActor.
+fn compensate<S, C>(compensation: C) -> impl FnOnce(&mut S) -> Result<(), ActorError>
+where
+    C: FnOnce(&mut S),
+{
+    move |state| {
+        compensation(state);
+        Ok(())
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
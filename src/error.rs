@@ -0,0 +1,69 @@
+// --------------------------------------------------------
+// Actor library - Errors
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::fmt;
+
+/// ActorError describes the errors returned by the actors of this library.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActorError {
+    /// The actor has been stopped and accepts no more tasks.
+    Stopped,
+    /// A task returned an error. The contained message is the one of the task.
+    Task(String),
+    /// The task could not be passed to the actor loop.
+    Send(String),
+    /// The sender exceeded its rate limit and its slow lane is full.
+    RateLimited,
+    /// Enqueuing the task would exceed the memory budget of the queue.
+    MemoryBudgetExceeded,
+    /// The queue of the actor is full.
+    Full,
+    /// Reading or writing the persisted tasks failed.
+    Persistence(String),
+    /// No permit of the bulkhead has been available in time.
+    BulkheadFull,
+    /// A recursively sent task exceeded the maximum depth.
+    RecursionLimit,
+    /// The fused actor already got all the tasks it processes.
+    Fused,
+    /// The operation didn't complete in time.
+    Timeout,
+    /// The available system memory is below the threshold.
+    MemoryPressure,
+    /// An async task didn't complete within its task timeout.
+    TaskTimeout { elapsed: std::time::Duration },
+    /// The actor sheds new tasks as its recent tasks have been too slow.
+    LoadShedding,
+    /// The queue of the actor is above its high watermark.
+    Watermark,
+}
+
+impl fmt::Display for ActorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ActorError::Stopped => write!(f, "Actor is stopped"),
+            ActorError::Task(msg) => write!(f, "{}", msg),
+            ActorError::Send(msg) => write!(f, "Actor send error: {}", msg),
+            ActorError::RateLimited => write!(f, "Sender exceeds its rate limit"),
+            ActorError::MemoryBudgetExceeded => write!(f, "Actor queue memory budget exceeded"),
+            ActorError::Full => write!(f, "Actor queue is full"),
+            ActorError::Persistence(msg) => write!(f, "Actor persistence error: {}", msg),
+            ActorError::BulkheadFull => write!(f, "Bulkhead has no free permit"),
+            ActorError::RecursionLimit => write!(f, "Recursion depth limit exceeded"),
+            ActorError::Fused => write!(f, "Fused actor accepts no more tasks"),
+            ActorError::Timeout => write!(f, "Actor operation timed out"),
+            ActorError::MemoryPressure => write!(f, "Available system memory is too low"),
+            ActorError::TaskTimeout { elapsed } => write!(f, "Task timed out after {:?}", elapsed),
+            ActorError::LoadShedding => write!(f, "Actor sheds load due to slow tasks"),
+            ActorError::Watermark => write!(f, "Actor queue is above its high watermark"),
+        }
+    }
+}
+
+impl std::error::Error for ActorError {}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
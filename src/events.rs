@@ -0,0 +1,99 @@
+// --------------------------------------------------------
+// Actor library - Task events
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+use crate::{ActorError, Stream, TaskId};
+
+/// TaskEvent notifies about a step in the lifecycle of a task.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskEvent {
+    pub task_id: TaskId,
+    pub kind: TaskEventKind,
+}
+
+/// TaskEventKind tells which step of its lifecycle a task has reached.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaskEventKind {
+    /// The task has been enqueued.
+    Enqueued,
+    /// The actor loop started the task.
+    Started,
+    /// A WatchdogActor found the task running for the given duration, longer
+    /// than allowed.
+    Stuck { running: Duration },
+    /// The task completed successfully after the given duration.
+    Completed { duration: Duration },
+    /// The task returned an error.
+    Failed { error: ActorError },
+    /// The task has been dropped without being processed.
+    Dropped,
+    /// The task has been cancelled before being processed.
+    Cancelled,
+    /// The task has been moved to the queue of another actor.
+    Moved,
+    /// The task has been dropped as it wasn't started before its deadline.
+    Expired,
+}
+
+type RecvResult<T> = (
+    Result<T, broadcast::error::RecvError>,
+    broadcast::Receiver<T>,
+);
+
+type RecvFuture<T> = Pin<Box<dyn Future<Output = RecvResult<T>> + Send>>;
+
+async fn recv_next<T: Clone>(mut receiver: broadcast::Receiver<T>) -> RecvResult<T> {
+    let result = receiver.recv().await;
+    (result, receiver)
+}
+
+/// BroadcastStream turns a broadcast receiver into a stream. Values missed by a
+/// lagging receiver are skipped, the stream ends when the sender is dropped.
+pub(crate) struct BroadcastStream<T> {
+    future: RecvFuture<T>,
+}
+
+impl<T: Clone + Send + 'static> BroadcastStream<T> {
+    pub(crate) fn new(receiver: broadcast::Receiver<T>) -> Self {
+        Self {
+            future: Box::pin(recv_next(receiver)),
+        }
+    }
+}
+
+impl<T: Clone + Send + 'static> Stream for BroadcastStream<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        loop {
+            let (result, receiver) = match self.future.as_mut().poll(cx) {
+                Poll::Ready(ready) => ready,
+                Poll::Pending => return Poll::Pending,
+            };
+            match result {
+                Ok(value) => {
+                    self.future = Box::pin(recv_next(receiver));
+                    return Poll::Ready(Some(value));
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => {
+                    self.future = Box::pin(recv_next(receiver));
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    self.future = Box::pin(recv_next(receiver));
+                    return Poll::Ready(None);
+                }
+            }
+        }
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
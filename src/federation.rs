@@ -0,0 +1,52 @@
+// --------------------------------------------------------
+// Actor library - Federated sender
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::sync::Arc;
+use tokio::runtime::Handle;
+use tokio::sync::oneshot;
+
+use crate::{ActorError, AsyncActor};
+
+/// FederatedSender sends tasks to an AsyncActor living on another Tokio
+/// runtime. The sending runs on the remote runtime, the result is passed
+/// back over a channel working across runtimes. So actors of different
+/// runtimes can communicate without sharing a runtime driver.
+#[derive(Clone)]
+pub struct FederatedSender {
+    remote: Handle,
+    actor: Arc<AsyncActor>,
+}
+
+impl FederatedSender {
+    /// Creates a new FederatedSender for the actor on the remote runtime.
+    pub fn new(remote: Handle, actor: Arc<AsyncActor>) -> Self {
+        Self { remote, actor }
+    }
+
+    /// Sends a task to the actor on the remote runtime. Returns
+    /// `ActorError::Stopped` if the remote runtime is shut down before
+    /// sending.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send<F>(&self, task: F) -> Result<(), ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        let (responder, response) = oneshot::channel();
+        let actor = self.actor.clone();
+        self.remote.spawn(async move {
+            let _ = responder.send(actor.send(task).await.map(|_| ()));
+        });
+        response.await.map_err(|_| ActorError::Stopped)?
+    }
+
+    /// Returns the remote actor.
+    pub fn actor(&self) -> &Arc<AsyncActor> {
+        &self.actor
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
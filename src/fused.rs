@@ -0,0 +1,134 @@
+// --------------------------------------------------------
+// Actor library - Fused actor handle
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+
+use crate::{ActorError, AsyncActor, TaskId};
+
+/// Fuse counts the tasks of a FusedActorHandle.
+struct Fuse {
+    limit: usize,
+    admitted: AtomicUsize,
+    succeeded: AtomicUsize,
+}
+
+impl Fuse {
+    /// Admits one more task if the limit isn't reached yet.
+    fn admit(&self) -> Result<(), ActorError> {
+        self.admitted
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |admitted| {
+                (admitted < self.limit).then_some(admitted + 1)
+            })
+            .map(|_| ())
+            .map_err(|_| ActorError::Fused)
+    }
+
+    /// Gives back the slot of a task which failed or couldn't be sent.
+    fn release(&self) {
+        self.admitted.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    /// Counts a successful task and stops the actor after the last one.
+    fn succeed(&self, actor: &Weak<AsyncActor>) {
+        if self.succeeded.fetch_add(1, Ordering::SeqCst) + 1 == self.limit {
+            if let Some(actor) = actor.upgrade() {
+                actor.close_queue();
+            }
+        }
+    }
+}
+
+/// FusedActorHandle sends tasks to an AsyncActor and stops it after exactly
+/// `n` tasks completed successfully. Sending more tasks than needed to reach
+/// the limit fails with `ActorError::Fused`, failed tasks give their slot back.
+/// It's created with `AsyncActor::fuse()`.
+pub struct FusedActorHandle {
+    actor: Arc<AsyncActor>,
+    fuse: Arc<Fuse>,
+}
+
+impl FusedActorHandle {
+    pub(crate) fn new(actor: Arc<AsyncActor>, limit: usize) -> Self {
+        if limit == 0 {
+            actor.close_queue();
+        }
+        Self {
+            actor,
+            fuse: Arc::new(Fuse {
+                limit,
+                admitted: AtomicUsize::new(0),
+                succeeded: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Sends a task to the actor if the limit isn't reached yet.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send<F>(&self, task: F) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        self.fuse.admit()?;
+        let fuse = self.fuse.clone();
+        let actor = Arc::downgrade(&self.actor);
+        let result = self
+            .actor
+            .send(move || {
+                let result = task();
+                match result {
+                    Ok(()) => fuse.succeed(&actor),
+                    Err(_) => fuse.release(),
+                }
+                result
+            })
+            .await;
+        if result.is_err() {
+            self.fuse.release();
+        }
+        result
+    }
+
+    /// Sends a function to the actor and waits for its result if the limit
+    /// isn't reached yet. The function counts as successful task.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn ask<F, R>(&self, f: F) -> Result<R, ActorError>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        self.fuse.admit()?;
+        let fuse = self.fuse.clone();
+        let actor = Arc::downgrade(&self.actor);
+        let result = self
+            .actor
+            .ask(move || {
+                let result = f();
+                fuse.succeed(&actor);
+                result
+            })
+            .await;
+        // An error means the function hasn't run.
+        if result.is_err() {
+            self.fuse.release();
+        }
+        result
+    }
+
+    /// Returns the number of successful tasks still needed until the actor
+    /// stops.
+    pub fn tasks_remaining(&self) -> usize {
+        self.fuse.limit - self.fuse.succeeded.load(Ordering::SeqCst)
+    }
+
+    /// Returns the fused actor.
+    pub fn actor(&self) -> &Arc<AsyncActor> {
+        &self.actor
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,120 @@
+// --------------------------------------------------------
+// Actor library - Task graph
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+
+use crate::{ActorError, ActorTask, AsyncActor, TaskId};
+
+/// TaskStatus is the state of a task recorded by a TaskGraph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskStatus {
+    /// The task is waiting in the queue.
+    Pending,
+    /// The task is running.
+    Running,
+    /// The task completed successfully.
+    Done,
+    /// The task returned an error.
+    Failed,
+}
+
+impl TaskStatus {
+    /// Returns the name and the fill color of the status in DOT.
+    fn dot_style(self) -> (&'static str, &'static str) {
+        match self {
+            TaskStatus::Pending => ("pending", "white"),
+            TaskStatus::Running => ("running", "yellow"),
+            TaskStatus::Done => ("done", "palegreen"),
+            TaskStatus::Failed => ("failed", "salmon"),
+        }
+    }
+}
+
+/// TaskGraph sends tasks to an AsyncActor together with the IDs of the tasks
+/// they depend on and records their status. The dependencies are only
+/// recorded, the actor still processes the tasks in the order they are sent.
+/// The graph can be exported as Graphviz DOT to debug workflows.
+pub struct TaskGraph {
+    actor: Arc<AsyncActor>,
+    nodes: Arc<Mutex<BTreeMap<TaskId, TaskStatus>>>,
+    edges: Mutex<Vec<(TaskId, TaskId)>>,
+}
+
+impl TaskGraph {
+    /// Creates a new empty TaskGraph for the actor.
+    pub fn new(actor: Arc<AsyncActor>) -> Self {
+        Self {
+            actor,
+            nodes: Arc::new(Mutex::new(BTreeMap::new())),
+            edges: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Sends a task depending on the given tasks to the actor.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send<F>(&self, task: F, depends_on: &[TaskId]) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        let task_id = self.actor.next_task_id();
+        let nodes = self.nodes.clone();
+        nodes.lock().unwrap().insert(task_id, TaskStatus::Pending);
+        let recorded = move || {
+            let set = |status| nodes.lock().unwrap().insert(task_id, status);
+            set(TaskStatus::Running);
+            let result = task();
+            set(match result {
+                Ok(()) => TaskStatus::Done,
+                Err(_) => TaskStatus::Failed,
+            });
+            result
+        };
+        if let Err(err) = self
+            .actor
+            .enqueue(task_id, ActorTask::Closure(Box::new(recorded)))
+            .await
+        {
+            self.nodes.lock().unwrap().remove(&task_id);
+            return Err(err);
+        }
+        let mut edges = self.edges.lock().unwrap();
+        edges.extend(depends_on.iter().map(|dependency| (*dependency, task_id)));
+        Ok(task_id)
+    }
+
+    /// Returns the status of a task sent via the graph.
+    pub fn status(&self, task_id: TaskId) -> Option<TaskStatus> {
+        self.nodes.lock().unwrap().get(&task_id).copied()
+    }
+
+    /// Renders the tasks with their status and the dependencies as Graphviz
+    /// DOT. Edges point from a dependency to the depending task.
+    pub fn to_dot(&self) -> String {
+        let nodes = self.nodes.lock().unwrap().clone();
+        let mut edges = self.edges.lock().unwrap().clone();
+        edges.sort();
+
+        let mut dot = String::from("digraph tasks {\n");
+        for (TaskId(id), status) in nodes {
+            let (label, color) = status.dot_style();
+            let _ = writeln!(
+                dot,
+                "    \"task-{}\" [label=\"{}\\n{}\", style=filled, fillcolor={}];",
+                id, id, label, color
+            );
+        }
+        for (TaskId(from), TaskId(to)) in edges {
+            let _ = writeln!(dot, "    \"task-{}\" -> \"task-{}\";", from, to);
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,62 @@
+// --------------------------------------------------------
+// Actor library - Task group
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::sync::{Arc, Mutex};
+
+use crate::{ActorError, AsyncActor, Task, TaskId};
+
+/// TaskGroup collects related tasks, e.g. all work for one request, and sends
+/// them to an AsyncActor together. The tasks are kept by the group until they
+/// are submitted. A group dropped without submitting discards its tasks.
+pub struct TaskGroup {
+    actor: Arc<AsyncActor>,
+    pending: Mutex<Vec<Task>>,
+}
+
+impl TaskGroup {
+    /// Creates a new empty TaskGroup for the actor.
+    pub fn new(actor: Arc<AsyncActor>) -> Self {
+        Self {
+            actor,
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Adds a task to the group.
+    pub fn add<F>(&self, task: F) -> &Self
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        self.pending.lock().unwrap().push(Box::new(task));
+        self
+    }
+
+    /// Drops all tasks not yet submitted.
+    pub fn cancel_pending(&self) {
+        self.pending.lock().unwrap().clear();
+    }
+
+    /// Returns the number of tasks not yet submitted.
+    pub fn len(&self) -> usize {
+        self.pending.lock().unwrap().len()
+    }
+
+    /// Returns true if the group has no tasks to submit.
+    pub fn is_empty(&self) -> bool {
+        self.pending.lock().unwrap().is_empty()
+    }
+
+    /// Sends all pending tasks to the actor as one batch, so either all or none
+    /// of them are enqueued. Returns the IDs in the order the tasks were added.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn submit(&self) -> Result<Vec<TaskId>, ActorError> {
+        let tasks = std::mem::take(&mut *self.pending.lock().unwrap());
+        self.actor.send_batch(tasks).await
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
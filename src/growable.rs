@@ -0,0 +1,95 @@
+// --------------------------------------------------------
+// Actor library - Growable pool
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::{ActorError, AsyncActor, AsyncActorPool, TaskId};
+
+/// Factory creates the actors of a GrowablePool.
+type Factory = Box<dyn Fn() -> Arc<AsyncActor> + Send + Sync>;
+
+/// GrowablePool is an AsyncActorPool whose number of actors can be changed
+/// at runtime. New actors are created by the factory, removed ones drain
+/// their queued tasks before they stop.
+pub struct GrowablePool {
+    pool: Arc<AsyncActorPool>,
+    factory: Factory,
+    draining: AtomicUsize,
+    target: AtomicUsize,
+}
+
+impl GrowablePool {
+    /// Creates a new pool of `initial` actors of the factory.
+    pub fn new<F>(initial: usize, factory: F) -> Arc<Self>
+    where
+        F: Fn() -> Arc<AsyncActor> + Send + Sync + 'static,
+    {
+        let pool = AsyncActorPool::from_actors((0..initial).map(|_| factory()).collect());
+        Arc::new(Self {
+            pool,
+            factory: Box::new(factory),
+            draining: AtomicUsize::new(0),
+            target: AtomicUsize::new(initial),
+        })
+    }
+
+    /// Adds `n` new actors to the pool.
+    pub fn grow(&self, n: usize) {
+        self.target.fetch_add(n, Ordering::SeqCst);
+        self.pool.push((0..n).map(|_| (self.factory)()));
+    }
+
+    /// Removes the `n` actors with the fewest waiting tasks, or all if the
+    /// pool has less. They get no new tasks from the moment of the call and
+    /// are stopped after their queued tasks. Returns when they are stopped.
+    pub async fn shrink(&self, n: usize) {
+        let removed = self.pool.remove_least_loaded(n);
+        self.target.fetch_sub(removed.len(), Ordering::SeqCst);
+        self.draining.fetch_add(removed.len(), Ordering::SeqCst);
+        // Stop all before waiting for the first one, so they drain in parallel.
+        for actor in &removed {
+            let _ = actor.stop().await;
+        }
+        for actor in &removed {
+            actor.join().await;
+            self.draining.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Sends a task to the next active actor in round-robin order.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send<F>(&self, task: F) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        self.pool.send(task).await
+    }
+
+    /// Returns the number of actors, including the ones still draining.
+    pub fn len(&self) -> usize {
+        self.pool.len() + self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Returns true if the pool has no actors, including draining ones.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of actors the pool has once all draining actors
+    /// are stopped.
+    pub fn target_len(&self) -> usize {
+        self.target.load(Ordering::SeqCst)
+    }
+
+    /// Returns the pool of the active actors, e.g. for other ways of sending.
+    pub fn pool(&self) -> &Arc<AsyncActorPool> {
+        &self.pool
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
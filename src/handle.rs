@@ -0,0 +1,135 @@
+// --------------------------------------------------------
+// Actor library - Actor handle
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::ops::Deref;
+use std::sync::{Arc, Weak};
+
+use crate::{ActorError, ActorTask, AsyncActor, TaskId};
+
+/// ActorId identifies an AsyncActor within the process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ActorId(pub u64);
+
+/// SenderId identifies the sender of tasks using an ActorHandle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SenderId(pub u64);
+
+/// ActorHandle sends tasks to an AsyncActor on behalf of one sender. Handles
+/// are created with `AsyncActor::handle()`, each one gets its own SenderId.
+/// Clones of a handle keep the SenderId and so count as the same sender.
+#[derive(Clone)]
+pub struct ActorHandle {
+    actor: Arc<AsyncActor>,
+    sender_id: SenderId,
+}
+
+impl ActorHandle {
+    pub(crate) fn new(actor: Arc<AsyncActor>, sender_id: SenderId) -> Self {
+        Self { actor, sender_id }
+    }
+
+    /// Returns the ID of the sender.
+    pub fn sender_id(&self) -> SenderId {
+        self.sender_id
+    }
+
+    /// Returns the actor the handle sends to.
+    pub fn actor(&self) -> &Arc<AsyncActor> {
+        &self.actor
+    }
+
+    /// Sends a task to the actor. If a per-sender rate limit is configured and
+    /// the sender exceeded it, the task is delayed in the sender's slow lane.
+    pub async fn send<F>(&self, task: F) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        let task_id = self.actor.next_task_id();
+        let task = Box::new(task);
+        match self.actor.rate_limiter() {
+            None => {
+                self.actor
+                    .enqueue(task_id, ActorTask::Closure(task))
+                    .await?
+            }
+            Some(limiter) => {
+                if let Some(task) = limiter.admit(&self.actor, self.sender_id, task_id, task)? {
+                    let result = self.actor.enqueue(task_id, ActorTask::Closure(task)).await;
+                    if result.is_err() {
+                        limiter.unreserve(self.sender_id);
+                    }
+                    result?;
+                }
+            }
+        }
+        Ok(task_id)
+    }
+}
+
+/// UniqueActorHandle is the single owner of an AsyncActor. It can't be cloned,
+/// sharing the actor requires an explicit call of `share()`. Dropping the
+/// handle stops the actor after the tasks already in its queue, even if
+/// shared references still exist.
+pub struct UniqueActorHandle(Arc<AsyncActor>);
+
+impl UniqueActorHandle {
+    /// Creates the handle owning the actor.
+    pub fn new(actor: Arc<AsyncActor>) -> Self {
+        Self(actor)
+    }
+
+    /// Returns a shareable reference to the actor.
+    pub fn share(&self) -> Arc<AsyncActor> {
+        self.0.clone()
+    }
+}
+
+impl Deref for UniqueActorHandle {
+    type Target = AsyncActor;
+
+    fn deref(&self) -> &AsyncActor {
+        &self.0
+    }
+}
+
+impl Drop for UniqueActorHandle {
+    fn drop(&mut self) {
+        self.0.close_queue();
+    }
+}
+
+/// WeakActorRef references an AsyncActor without keeping it alive. Tasks can
+/// capture it to send further tasks to their own actor without creating a
+/// reference cycle between the actor and its queued tasks.
+#[derive(Clone)]
+pub struct WeakActorRef(Weak<AsyncActor>);
+
+impl WeakActorRef {
+    pub(crate) fn new(actor: Weak<AsyncActor>) -> Self {
+        Self(actor)
+    }
+
+    /// Returns the actor if it still exists.
+    pub fn upgrade(&self) -> Option<Arc<AsyncActor>> {
+        self.0.upgrade()
+    }
+
+    /// Sends a task to the actor without waiting, see `AsyncActor::try_send()`.
+    /// Returns `ActorError::Stopped` if the actor doesn't exist anymore.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub fn try_send<F>(&self, task: F) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        match self.upgrade() {
+            Some(actor) => actor.try_send(task),
+            None => Err(ActorError::Stopped),
+        }
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,101 @@
+// --------------------------------------------------------
+// Actor library - Health
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::{ActorError, ActorState};
+
+/// ActorHealth is a consistent sample of the condition of an actor, e.g. for
+/// liveness and readiness probes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActorHealth {
+    /// The state of the actor.
+    pub state: ActorState,
+    /// Number of tasks waiting in the queue.
+    pub queue_depth: usize,
+    /// The error of the last failed task, if any.
+    pub last_error: Option<ActorError>,
+    /// Time since the actor has been created.
+    pub uptime: Duration,
+    /// Number of tasks completed successfully.
+    pub tasks_processed: u64,
+    /// True if no task has been waiting when sampling.
+    pub is_idle: bool,
+}
+
+impl ActorHealth {
+    /// Returns the HTTP status code of a health check endpoint reporting this
+    /// sample. It's `503` if the actor failed, otherwise `200`.
+    pub fn http_status(&self) -> u16 {
+        match self.state {
+            ActorState::Error => 503,
+            _ => 200,
+        }
+    }
+
+    /// Returns true if the actor doesn't accept tasks anymore but isn't failed.
+    pub fn is_draining(&self) -> bool {
+        self.state == ActorState::Stopped
+    }
+
+    /// Renders the sample as JSON body of a health check endpoint.
+    pub fn to_json(&self) -> String {
+        let last_error = match &self.last_error {
+            Some(err) => format!("\"{}\"", escape_json(&err.to_string())),
+            None => "null".to_string(),
+        };
+        format!(
+            "{{\"state\":\"{:?}\",\"queue_depth\":{},\"last_error\":{},\"uptime_ms\":{},\
+             \"tasks_processed\":{},\"is_idle\":{},\"draining\":{}}}",
+            self.state,
+            self.queue_depth,
+            last_error,
+            self.uptime.as_millis(),
+            self.tasks_processed,
+            self.is_idle,
+            self.is_draining()
+        )
+    }
+}
+
+/// Escapes a string for the use inside of a JSON string.
+pub(crate) fn escape_json(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl fmt::Display for ActorHealth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "state={:?} queue={} processed={} uptime={:.3}s idle={}",
+            self.state,
+            self.queue_depth,
+            self.tasks_processed,
+            self.uptime.as_secs_f64(),
+            self.is_idle
+        )?;
+        match &self.last_error {
+            Some(err) => write!(f, " last_error=\"{}\"", err),
+            None => Ok(()),
+        }
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
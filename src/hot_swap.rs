@@ -0,0 +1,49 @@
+// --------------------------------------------------------
+// Actor library - Hot swap actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::sync::{Arc, Mutex};
+
+use crate::{ActorError, AsyncActor, TaskId};
+
+/// HotSwapActor forwards tasks to its current AsyncActor, which can be
+/// replaced at any time, e.g. by one with a new configuration. The senders
+/// keep using the HotSwapActor, tasks already sent to the old actor are
+/// processed by it while new tasks go to the replacement.
+pub struct HotSwapActor {
+    current: Mutex<Arc<AsyncActor>>,
+}
+
+impl HotSwapActor {
+    /// Creates a new HotSwapActor forwarding to the initial actor.
+    pub fn new(initial: Arc<AsyncActor>) -> Arc<Self> {
+        Arc::new(Self {
+            current: Mutex::new(initial),
+        })
+    }
+
+    /// Sends a task to the current actor.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send<F>(&self, task: F) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        self.actor().send(task).await
+    }
+
+    /// Replaces the current actor and returns the old one. It isn't stopped,
+    /// so the owner decides when to stop it.
+    pub fn replace(&self, new_actor: Arc<AsyncActor>) -> Arc<AsyncActor> {
+        std::mem::replace(&mut *self.current.lock().unwrap(), new_actor)
+    }
+
+    /// Returns the current actor.
+    pub fn actor(&self) -> Arc<AsyncActor> {
+        self.current.lock().unwrap().clone()
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,98 @@
+// --------------------------------------------------------
+// Actor library - Latency budget actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::{ActorError, AsyncActor, TaskId};
+
+/// Class is the latency budget of a task class and its violations.
+struct Class {
+    budget: Duration,
+    violations: Arc<AtomicU64>,
+}
+
+/// LatencyBudgetActor monitors how long the tasks of registered classes wait
+/// in the queue of its actor. A task started later than the budget of its
+/// class after it has been sent counts as an SLA violation of the class and
+/// in the `sla_violations` statistics of the actor. The task still runs.
+pub struct LatencyBudgetActor {
+    inner: Arc<AsyncActor>,
+    classes: Mutex<HashMap<String, Class>>,
+}
+
+impl LatencyBudgetActor {
+    /// Creates a new LatencyBudgetActor without classes.
+    pub fn new(inner: Arc<AsyncActor>) -> Arc<Self> {
+        Arc::new(Self {
+            inner,
+            classes: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Registers a class with its budget. The budget of an already registered
+    /// class is replaced, its violations are kept.
+    pub fn register_class(&self, class: &str, budget: Duration) {
+        let mut classes = self.classes.lock().unwrap();
+        match classes.get_mut(class) {
+            Some(registered) => registered.budget = budget,
+            None => {
+                let violations = Arc::new(AtomicU64::new(0));
+                classes.insert(class.to_string(), Class { budget, violations });
+            }
+        }
+    }
+
+    /// Sends a task of the class. Tasks of unregistered classes are sent
+    /// without budget.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send_classed<F>(&self, task: F, class: &str) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        let budget = self
+            .classes
+            .lock()
+            .unwrap()
+            .get(class)
+            .map(|class| (class.budget, class.violations.clone()));
+        let Some((budget, violations)) = budget else {
+            return self.inner.send(task).await;
+        };
+        let sent = Instant::now();
+        let actor = Arc::downgrade(&self.inner);
+        self.inner
+            .send(move || {
+                if sent.elapsed() > budget {
+                    violations.fetch_add(1, Ordering::Relaxed);
+                    if let Some(actor) = actor.upgrade() {
+                        actor.count_sla_violation();
+                    }
+                }
+                task()
+            })
+            .await
+    }
+
+    /// Returns the number of SLA violations of the class.
+    pub fn violations(&self, class: &str) -> u64 {
+        self.classes
+            .lock()
+            .unwrap()
+            .get(class)
+            .map_or(0, |class| class.violations.load(Ordering::Relaxed))
+    }
+
+    /// Returns the actor executing the tasks.
+    pub fn inner(&self) -> &Arc<AsyncActor> {
+        &self.inner
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,157 @@
+// --------------------------------------------------------
+// Actor library - Macros
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+/// Generates an actor-backed struct from a state definition. The state struct
+/// gets the given fields and methods, the actor struct owns an
+/// `Arc<StateActor<State>>` and an async method for each state method delegating
+/// to the actor. As declarative macros cannot build identifiers, the name of
+/// the state struct is given after the one of the actor struct.
+///
+/// ```
+/// use actor::actor;
+///
+/// actor!(pub Counter: CounterState { value: i32 } impl {
+///     fn incr(&mut self) { self.value += 1 }
+///     fn add(&mut self, n: i32) { self.value += n }
+/// });
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let counter = Counter::new(CounterState { value: 0 });
+/// counter.incr().await.unwrap();
+/// counter.add(2).await.unwrap();
+/// assert_eq!(counter.read(|state| state.value).await, Ok(3));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! actor {
+    (
+        $vis:vis $name:ident : $state:ident { $($field:ident : $fty:ty),* $(,)? }
+        impl {
+            $(fn $method:ident(&mut $this:ident $(, $arg:ident : $aty:ty)* $(,)?) $body:block)*
+        }
+    ) => {
+        $vis struct $state {
+            $(pub $field: $fty),*
+        }
+
+        impl $state {
+            $(fn $method(&mut $this $(, $arg: $aty)*) $body)*
+        }
+
+        $vis struct $name {
+            actor: ::std::sync::Arc<$crate::StateActor<$state>>,
+        }
+
+        impl $name {
+            /// Creates the actor owning the given state.
+            $vis fn new(state: $state) -> Self {
+                Self {
+                    actor: $crate::StateActor::new(state),
+                }
+            }
+
+            /// Returns the underlying state actor.
+            $vis fn actor(&self) -> &::std::sync::Arc<$crate::StateActor<$state>> {
+                &self.actor
+            }
+
+            /// Reads from the state inside of the actor.
+            $vis async fn read<R, F>(&self, f: F) -> Result<R, $crate::ActorError>
+            where
+                F: FnOnce(&$state) -> R + Send + 'static,
+                R: Send + 'static,
+            {
+                self.actor.ask(move |state| f(state)).await
+            }
+
+            $(
+                $vis async fn $method(&self $(, $arg: $aty)*) -> Result<(), $crate::ActorError> {
+                    self.actor
+                        .send(move |state| {
+                            state.$method($($arg),*);
+                            Ok(())
+                        })
+                        .await
+                }
+            )*
+        }
+    };
+}
+
+/// Sends the statements as task to an AsyncActor and awaits the sending. The
+/// statements are wrapped into a `move` closure returning `Ok(())`, so
+/// referenced variables are captured automatically.
+///
+/// ```
+/// use actor::{send, AsyncActor};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let actor = AsyncActor::new();
+/// let name = "World".to_string();
+/// send!(actor, { println!("Hello, {}!", name); }).unwrap();
+/// # }
+/// ```
+#[macro_export]
+macro_rules! send {
+    ($actor:expr, { $($body:tt)* }) => {
+        $actor
+            .send(move || {
+                { $($body)* }
+                Ok(())
+            })
+            .await
+    };
+}
+
+/// Asks an AsyncActor for the value of the expression and awaits the answer.
+/// The expression is evaluated inside of the actor.
+///
+/// ```
+/// use actor::{ask, AsyncActor};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let actor = AsyncActor::new();
+/// let answer = ask!(actor, 6 * 7);
+/// assert_eq!(answer, Ok(42));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! ask {
+    ($actor:expr, $expr:expr) => {
+        $actor.ask(move || $expr).await
+    };
+}
+
+/// Returns the TaskLocation of the call site. Each expansion counts its own
+/// calls, so a loop gives each of its tasks a distinct location.
+///
+/// ```
+/// use actor::task_id;
+///
+/// let locations: Vec<_> = (0..3).map(|_| task_id!()).collect();
+/// assert_eq!(locations[2].call, 3);
+/// assert_eq!(
+///     locations[0].to_string(),
+///     format!("{}:{}:1", locations[0].file, locations[0].line)
+/// );
+/// ```
+#[macro_export]
+macro_rules! task_id {
+    () => {{
+        static CALLS: ::std::sync::atomic::AtomicU32 = ::std::sync::atomic::AtomicU32::new(0);
+        $crate::TaskLocation {
+            file: ::std::file!(),
+            line: ::std::line!(),
+            call: CALLS.fetch_add(1, ::std::sync::atomic::Ordering::Relaxed) + 1,
+        }
+    }};
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
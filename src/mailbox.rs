@@ -0,0 +1,238 @@
+// --------------------------------------------------------
+// Actor library - Mailbox
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tokio::sync::Notify;
+
+/// PushError is returned when an item cannot be pushed into the mailbox. It
+/// hands the item back to the caller.
+pub(crate) enum PushError<T> {
+    /// The mailbox reached its capacity.
+    Full(T),
+    /// The mailbox has been closed.
+    Closed(T),
+}
+
+struct Inner<T> {
+    queue: VecDeque<T>,
+    closed: bool,
+}
+
+/// Mailbox is the bounded queue between an actor and its loop. Other than a
+/// channel it allows to access the queued items, e.g. to drop the oldest one.
+pub(crate) struct Mailbox<T> {
+    inner: Mutex<Inner<T>>,
+    capacity: usize,
+    not_empty: Notify,
+    // All waiting pushers are woken on free room, as a single freed slot may
+    // not be enough for a waiting batch but for a single item behind it.
+    not_full: Notify,
+}
+
+impl<T> Mailbox<T> {
+    /// Creates a new mailbox with the given capacity.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                queue: VecDeque::with_capacity(capacity),
+                closed: false,
+            }),
+            capacity: capacity.max(1),
+            not_empty: Notify::new(),
+            not_full: Notify::new(),
+        }
+    }
+
+    /// Pushes an item, waiting while the mailbox is full.
+    pub(crate) async fn push(&self, mut item: T) -> Result<(), PushError<T>> {
+        loop {
+            let notified = self.not_full.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            match self.try_push(item) {
+                Err(PushError::Full(rejected)) => item = rejected,
+                result => return result,
+            }
+            notified.await;
+        }
+    }
+
+    /// Pushes an item if the mailbox is neither full nor closed.
+    pub(crate) fn try_push(&self, item: T) -> Result<(), PushError<T>> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.closed {
+            return Err(PushError::Closed(item));
+        }
+        if inner.queue.len() >= self.capacity {
+            return Err(PushError::Full(item));
+        }
+        inner.queue.push_back(item);
+        drop(inner);
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    /// Pushes all items in a row, waiting until the mailbox has room for all of
+    /// them. An empty mailbox accepts more items than its capacity, so that
+    /// large batches cannot wait forever.
+    pub(crate) async fn push_all(&self, mut items: Vec<T>) -> Result<(), PushError<Vec<T>>> {
+        loop {
+            let notified = self.not_full.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            match self.try_push_all(items) {
+                Err(PushError::Full(rejected)) => items = rejected,
+                result => return result,
+            }
+            notified.await;
+        }
+    }
+
+    /// Pushes all items in a row if the mailbox has room for all of them and is
+    /// not closed.
+    pub(crate) fn try_push_all(&self, items: Vec<T>) -> Result<(), PushError<Vec<T>>> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.closed {
+            return Err(PushError::Closed(items));
+        }
+        if !inner.queue.is_empty() && inner.queue.len() + items.len() > self.capacity {
+            return Err(PushError::Full(items));
+        }
+        inner.queue.extend(items);
+        drop(inner);
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    /// Pushes an item and removes the oldest evictable one if the mailbox is
+    /// full. The removed item is returned. If no item is evictable the mailbox
+    /// grows beyond its capacity.
+    pub(crate) fn push_evicting<E>(&self, item: T, evictable: E) -> Result<Option<T>, PushError<T>>
+    where
+        E: Fn(&T) -> bool,
+    {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.closed {
+            return Err(PushError::Closed(item));
+        }
+        let mut evicted = None;
+        if inner.queue.len() >= self.capacity {
+            if let Some(index) = inner.queue.iter().position(evictable) {
+                evicted = inner.queue.remove(index);
+            }
+        }
+        inner.queue.push_back(item);
+        drop(inner);
+        self.not_empty.notify_one();
+        Ok(evicted)
+    }
+
+    /// Pushes an item in front of all others, even beyond the capacity, and
+    /// closes the mailbox.
+    pub(crate) fn push_front_closing(&self, item: T) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.queue.push_front(item);
+        inner.closed = true;
+        drop(inner);
+        self.not_empty.notify_one();
+        self.not_full.notify_waiters();
+    }
+
+    /// Pops the oldest item, waiting while the mailbox is empty. Returns `None`
+    /// once the mailbox is closed and empty.
+    pub(crate) async fn pop(&self) -> Option<T> {
+        loop {
+            let notified = self.not_empty.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            {
+                let mut inner = self.inner.lock().unwrap();
+                if let Some(item) = inner.queue.pop_front() {
+                    drop(inner);
+                    self.not_full.notify_waiters();
+                    return Some(item);
+                }
+                if inner.closed {
+                    return None;
+                }
+            }
+            notified.await;
+        }
+    }
+
+    /// Pops the oldest item if there is one.
+    pub(crate) fn try_pop(&self) -> Option<T> {
+        let item = self.inner.lock().unwrap().queue.pop_front();
+        if item.is_some() {
+            self.not_full.notify_waiters();
+        }
+        item
+    }
+
+    /// Removes the oldest item matching the predicate.
+    pub(crate) fn remove_first<P>(&self, predicate: P) -> Option<T>
+    where
+        P: Fn(&T) -> bool,
+    {
+        let mut inner = self.inner.lock().unwrap();
+        let index = inner.queue.iter().position(predicate)?;
+        let item = inner.queue.remove(index);
+        drop(inner);
+        self.not_full.notify_waiters();
+        item
+    }
+
+    /// Removes up to `n` of the newest items matching the predicate. They are
+    /// returned in their queue order.
+    pub(crate) fn take_newest<P>(&self, n: usize, predicate: P) -> Vec<T>
+    where
+        P: Fn(&T) -> bool,
+    {
+        let mut inner = self.inner.lock().unwrap();
+        let mut taken = Vec::new();
+        let mut index = inner.queue.len();
+        while index > 0 && taken.len() < n {
+            index -= 1;
+            if predicate(&inner.queue[index]) {
+                taken.extend(inner.queue.remove(index));
+            }
+        }
+        drop(inner);
+        if !taken.is_empty() {
+            self.not_full.notify_waiters();
+        }
+        taken.reverse();
+        taken
+    }
+
+    /// Closes the mailbox. Queued items can still be popped, but no new ones
+    /// can be pushed.
+    pub(crate) fn close(&self) {
+        self.inner.lock().unwrap().closed = true;
+        self.not_empty.notify_waiters();
+        self.not_empty.notify_one();
+        self.not_full.notify_waiters();
+    }
+
+    /// Returns true if the mailbox has been closed.
+    pub(crate) fn is_closed(&self) -> bool {
+        self.inner.lock().unwrap().closed
+    }
+
+    /// Returns the capacity of the mailbox.
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of queued items.
+    pub(crate) fn len(&self) -> usize {
+        self.inner.lock().unwrap().queue.len()
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
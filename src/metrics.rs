@@ -0,0 +1,113 @@
+// --------------------------------------------------------
+// Actor library - Task metrics
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+/// Polls taking at least this long count as slow.
+const SLOW_POLL_THRESHOLD: Duration = Duration::from_micros(50);
+
+/// TaskMetrics is a snapshot of how the runtime polled the loop of an actor.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskMetrics {
+    /// Time between spawning the loop and its first poll, `None` before.
+    pub first_poll_delay: Option<Duration>,
+    /// Number of polls of the loop.
+    pub total_poll_count: u64,
+    /// Time spent in all polls of the loop.
+    pub total_poll_duration: Duration,
+    /// Number of polls taking 50µs or longer.
+    pub total_slow_poll_count: u64,
+}
+
+impl TaskMetrics {
+    /// Returns the mean duration of one poll.
+    pub fn mean_poll_duration(&self) -> Duration {
+        match self.total_poll_count {
+            0 => Duration::ZERO,
+            count => {
+                Duration::from_nanos((self.total_poll_duration.as_nanos() / count as u128) as u64)
+            }
+        }
+    }
+}
+
+/// TaskMonitor collects the metrics of one instrumented future.
+pub(crate) struct TaskMonitor {
+    spawned: Instant,
+    first_poll_delay: AtomicU64,
+    polls: AtomicU64,
+    poll_duration: AtomicU64,
+    slow_polls: AtomicU64,
+}
+
+impl TaskMonitor {
+    /// Creates a new monitor, the first poll delay is measured from now.
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::new(Self {
+            spawned: Instant::now(),
+            first_poll_delay: AtomicU64::new(u64::MAX),
+            polls: AtomicU64::new(0),
+            poll_duration: AtomicU64::new(0),
+            slow_polls: AtomicU64::new(0),
+        })
+    }
+
+    /// Wraps the future to record its polls.
+    pub(crate) fn instrument<F: Future>(self: &Arc<Self>, future: F) -> Instrumented<F> {
+        Instrumented {
+            future: Box::pin(future),
+            monitor: self.clone(),
+        }
+    }
+
+    /// Returns a snapshot of the metrics.
+    pub(crate) fn metrics(&self) -> TaskMetrics {
+        let first_poll_delay = self.first_poll_delay.load(Ordering::Relaxed);
+        TaskMetrics {
+            first_poll_delay: (first_poll_delay != u64::MAX)
+                .then(|| Duration::from_nanos(first_poll_delay)),
+            total_poll_count: self.polls.load(Ordering::Relaxed),
+            total_poll_duration: Duration::from_nanos(self.poll_duration.load(Ordering::Relaxed)),
+            total_slow_poll_count: self.slow_polls.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Instrumented is a future recording its polls in a TaskMonitor.
+pub(crate) struct Instrumented<F> {
+    future: Pin<Box<F>>,
+    monitor: Arc<TaskMonitor>,
+}
+
+impl<F: Future> Future for Instrumented<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let monitor = self.monitor.clone();
+        let started = Instant::now();
+        if monitor.polls.fetch_add(1, Ordering::Relaxed) == 0 {
+            let delay = started.duration_since(monitor.spawned).as_nanos() as u64;
+            monitor.first_poll_delay.store(delay, Ordering::Relaxed);
+        }
+        let poll = self.future.as_mut().poll(cx);
+        let duration = started.elapsed();
+        monitor
+            .poll_duration
+            .fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+        if duration >= SLOW_POLL_THRESHOLD {
+            monitor.slow_polls.fetch_add(1, Ordering::Relaxed);
+        }
+        poll
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
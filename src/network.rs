@@ -0,0 +1,366 @@
+// --------------------------------------------------------
+// Actor library - Network actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::collections::HashMap;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::runtime;
+use crate::{ActorError, AsyncActor, CancellationToken};
+
+/// Maximum size of the payload of a frame. A peer sending a larger one is
+/// disconnected.
+const MAX_FRAME_SIZE: usize = 16 * 1024 * 1024;
+
+/// TaskSerializer converts the tasks of a NetworkActor to bytes and back. As
+/// closures can't be transferred, the tasks are plain values describing the
+/// work, which the NetworkActorServer passes to its handler.
+pub trait TaskSerializer: Send + Sync + 'static {
+    /// The type of the tasks.
+    type Task: Send + 'static;
+
+    /// Serializes a task for sending it.
+    fn serialize(&self, task: &Self::Task) -> Vec<u8>;
+
+    /// Deserializes a received task.
+    fn deserialize(&self, bytes: &[u8]) -> Result<Self::Task, String>;
+}
+
+/// Responder passes the result of a remote task back to its sender.
+type Responder = oneshot::Sender<Result<(), ActorError>>;
+
+/// Pending maps the IDs of the sent requests to their responders.
+type Pending = Arc<Mutex<HashMap<u64, Responder>>>;
+
+/// NetworkActor sends tasks over TCP to a NetworkActorServer, which executes
+/// them on its actor. The requests of all senders share one connection, the
+/// responses are matched by their request ID. A lost connection is
+/// reestablished with the configured retries, the requests waiting at that
+/// time fail.
+///
+/// This is a proof of concept transport without authentication or
+/// encryption.
+pub struct NetworkActor<S: TaskSerializer> {
+    serializer: S,
+    requests: mpsc::Sender<(u64, Vec<u8>, Responder)>,
+    next_id: AtomicU64,
+}
+
+impl<S: TaskSerializer> NetworkActor<S> {
+    /// Connects to the server at the address. A lost connection is retried
+    /// three times with a delay of 100 ms.
+    pub async fn connect(addr: SocketAddr, serializer: S) -> io::Result<Arc<Self>> {
+        Self::connect_with_retry(addr, serializer, 3, Duration::from_millis(100)).await
+    }
+
+    /// Connects to the server at the address. A lost connection is retried up
+    /// to `attempts` times with the delay in between. Afterwards all sending
+    /// fails with `ActorError::Stopped`.
+    pub async fn connect_with_retry(
+        addr: SocketAddr,
+        serializer: S,
+        attempts: usize,
+        delay: Duration,
+    ) -> io::Result<Arc<Self>> {
+        let stream = TcpStream::connect(addr).await?;
+        let (requests, receiver) = mpsc::channel(32);
+        runtime::spawn(drive(addr, stream, receiver, attempts, delay));
+        Ok(Arc::new(Self {
+            serializer,
+            requests,
+            next_id: AtomicU64::new(1),
+        }))
+    }
+
+    /// Sends a task to the server and waits until it has been executed.
+    /// Errors returned by the remote handler are returned as
+    /// `ActorError::Task`, tasks serialized to more than 16 MiB are rejected
+    /// with `ActorError::Send`.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send(&self, task: S::Task) -> Result<(), ActorError> {
+        let bytes = self.serializer.serialize(&task);
+        if bytes.len() > MAX_FRAME_SIZE {
+            return Err(ActorError::Send(format!(
+                "task of {} bytes exceeds the maximum frame size",
+                bytes.len()
+            )));
+        }
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (responder, response) = oneshot::channel();
+        self.requests
+            .send((id, bytes, responder))
+            .await
+            .map_err(|_| ActorError::Stopped)?;
+        response.await.map_err(|_| ActorError::Stopped)?
+    }
+}
+
+/// Drives the connection of a NetworkActor. It writes the requests, while a
+/// reader task resolves the responses, and reconnects after errors.
+async fn drive(
+    addr: SocketAddr,
+    mut stream: TcpStream,
+    mut requests: mpsc::Receiver<(u64, Vec<u8>, Responder)>,
+    attempts: usize,
+    delay: Duration,
+) {
+    loop {
+        let (reader, mut writer) = stream.into_split();
+        let pending: Pending = Arc::new(Mutex::new(HashMap::new()));
+        let (lost, mut reader_done) = oneshot::channel::<()>();
+        let reader_pending = pending.clone();
+        runtime::spawn(async move {
+            read_responses(reader, reader_pending).await;
+            let _ = lost.send(());
+        });
+
+        loop {
+            tokio::select! {
+                request = requests.recv() => {
+                    let Some((id, bytes, responder)) = request else {
+                        return;
+                    };
+                    pending.lock().unwrap().insert(id, responder);
+                    if write_frame(&mut writer, id, &bytes).await.is_err() {
+                        break;
+                    }
+                }
+                _ = &mut reader_done => break,
+            }
+        }
+
+        // Fail the waiting requests, their tasks may or may not have run.
+        for (_, responder) in pending.lock().unwrap().drain() {
+            let _ = responder.send(Err(ActorError::Send("connection lost".to_string())));
+        }
+        drop(writer);
+
+        let mut reconnected = None;
+        for _ in 0..attempts {
+            runtime::sleep(delay).await;
+            if let Ok(new_stream) = TcpStream::connect(addr).await {
+                reconnected = Some(new_stream);
+                break;
+            }
+        }
+        match reconnected {
+            Some(new_stream) => stream = new_stream,
+            None => return,
+        }
+    }
+}
+
+/// Reads the responses of the server and passes them to the responders.
+async fn read_responses<R: AsyncRead + Unpin>(mut reader: R, pending: Pending) {
+    loop {
+        let Ok(id) = reader.read_u64().await else {
+            return;
+        };
+        let Ok(status) = reader.read_u8().await else {
+            return;
+        };
+        let Ok(message) = read_bytes(&mut reader).await else {
+            return;
+        };
+        let result = match status {
+            0 => Ok(()),
+            _ => Err(ActorError::Task(
+                String::from_utf8_lossy(&message).into_owned(),
+            )),
+        };
+        if let Some(responder) = pending.lock().unwrap().remove(&id) {
+            let _ = responder.send(result);
+        }
+    }
+}
+
+/// NetworkActorServer accepts connections of NetworkActors and executes the
+/// received tasks sequentially with its handler on an AsyncActor. Connections
+/// sending frames above 16 MiB are closed, error messages of the handler are
+/// truncated to that size.
+pub struct NetworkActorServer {
+    local_addr: SocketAddr,
+    actor: Arc<AsyncActor>,
+    shutdown: CancellationToken,
+}
+
+impl NetworkActorServer {
+    /// Binds the server to the address and starts accepting connections.
+    pub async fn bind<S, H>(addr: SocketAddr, serializer: S, handler: H) -> io::Result<Arc<Self>>
+    where
+        S: TaskSerializer,
+        H: Fn(S::Task) -> Result<(), String> + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind(addr).await?;
+        let server = Arc::new(Self {
+            local_addr: listener.local_addr()?,
+            actor: AsyncActor::new(),
+            shutdown: CancellationToken::new(),
+        });
+
+        let serializer = Arc::new(serializer);
+        let handler = Arc::new(handler);
+        let actor = server.actor.clone();
+        let shutdown = server.shutdown.clone();
+        runtime::spawn(async move {
+            loop {
+                let stream = tokio::select! {
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, _)) => stream,
+                        Err(_) => continue,
+                    },
+                    _ = shutdown.cancelled() => return,
+                };
+                runtime::spawn(serve(
+                    stream,
+                    serializer.clone(),
+                    handler.clone(),
+                    actor.clone(),
+                    shutdown.clone(),
+                ));
+            }
+        });
+        Ok(server)
+    }
+
+    /// Returns the address the server listens on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Returns the actor executing the tasks.
+    pub fn actor(&self) -> &Arc<AsyncActor> {
+        &self.actor
+    }
+
+    /// Stops accepting connections and closes the open ones.
+    pub fn shutdown(&self) {
+        self.shutdown.cancel();
+    }
+}
+
+impl Drop for NetworkActorServer {
+    fn drop(&mut self) {
+        self.shutdown.cancel();
+    }
+}
+
+/// Serves one connection. Each request is executed on the actor, the responses
+/// are written by a separate task in the order the tasks complete.
+async fn serve<S, H>(
+    stream: TcpStream,
+    serializer: Arc<S>,
+    handler: Arc<H>,
+    actor: Arc<AsyncActor>,
+    shutdown: CancellationToken,
+) where
+    S: TaskSerializer,
+    H: Fn(S::Task) -> Result<(), String> + Send + Sync + 'static,
+{
+    let (mut reader, mut writer) = stream.into_split();
+    let (responses, mut outgoing) = mpsc::channel::<(u64, Result<(), String>)>(32);
+    runtime::spawn(async move {
+        while let Some((id, result)) = outgoing.recv().await {
+            let (status, message) = match result {
+                Ok(()) => (0, Vec::new()),
+                Err(msg) => {
+                    let mut message = msg.into_bytes();
+                    message.truncate(MAX_FRAME_SIZE);
+                    (1, message)
+                }
+            };
+            let written = async {
+                writer.write_u64(id).await?;
+                writer.write_u8(status).await?;
+                write_bytes(&mut writer, &message).await
+            };
+            if written.await.is_err() {
+                return;
+            }
+        }
+    });
+
+    loop {
+        let request = tokio::select! {
+            request = read_request(&mut reader) => request,
+            _ = shutdown.cancelled() => return,
+            // Writing the responses failed, so the connection is closed.
+            _ = responses.closed() => return,
+        };
+        let Ok((id, bytes)) = request else {
+            return;
+        };
+        let task = match serializer.deserialize(&bytes) {
+            Ok(task) => task,
+            Err(msg) => {
+                let _ = responses.send((id, Err(msg))).await;
+                continue;
+            }
+        };
+        let handler = handler.clone();
+        let actor = actor.clone();
+        let responses = responses.clone();
+        runtime::spawn(async move {
+            let result = match actor.ask(move || handler(task)).await {
+                Ok(result) => result,
+                Err(err) => Err(err.to_string()),
+            };
+            let _ = responses.send((id, result)).await;
+        });
+    }
+}
+
+/// Reads a request frame with its ID and payload.
+async fn read_request<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<(u64, Vec<u8>)> {
+    let id = reader.read_u64().await?;
+    let bytes = read_bytes(reader).await?;
+    Ok((id, bytes))
+}
+
+/// Writes a request frame with its ID and payload.
+async fn write_frame<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    id: u64,
+    bytes: &[u8],
+) -> io::Result<()> {
+    writer.write_u64(id).await?;
+    write_bytes(writer, bytes).await
+}
+
+/// Reads bytes prefixed by their length. Lengths above the maximum frame
+/// size are invalid.
+async fn read_bytes<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let len = reader.read_u32().await? as usize;
+    if len > MAX_FRAME_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame of {} bytes exceeds the maximum size", len),
+        ));
+    }
+    let mut bytes = vec![0; len];
+    reader.read_exact(&mut bytes).await?;
+    Ok(bytes)
+}
+
+/// Writes bytes prefixed by their length.
+async fn write_bytes<W: AsyncWrite + Unpin>(writer: &mut W, bytes: &[u8]) -> io::Result<()> {
+    if bytes.len() > MAX_FRAME_SIZE {
+        return Err(io::ErrorKind::InvalidInput.into());
+    }
+    let len = bytes.len() as u32;
+    writer.write_u32(len).await?;
+    writer.write_all(bytes).await?;
+    writer.flush().await
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,138 @@
+// --------------------------------------------------------
+// Actor library - Output actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use tokio::sync::mpsc;
+
+use crate::runtime;
+use crate::{ActorError, ActorState, Stream};
+
+/// OutputTask is a function or closure taking no arguments and returning a
+/// Result<T, ActorError>.
+pub type OutputTask<T> = Box<dyn FnOnce() -> Result<T, ActorError> + Send>;
+
+/// OutputActor runs tasks producing a value sequentially like the AsyncActor.
+/// The results of the tasks are collected in order and can be consumed as a
+/// stream, so the actor works like an asynchronous generator inside of
+/// processing pipelines.
+///
+/// The first error returned by a task is the last item of the stream. The actor
+/// stops processing tasks afterwards.
+pub struct OutputActor<T: Send + 'static> {
+    sender: mpsc::Sender<OutputTask<T>>,
+    receiver: mpsc::Receiver<Result<T, ActorError>>,
+    state: Arc<Mutex<ActorState>>,
+}
+
+impl<T: Send + 'static> OutputActor<T> {
+    /// Creates a new OutputActor.
+    pub fn new() -> Self {
+        let (sender, mut tasks) = mpsc::channel::<OutputTask<T>>(32);
+        let (outputs, receiver) = mpsc::channel::<Result<T, ActorError>>(32);
+        let state = Arc::new(Mutex::new(ActorState::Running));
+
+        let loop_state = state.clone();
+        runtime::spawn(async move {
+            while let Some(task) = tasks.recv().await {
+                match task() {
+                    Ok(output) => {
+                        // Stop working if nobody is interested in the outputs anymore.
+                        if outputs.send(Ok(output)).await.is_err() {
+                            *loop_state.lock().unwrap() = ActorState::Stopped;
+                            return;
+                        }
+                    }
+                    Err(err) => {
+                        *loop_state.lock().unwrap() = ActorState::Error;
+                        let _ = outputs.send(Err(err)).await;
+                        return;
+                    }
+                }
+            }
+            *loop_state.lock().unwrap() = ActorState::Stopped;
+        });
+
+        Self {
+            sender,
+            receiver,
+            state,
+        }
+    }
+
+    /// Sends a task to the OutputActor.
+    pub async fn send<F>(&self, task: F) -> Result<(), ActorError>
+    where
+        F: FnOnce() -> Result<T, ActorError> + Send + 'static,
+    {
+        if *self.state.lock().unwrap() != ActorState::Running {
+            return Err(ActorError::Stopped);
+        }
+
+        self.sender
+            .send(Box::new(task))
+            .await
+            .map_err(|err| ActorError::Send(err.to_string()))
+    }
+
+    /// Returns a sender passing tasks to the loop without the state check, e.g.
+    /// for feeding the actor from a pipeline.
+    pub(crate) fn task_sender(&self) -> mpsc::Sender<OutputTask<T>> {
+        self.sender.clone()
+    }
+
+    /// Retrieves the current state of the OutputActor.
+    pub fn state(&self) -> ActorState {
+        self.state.lock().unwrap().clone()
+    }
+
+    /// Turns the actor into the stream of its outputs. No more tasks can be sent
+    /// afterwards, the stream ends after the outputs of all enqueued tasks have
+    /// been yielded or after the first error.
+    pub fn into_stream(self) -> impl Stream<Item = Result<T, ActorError>> + Unpin {
+        OutputStream {
+            receiver: self.receiver,
+            done: false,
+        }
+    }
+}
+
+impl<T: Send + 'static> Default for OutputActor<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// OutputStream yields the outputs of an OutputActor.
+struct OutputStream<T> {
+    receiver: mpsc::Receiver<Result<T, ActorError>>,
+    done: bool,
+}
+
+impl<T> Stream for OutputStream<T> {
+    type Item = Result<T, ActorError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+        match self.receiver.poll_recv(cx) {
+            Poll::Ready(Some(Err(err))) => {
+                self.done = true;
+                Poll::Ready(Some(Err(err)))
+            }
+            Poll::Ready(None) => {
+                self.done = true;
+                Poll::Ready(None)
+            }
+            poll => poll,
+        }
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
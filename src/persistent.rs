@@ -0,0 +1,325 @@
+// --------------------------------------------------------
+// Actor library - Persistent actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::sync::mpsc as std_mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::oneshot;
+
+use crate::runtime;
+use crate::{ActorError, StateActor};
+
+/// Replay is implemented by the tasks of a PersistentActor. They are encoded
+/// into the write-ahead log and decoded again to replay them after a crash.
+/// The encoded form must not contain line breaks.
+pub trait Replay<S>: Sized + Send + 'static {
+    /// Encodes the task into a single line.
+    fn encode(&self) -> String;
+
+    /// Decodes a task encoded before. Returns `None` if it's invalid.
+    fn decode(encoded: &str) -> Option<Self>;
+
+    /// Applies the task to the state of the actor.
+    fn apply(self, state: &mut S) -> Result<(), ActorError>;
+}
+
+/// DeliverySemantics defines how often a task of a PersistentActor is
+/// executed when the process crashes while the task is waiting or running.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum DeliverySemantics {
+    /// The task is committed when it starts. Tasks waiting at the crash are
+    /// replayed, a task running at the crash is lost.
+    AtMostOnce,
+    /// The task is committed after its successful execution. Tasks waiting
+    /// or running at the crash are replayed, so a task may run twice.
+    #[default]
+    AtLeastOnce,
+}
+
+/// PersistentActor writes its tasks into a write-ahead log before passing them
+/// to a StateActor. Each task is logged with its ID and timestamp, after its
+/// successful execution a commit record follows. When created again after a
+/// crash the uncommitted tasks of the log are replayed first, so each task is
+/// executed at least once. With `DeliverySemantics::AtMostOnce` the commit
+/// record is written when the task starts instead, the task waits until it's
+/// synced.
+///
+/// The log is written outside of the runtime. The commit records of
+/// `DeliverySemantics::AtLeastOnce` are synced in the background, a crash
+/// before that replays the task. Replayed tasks
+/// are committed whether they succeed or not. A replayed task failing or not
+/// decodable is quarantined instead of failing the actor again on each start.
+pub struct PersistentActor<S: Send + 'static, T: Replay<S>> {
+    inner: Arc<StateActor<S>>,
+    wal: Arc<Mutex<File>>,
+    records: std_mpsc::Sender<Record>,
+    semantics: DeliverySemantics,
+    next_task_id: tokio::sync::Mutex<u64>,
+    quarantined: Arc<Mutex<Vec<(u64, ActorError)>>>,
+    tasks: PhantomData<fn(T)>,
+}
+
+/// Uncommitted maps the IDs of the uncommitted tasks to their timestamps
+/// and encoded forms.
+type Uncommitted = BTreeMap<u64, (u128, String)>;
+
+/// Record is passed to the background writer of the log. A commit with an
+/// acknowledgement is answered once it's synced.
+enum Record {
+    Commit(u64, Option<std_mpsc::Sender<Result<(), ActorError>>>),
+    Flush(oneshot::Sender<Result<(), ActorError>>),
+}
+
+impl<S: Send + 'static, T: Replay<S>> PersistentActor<S, T> {
+    /// Creates a new PersistentActor using the given StateActor and log file.
+    /// Uncommitted tasks found in the log are replayed before returning.
+    pub async fn new(
+        inner: Arc<StateActor<S>>,
+        wal_path: PathBuf,
+    ) -> Result<Arc<Self>, ActorError> {
+        Self::with_semantics(inner, wal_path, DeliverySemantics::AtLeastOnce).await
+    }
+
+    /// Creates a new PersistentActor like `new()` committing the tasks as
+    /// defined by the delivery semantics.
+    pub async fn with_semantics(
+        inner: Arc<StateActor<S>>,
+        wal_path: PathBuf,
+        semantics: DeliverySemantics,
+    ) -> Result<Arc<Self>, ActorError> {
+        let (uncommitted, file) = runtime::spawn_blocking(move || rewrite(&wal_path))
+            .await
+            .unwrap_or_else(|| Err(io::Error::other("rewriting the log panicked")))
+            .map_err(persistence_error)?;
+
+        let next_task_id = uncommitted.keys().next_back().map_or(1, |id| id + 1);
+        let wal = Arc::new(Mutex::new(file));
+        let actor = Arc::new(Self {
+            inner,
+            wal: wal.clone(),
+            records: spawn_writer(wal),
+            semantics,
+            next_task_id: tokio::sync::Mutex::new(next_task_id),
+            quarantined: Arc::new(Mutex::new(Vec::new())),
+            tasks: PhantomData,
+        });
+
+        for (id, (_, encoded)) in uncommitted {
+            let task = T::decode(&encoded)
+                .ok_or_else(|| ActorError::Persistence(format!("cannot decode task {}", id)));
+            actor.replay(id, task).await?;
+        }
+        actor.flush().await?;
+
+        Ok(actor)
+    }
+
+    /// Logs the task and sends it to the StateActor. The task is logged and
+    /// sent while holding its ID, so a replay keeps the order of the tasks.
+    /// A task encoded with line breaks is rejected, as it would corrupt the
+    /// log.
+    pub async fn send(&self, task: T) -> Result<(), ActorError> {
+        let encoded = task.encode();
+        if encoded.contains(['\n', '\r']) {
+            return Err(ActorError::Persistence(
+                "encoded task contains a line break".to_string(),
+            ));
+        }
+        let mut next_task_id = self.next_task_id.lock().await;
+        let id = *next_task_id;
+        *next_task_id += 1;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis());
+        let record = format!("task {} {} {}\n", id, timestamp, encoded);
+        write_records(self.wal.clone(), record).await?;
+        self.execute(id, task).await
+    }
+
+    /// Waits until the replayed tasks and the tasks sent before are executed
+    /// and their commit records are synced to the log. Tasks after a failed
+    /// one are not executed and so stay uncommitted.
+    pub async fn flush(&self) -> Result<(), ActorError> {
+        let _order = self.next_task_id.lock().await;
+        let _ = self.inner.ask(|_| ()).await;
+        let (responder, response) = oneshot::channel();
+        self.records
+            .send(Record::Flush(responder))
+            .map_err(|err| ActorError::Send(err.to_string()))?;
+        response.await.unwrap_or(Err(ActorError::Stopped))
+    }
+
+    /// Returns the IDs of the replayed tasks which failed or could not be
+    /// decoded together with their errors.
+    pub fn quarantined(&self) -> Vec<(u64, ActorError)> {
+        self.quarantined.lock().unwrap().clone()
+    }
+
+    /// Returns the StateActor executing the tasks.
+    pub fn inner(&self) -> &Arc<StateActor<S>> {
+        &self.inner
+    }
+
+    /// Sends the task to the StateActor, committing it before or after its
+    /// execution depending on the delivery semantics.
+    async fn execute(&self, id: u64, task: T) -> Result<(), ActorError> {
+        let records = self.records.clone();
+        let semantics = self.semantics;
+        self.inner
+            .send(move |state| {
+                let ended = || ActorError::Persistence("log writer ended".to_string());
+                match semantics {
+                    // The commit is synced before the task starts, so a crash
+                    // while it's running can't replay it.
+                    DeliverySemantics::AtMostOnce => {
+                        let (ack, synced) = std_mpsc::channel();
+                        records
+                            .send(Record::Commit(id, Some(ack)))
+                            .map_err(|_| ended())?;
+                        synced.recv().map_err(|_| ended())??;
+                        task.apply(state)
+                    }
+                    DeliverySemantics::AtLeastOnce => {
+                        task.apply(state)?;
+                        records.send(Record::Commit(id, None)).map_err(|_| ended())
+                    }
+                }
+            })
+            .await
+    }
+
+    /// Sends a replayed task to the StateActor. It's committed in any case,
+    /// an error quarantines it instead of failing the StateActor.
+    async fn replay(&self, id: u64, task: Result<T, ActorError>) -> Result<(), ActorError> {
+        let records = self.records.clone();
+        let quarantined = self.quarantined.clone();
+        self.inner
+            .send(move |state| {
+                if let Err(err) = task.and_then(|task| task.apply(state)) {
+                    quarantined.lock().unwrap().push((id, err));
+                }
+                let _ = records.send(Record::Commit(id, None));
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// Spawns the writer appending the records it receives to the log. It runs
+/// on its own thread, so tasks can wait for their commit without depending
+/// on the runtime. Records received together are synced together. A write
+/// error is answered to the following flushes and synced commits.
+fn spawn_writer(wal: Arc<Mutex<File>>) -> std_mpsc::Sender<Record> {
+    let (sender, receiver) = std_mpsc::channel();
+    std::thread::spawn(move || {
+        let mut failed = None;
+        while let Ok(record) = receiver.recv() {
+            let mut commits = String::new();
+            let mut flushes = Vec::new();
+            let mut synced = Vec::new();
+            let mut next = Some(record);
+            while let Some(record) = next {
+                match record {
+                    Record::Commit(id, ack) => {
+                        commits.push_str(&format!("commit {}\n", id));
+                        synced.extend(ack);
+                    }
+                    Record::Flush(responder) => flushes.push(responder),
+                }
+                next = receiver.try_recv().ok();
+            }
+            if !commits.is_empty() && failed.is_none() {
+                let mut file = wal.lock().unwrap();
+                failed = file
+                    .write_all(commits.as_bytes())
+                    .and_then(|_| file.sync_data())
+                    .map_err(persistence_error)
+                    .err();
+            }
+            let result = failed.clone().map_or(Ok(()), Err);
+            for ack in synced {
+                let _ = ack.send(result.clone());
+            }
+            for responder in flushes {
+                let _ = responder.send(result.clone());
+            }
+        }
+    });
+    sender
+}
+
+/// Appends the records to the log and syncs it on a thread for blocking work.
+async fn write_records(wal: Arc<Mutex<File>>, records: String) -> Result<(), ActorError> {
+    runtime::spawn_blocking(move || {
+        let mut file = wal.lock().unwrap();
+        file.write_all(records.as_bytes())
+            .and_then(|_| file.sync_data())
+    })
+    .await
+    .unwrap_or_else(|| Err(io::Error::other("writing the log panicked")))
+    .map_err(persistence_error)
+}
+
+/// Rewrites the log with its uncommitted tasks only and opens it for
+/// appending. The tasks are written to a temporary file first, which then
+/// replaces the log, so a crash leaves either the old or the new log.
+fn rewrite(wal_path: &PathBuf) -> io::Result<(Uncommitted, File)> {
+    let uncommitted = read_uncommitted(wal_path)?;
+    let mut temporary = wal_path.clone().into_os_string();
+    temporary.push(".tmp");
+    let mut file = File::create(&temporary)?;
+    for (id, (timestamp, encoded)) in &uncommitted {
+        writeln!(file, "task {} {} {}", id, timestamp, encoded)?;
+    }
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&temporary, wal_path)?;
+    let file = OpenOptions::new().append(true).open(wal_path)?;
+    Ok((uncommitted, file))
+}
+
+/// Reads the log and returns the timestamps and encoded forms of the tasks
+/// without commit record, ordered by their IDs.
+fn read_uncommitted(wal_path: &PathBuf) -> io::Result<Uncommitted> {
+    let content = match fs::read_to_string(wal_path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err),
+    };
+
+    let mut uncommitted = BTreeMap::new();
+    for line in content.lines() {
+        let mut fields = line.splitn(4, ' ');
+        let kind = fields.next();
+        let id = fields.next().and_then(|id| id.parse::<u64>().ok());
+        match (kind, id) {
+            (Some("task"), Some(id)) => {
+                let timestamp = fields.next().and_then(|ts| ts.parse().ok()).unwrap_or(0);
+                let encoded = fields.next().unwrap_or_default().to_string();
+                uncommitted.insert(id, (timestamp, encoded));
+            }
+            (Some("commit"), Some(id)) => {
+                uncommitted.remove(&id);
+            }
+            // A record torn by the crash is ignored.
+            _ => {}
+        }
+    }
+    Ok(uncommitted)
+}
+
+/// Converts an IO error into an actor error.
+fn persistence_error(err: io::Error) -> ActorError {
+    ActorError::Persistence(err.to_string())
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,128 @@
+// --------------------------------------------------------
+// Actor library - Pipeline actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+use crate::runtime;
+use crate::{ActorError, OutputActor, StateActor};
+
+/// Connect wires the stages of a pipeline from its input to its current output.
+type Connect<I, O> = Box<
+    dyn FnOnce(mpsc::Receiver<Result<I, ActorError>>) -> mpsc::Receiver<Result<O, ActorError>>
+        + Send,
+>;
+
+/// PipelineActor chains handlers, each running in its own actor, so that the
+/// output of one stage is the input of the next one. The stages work in
+/// parallel on different inputs while each stage keeps the order of them.
+pub struct PipelineActor;
+
+impl PipelineActor {
+    /// Creates a new PipelineBuilder without stages.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<I: Send + 'static>() -> PipelineBuilder<I, I> {
+        PipelineBuilder {
+            connect: Box::new(|input| input),
+            _types: PhantomData,
+        }
+    }
+}
+
+/// PipelineBuilder adds the stages of a pipeline taking inputs of type `I` and
+/// currently producing outputs of type `O`.
+pub struct PipelineBuilder<I, O> {
+    connect: Connect<I, O>,
+    _types: PhantomData<fn(I) -> O>,
+}
+
+impl<I: Send + 'static, O: Send + 'static> PipelineBuilder<I, O> {
+    /// Appends a stage processing the outputs of the previous stage. An error
+    /// returned by the handler ends the pipeline after passing the error to
+    /// the output.
+    pub fn stage<P, H>(self, handler: H) -> PipelineBuilder<I, P>
+    where
+        P: Send + 'static,
+        H: Fn(O) -> Result<P, ActorError> + Send + Sync + 'static,
+    {
+        let connect = self.connect;
+        PipelineBuilder {
+            connect: Box::new(move |input| run_stage(connect(input), handler)),
+            _types: PhantomData,
+        }
+    }
+
+    /// Starts the stages and returns the sink for the inputs and the actor
+    /// yielding the outputs. The pipeline ends when all sinks are dropped.
+    pub fn build(self) -> (InputSink<I>, OutputActor<O>) {
+        let (sender, input) = mpsc::channel(32);
+        let mut outputs = (self.connect)(input);
+        let output = OutputActor::new();
+        let feeder = output.task_sender();
+        runtime::spawn(async move {
+            while let Some(result) = outputs.recv().await {
+                if feeder.send(Box::new(move || result)).await.is_err() {
+                    return;
+                }
+            }
+        });
+        (InputSink { sender }, output)
+    }
+}
+
+/// InputSink passes inputs into a pipeline. Bounded channels between the stages
+/// let sending wait while the pipeline is busy.
+#[derive(Clone)]
+pub struct InputSink<I> {
+    sender: mpsc::Sender<Result<I, ActorError>>,
+}
+
+impl<I: Send + 'static> InputSink<I> {
+    /// Sends an input into the pipeline.
+    pub async fn send(&self, input: I) -> Result<(), ActorError> {
+        self.sender
+            .send(Ok(input))
+            .await
+            .map_err(|_| ActorError::Stopped)
+    }
+}
+
+/// Runs the handler of a stage in its own actor on the received inputs and
+/// passes the results to the returned receiver.
+fn run_stage<I, O, H>(
+    mut input: mpsc::Receiver<Result<I, ActorError>>,
+    handler: H,
+) -> mpsc::Receiver<Result<O, ActorError>>
+where
+    I: Send + 'static,
+    O: Send + 'static,
+    H: Fn(I) -> Result<O, ActorError> + Send + Sync + 'static,
+{
+    let (sender, output) = mpsc::channel(32);
+    let actor = StateActor::new(());
+    let handler = Arc::new(handler);
+    runtime::spawn(async move {
+        while let Some(item) = input.recv().await {
+            let result = match item {
+                Ok(value) => {
+                    let handler = handler.clone();
+                    actor.ask(move |_| handler(value)).await.and_then(|r| r)
+                }
+                Err(err) => Err(err),
+            };
+            let failed = result.is_err();
+            if sender.send(result).await.is_err() || failed {
+                break;
+            }
+        }
+        let _ = actor.stop().await;
+    });
+    output
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
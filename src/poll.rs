@@ -0,0 +1,108 @@
+// --------------------------------------------------------
+// Actor library - Poll actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use crate::{ActorError, ActorState};
+
+/// PollTask is a plain function returning a static error message.
+pub type PollTask = fn() -> Result<(), &'static str>;
+
+/// PollActor queues up to `N` tasks in a fixed buffer and runs them when it is
+/// polled, e.g. inside of a bare-metal main loop. It neither needs a runtime
+/// nor heap memory for its tasks. Like with the AsyncActor a failing task lets
+/// the actor change into the error state and drops the remaining tasks.
+pub struct PollActor<const N: usize> {
+    queue: [Option<PollTask>; N],
+    head: usize,
+    len: usize,
+    state: ActorState,
+    message: Option<&'static str>,
+}
+
+impl<const N: usize> PollActor<N> {
+    /// Creates a new PollActor with an empty queue.
+    pub const fn new() -> Self {
+        Self {
+            queue: [None; N],
+            head: 0,
+            len: 0,
+            state: ActorState::Running,
+            message: None,
+        }
+    }
+
+    /// Enqueues a task to be run by one of the next polls.
+    pub fn send(&mut self, task: PollTask) -> Result<(), ActorError> {
+        match self.state {
+            ActorState::Running => {}
+            ActorState::Stopped => return Err(ActorError::Stopped),
+            ActorState::Error => {
+                return Err(ActorError::Task(self.message.unwrap_or_default().into()))
+            }
+        }
+        if self.len == N {
+            return Err(ActorError::Full);
+        }
+        self.queue[(self.head + self.len) % N] = Some(task);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Runs the oldest queued task. Returns false if there was none.
+    pub fn poll(&mut self) -> bool {
+        let Some(task) = self.pop() else {
+            return false;
+        };
+        if let Err(msg) = task() {
+            self.state = ActorState::Error;
+            self.message = Some(msg);
+            while self.pop().is_some() {}
+        }
+        true
+    }
+
+    /// Stops the actor. Already queued tasks are still run by the next polls.
+    pub fn stop(&mut self) {
+        if self.state == ActorState::Running {
+            self.state = ActorState::Stopped;
+            self.message = Some("Actor stopped");
+        }
+    }
+
+    /// Retrieves the current state of the PollActor.
+    pub fn state(&self) -> ActorState {
+        self.state.clone()
+    }
+
+    /// Retrieves the current message of the PollActor.
+    pub fn message(&self) -> Option<&'static str> {
+        self.message
+    }
+
+    /// Retrieves the number of queued tasks.
+    pub fn queue_len(&self) -> usize {
+        self.len
+    }
+
+    /// Removes the oldest task from the queue.
+    fn pop(&mut self) -> Option<PollTask> {
+        if self.len == 0 {
+            return None;
+        }
+        let task = self.queue[self.head].take();
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+        task
+    }
+}
+
+impl<const N: usize> Default for PollActor<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,529 @@
+// --------------------------------------------------------
+// Actor library - Actor pool
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, Semaphore};
+
+use crate::runtime;
+use crate::{ActorError, ActorId, ActorState, AsyncActor, AsyncActorBuilder, StopReason, TaskId};
+
+/// AsyncActorPool distributes tasks over a number of AsyncActors working in
+/// parallel. Each actor still processes its own tasks sequentially.
+pub struct AsyncActorPool {
+    actors: Mutex<Vec<Arc<AsyncActor>>>,
+    next: AtomicUsize,
+    replaced: AtomicU64,
+    supervised: AtomicBool,
+}
+
+impl AsyncActorPool {
+    /// Creates a new pool of `size` actors with the default configuration.
+    pub fn new(size: usize) -> Arc<Self> {
+        Self::from_actors((0..size).map(|_| AsyncActor::new()).collect())
+    }
+
+    /// Creates a new pool of the given actors.
+    pub fn from_actors(actors: Vec<Arc<AsyncActor>>) -> Arc<Self> {
+        Arc::new(Self {
+            actors: Mutex::new(actors),
+            next: AtomicUsize::new(0),
+            replaced: AtomicU64::new(0),
+            supervised: AtomicBool::new(false),
+        })
+    }
+
+    /// Creates a new pool of `size` actors sharing one backpressure semaphore.
+    /// Each task holds one of the permits from sending until it's done, so
+    /// at most `permits` tasks are queued or running in the whole pool.
+    pub fn with_shared_semaphore(size: usize, permits: usize) -> Arc<Self> {
+        let semaphore = Arc::new(Semaphore::new(permits));
+        Self::from_actors(
+            (0..size)
+                .map(|_| {
+                    let mut builder = AsyncActorBuilder::new();
+                    builder.backpressure = Some((semaphore.clone(), permits));
+                    builder.build()
+                })
+                .collect(),
+        )
+    }
+
+    /// Checks the state of the actors every interval and replaces the ones
+    /// stopped by an error by new ones created by the factory. Actors stopped
+    /// on purpose, e.g. by `drain_and_stop()`, are kept. Tasks sent to a
+    /// member before its replacement may fail with the error of the old
+    /// actor. The monitor ends when the pool is dropped.
+    pub fn enable_health_monitor<F>(self: &Arc<Self>, interval: Duration, factory: F)
+    where
+        F: Fn() -> Arc<AsyncActor> + Send + 'static,
+    {
+        let weak = Arc::downgrade(self);
+        runtime::spawn(async move {
+            loop {
+                runtime::sleep(interval).await;
+                let Some(pool) = weak.upgrade() else {
+                    return;
+                };
+                let failed: Vec<_> = pool
+                    .actors()
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(_, actor)| matches!(actor.stop_reason(), Some(StopReason::Error(_))))
+                    .collect();
+                // The factory may take its time, so the replacements are
+                // created without holding the lock.
+                for (index, old) in failed {
+                    let new = factory();
+                    let mut actors = pool.actors.lock().unwrap();
+                    if actors
+                        .get(index)
+                        .is_some_and(|actor| Arc::ptr_eq(actor, &old))
+                    {
+                        actors[index] = new;
+                        pool.replaced.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Returns the number of failed actors of a supervised pool waiting for
+    /// their restart. They are counted from their failure until the restarted
+    /// actor replaced them.
+    pub fn restarting_count(&self) -> usize {
+        if !self.supervised.load(Ordering::SeqCst) {
+            return 0;
+        }
+        self.actors
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|actor| actor.state() == ActorState::Error)
+            .count()
+    }
+
+    /// Returns the number of actors in `Running` state.
+    pub fn healthy_count(&self) -> usize {
+        self.actors
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|actor| actor.state() == ActorState::Running)
+            .count()
+    }
+
+    /// Returns how many actors have been replaced by the health monitor.
+    pub fn replace_count(&self) -> u64 {
+        self.replaced.load(Ordering::Relaxed)
+    }
+
+    /// Returns the actor at the index computed from the number of actors.
+    fn select(&self, index: impl FnOnce(usize) -> usize) -> Result<Arc<AsyncActor>, ActorError> {
+        let actors = self.actors.lock().unwrap();
+        if actors.is_empty() {
+            return Err(ActorError::Stopped);
+        }
+        Ok(actors[index(actors.len())].clone())
+    }
+
+    /// Sends a task to the next actor of the pool in round-robin order. It's
+    /// the same as `send_round_robin()`.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send<F>(&self, task: F) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        self.send_round_robin(task).await
+    }
+
+    /// Sends a task to the next actor of the pool in round-robin order. Use it
+    /// for independent tasks, it spreads the load evenly but tasks sent one
+    /// after another may be processed in a different order.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send_round_robin<F>(&self, task: F) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        self.next_running()?.send(task).await
+    }
+
+    /// Returns the next running actor in round-robin order. Members which are
+    /// not running, e.g. while restarting, are skipped. If none is running,
+    /// the first one tried is returned to report the error.
+    fn next_running(&self) -> Result<Arc<AsyncActor>, ActorError> {
+        let actors = self.actors.lock().unwrap();
+        if actors.is_empty() {
+            return Err(ActorError::Stopped);
+        }
+        let n = actors.len();
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        Ok((0..n)
+            .map(|i| &actors[(start + i) % n])
+            .find(|actor| actor.state() == ActorState::Running)
+            .unwrap_or(&actors[start % n])
+            .clone())
+    }
+
+    /// Sends a task to the actor selected by the hash of the key. Tasks with
+    /// the same key always go to the same actor and so are processed in the
+    /// order they are sent, e.g. all tasks of one user or one account. Tasks
+    /// of different keys still run in parallel, but a few hot keys may load
+    /// single actors more than others.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send_sticky<F, K>(&self, task: F, key: K) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+        K: Hash,
+    {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        let hash = hasher.finish();
+        let actor = self.select(|n| (hash % n as u64) as usize)?;
+        actor.send(task).await
+    }
+
+    /// Lets the pool restart its member at the index with a new actor of
+    /// the factory whenever a task error ends it.
+    fn supervise<F>(self: &Arc<Self>, index: usize, factory: Arc<F>)
+    where
+        F: Fn() -> Arc<AsyncActor> + Send + Sync + 'static,
+    {
+        self.supervised.store(true, Ordering::SeqCst);
+        // Only weak references are kept while waiting, so dropping the pool
+        // also drops its members. The supervision ends when the member at
+        // the index is gone.
+        let weak = Arc::downgrade(self);
+        runtime::spawn(async move {
+            loop {
+                let Some((actor, joined)) = weak.upgrade().and_then(|pool| {
+                    let actors = pool.actors.lock().unwrap();
+                    let actor = actors.get(index)?;
+                    Some((Arc::downgrade(actor), actor.join()))
+                }) else {
+                    return;
+                };
+                if joined.await != ActorState::Error {
+                    return;
+                }
+                let Some(pool) = weak.upgrade() else {
+                    return;
+                };
+                let restarted = factory();
+                let mut actors = pool.actors.lock().unwrap();
+                match actors.get_mut(index) {
+                    Some(member) if Arc::as_ptr(member) == actor.as_ptr() => {
+                        *member = restarted;
+                        pool.replaced.fetch_add(1, Ordering::Relaxed);
+                    }
+                    // Replaced meanwhile, e.g. by the health monitor.
+                    Some(_) => {}
+                    None => return,
+                }
+            }
+        });
+    }
+
+    /// Maps the inputs in parallel on the actors of the pool. The results are
+    /// returned in the order of the inputs, each one failing on its own if its
+    /// task couldn't be sent or processed.
+    pub async fn map<T, R, M>(&self, inputs: Vec<T>, mapper: M) -> Vec<Result<R, ActorError>>
+    where
+        T: Send + 'static,
+        R: Send + 'static,
+        M: Fn(T) -> R + Send + Clone + 'static,
+    {
+        // Fan out all mapper tasks before waiting for the first result.
+        let mut responses = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            let (responder, response) = oneshot::channel();
+            let mapper = mapper.clone();
+            let sent = match self.next_running() {
+                Ok(actor) => {
+                    actor
+                        .send_unsampled(move || {
+                            let _ = responder.send(mapper(input));
+                            Ok(())
+                        })
+                        .await
+                }
+                Err(err) => Err(err),
+            };
+            responses.push(sent.map(|_| response));
+        }
+        let mut results = Vec::with_capacity(responses.len());
+        for response in responses {
+            results.push(match response {
+                Ok(response) => response.await.map_err(|_| ActorError::Stopped),
+                Err(err) => Err(err),
+            });
+        }
+        results
+    }
+
+    /// Maps the inputs in parallel on the actors of the pool and reduces the
+    /// results, kept in the order of the inputs, on one actor afterwards.
+    pub async fn map_reduce<T, R, M, D>(
+        &self,
+        inputs: Vec<T>,
+        mapper: M,
+        reducer: D,
+    ) -> Result<R, ActorError>
+    where
+        T: Send + Clone + 'static,
+        R: Send + 'static,
+        M: Fn(T) -> R + Send + Clone + 'static,
+        D: Fn(Vec<R>) -> R + Send + 'static,
+    {
+        let reducing = self.select(|_| 0)?;
+        // Fan out all mapper tasks before waiting for the first result.
+        let mut responses = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            let (responder, response) = oneshot::channel();
+            let mapper = mapper.clone();
+            self.next_running()?
+                .send_unsampled(move || {
+                    let _ = responder.send(mapper(input));
+                    Ok(())
+                })
+                .await?;
+            responses.push(response);
+        }
+        let mut results = Vec::with_capacity(responses.len());
+        for response in responses {
+            results.push(response.await.map_err(|_| ActorError::Stopped)?);
+        }
+        reducing.ask(move || reducer(results)).await
+    }
+
+    /// Sends a task of the factory to each actor and returns the first result,
+    /// a hedged request to reduce the tail latency. The tasks still waiting
+    /// afterwards are cancelled, running ones complete but their results are
+    /// dropped.
+    pub async fn ask_first<R, F, T>(&self, task_factory: T) -> Result<R, ActorError>
+    where
+        R: Send + 'static,
+        F: FnOnce() -> R + Send + 'static,
+        T: Fn() -> F,
+    {
+        let actors = self.actors();
+        let (responder, mut responses) = mpsc::channel(actors.len().max(1));
+        let mut sent = Vec::with_capacity(actors.len());
+        let mut failed = Err(ActorError::Stopped);
+        for actor in actors {
+            let task = task_factory();
+            let responder = responder.clone();
+            let result = actor
+                .send_unsampled(move || {
+                    let _ = responder.try_send(task());
+                    Ok(())
+                })
+                .await;
+            match result {
+                Ok(task_id) => sent.push((actor, task_id)),
+                Err(err) => failed = Err(err),
+            }
+        }
+        // Without the own responder the channel closes when no task is left.
+        drop(responder);
+        let Some(first) = responses.recv().await else {
+            return failed;
+        };
+        for (actor, task_id) in sent {
+            actor.cancel(task_id);
+        }
+        Ok(first)
+    }
+
+    /// Stops all actors concurrently after they processed their queued tasks
+    /// and waits for them. Actors not stopped within the timeout are stopped
+    /// immediately, dropping their remaining tasks, and report
+    /// `ActorError::Timeout`. Returns one result per actor in pool order.
+    pub async fn drain_and_stop(&self, timeout: Duration) -> Vec<Result<(), ActorError>> {
+        let actors = self.actors();
+        let mut results = Vec::with_capacity(actors.len());
+        for actor in actors {
+            let (responder, result) = oneshot::channel();
+            runtime::spawn(async move {
+                let drained = runtime::timeout(timeout, async {
+                    let _ = actor.stop().await;
+                    actor.join().await
+                })
+                .await;
+                let _ = responder.send(match drained {
+                    Some(ActorState::Error) => {
+                        Err(ActorError::Task(actor.message().unwrap_or_default()))
+                    }
+                    Some(_) => Ok(()),
+                    None => {
+                        let _ = actor.stop_now(StopReason::Timeout);
+                        Err(ActorError::Timeout)
+                    }
+                });
+            });
+            results.push(result);
+        }
+        let mut stopped = Vec::with_capacity(results.len());
+        for result in results {
+            stopped.push(result.await.unwrap_or(Err(ActorError::Stopped)));
+        }
+        stopped
+    }
+
+    /// Returns the current actors of the pool.
+    pub fn actors(&self) -> Vec<Arc<AsyncActor>> {
+        self.actors.lock().unwrap().clone()
+    }
+
+    /// Returns the number of actors in the pool.
+    pub fn len(&self) -> usize {
+        self.actors.lock().unwrap().len()
+    }
+
+    /// Returns true if the pool has no actors.
+    pub fn is_empty(&self) -> bool {
+        self.actors.lock().unwrap().is_empty()
+    }
+
+    /// Returns the actor with the fewest waiting tasks, `None` if the pool
+    /// is empty.
+    pub fn least_loaded(&self) -> Option<Arc<AsyncActor>> {
+        self.actors()
+            .into_iter()
+            .min_by_key(|actor| actor.queue_len())
+    }
+
+    /// Returns the actor with the most waiting tasks, `None` if the pool is
+    /// empty.
+    pub fn most_loaded(&self) -> Option<Arc<AsyncActor>> {
+        self.actors()
+            .into_iter()
+            .max_by_key(|actor| actor.queue_len())
+    }
+
+    /// Returns the ID and the number of waiting tasks of each actor in pool
+    /// order.
+    pub fn load_distribution(&self) -> Vec<(ActorId, usize)> {
+        self.actors
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|actor| (actor.id(), actor.queue_len()))
+            .collect()
+    }
+
+    /// Adds the actors to the pool.
+    pub(crate) fn push(&self, actors: impl IntoIterator<Item = Arc<AsyncActor>>) {
+        self.actors.lock().unwrap().extend(actors);
+    }
+
+    /// Removes the `n` actors with the fewest waiting tasks from the pool and
+    /// returns them.
+    pub(crate) fn remove_least_loaded(&self, n: usize) -> Vec<Arc<AsyncActor>> {
+        let mut actors = self.actors.lock().unwrap();
+        let mut removed = Vec::with_capacity(n.min(actors.len()));
+        for _ in 0..n {
+            let Some(index) = (0..actors.len()).min_by_key(|&i| actors[i].queue_len()) else {
+                break;
+            };
+            removed.push(actors.remove(index));
+        }
+        removed
+    }
+
+    /// Moves waiting tasks from hot actors, with more than twice the average
+    /// queue depth, to cold ones, with less than half of it. The newest tasks
+    /// are moved and get new task IDs from their new actor. Returns the number
+    /// of moved tasks. If a cold actor rejects a task, that task is dropped
+    /// with a `Dropped` event and the others taken with it are enqueued again
+    /// on their hot actor.
+    pub async fn rebalance(&self) -> usize {
+        let actors = self.actors();
+        let depths: Vec<usize> = actors.iter().map(|actor| actor.queue_len()).collect();
+        if depths.is_empty() {
+            return 0;
+        }
+        // Compare the depths multiplied by the number of actors with the total
+        // instead of the depths with the average to stay with integers.
+        let n = depths.len();
+        let total: usize = depths.iter().sum();
+        let target = total.div_ceil(n);
+        let hot: Vec<usize> = (0..n).filter(|&i| depths[i] * n > 2 * total).collect();
+        let mut cold = (0..n)
+            .filter(|&i| 2 * depths[i] * n < total && actors[i].is_accepting())
+            .map(|i| {
+                let room = actors[i].capacity().saturating_sub(depths[i]);
+                (i, target.saturating_sub(depths[i]).min(room))
+            })
+            .peekable();
+
+        let mut moved = 0;
+        for i in hot {
+            let mut excess = depths[i].saturating_sub(target);
+            while excess > 0 {
+                let Some((j, room)) = cold.peek_mut() else {
+                    return moved;
+                };
+                let count = excess.min(*room);
+                let receiver = &actors[*j];
+                let mut taken = actors[i].take_queued(count).into_iter();
+                let mut handled = 0;
+                let mut failed = false;
+                while let Some((task_id, task)) = taken.next() {
+                    handled += 1;
+                    if receiver
+                        .enqueue(receiver.next_task_id(), task)
+                        .await
+                        .is_ok()
+                    {
+                        moved += 1;
+                        continue;
+                    }
+                    // The rejected task is lost, the remaining ones go back
+                    // and the receiver isn't tried again.
+                    actors[i].drop_lost(task_id);
+                    for (task_id, task) in taken.by_ref() {
+                        if actors[i].try_enqueue(task_id, task).is_err() {
+                            actors[i].drop_lost(task_id);
+                        }
+                    }
+                    failed = true;
+                }
+                excess -= handled;
+                *room -= handled;
+                if failed || *room == 0 {
+                    cold.next();
+                }
+                // The hot actor processed its tasks meanwhile.
+                if !failed && handled < count {
+                    break;
+                }
+            }
+        }
+        moved
+    }
+}
+
+/// Creates a pool of `size` actors of the factory, each one restarted with
+/// a new actor of the factory when a task error ends it. Sending in
+/// round-robin order skips the members while they restart. Stopped members
+/// are not restarted.
+pub fn spawn_supervised_pool<F>(size: usize, factory: F) -> Arc<AsyncActorPool>
+where
+    F: Fn() -> Arc<AsyncActor> + Send + Sync + 'static,
+{
+    let pool = AsyncActorPool::from_actors((0..size).map(|_| factory()).collect());
+    let factory = Arc::new(factory);
+    for index in 0..size {
+        pool.supervise(index, factory.clone());
+    }
+    pool
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,118 @@
+// --------------------------------------------------------
+// Actor library - Memory pressure actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::runtime;
+use crate::{ActorError, AsyncActor, TaskId};
+
+/// ShedPolicy defines how a MemoryPressureActor sheds tasks while the
+/// available memory is low.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShedPolicy {
+    /// New tasks are rejected with `ActorError::MemoryPressure`.
+    DropNew,
+    /// The oldest waiting task is dropped for each new one.
+    DropOldest,
+}
+
+/// MemoryPressureActor forwards tasks to an AsyncActor and sheds tasks while
+/// the available system memory is below the threshold. The memory is checked
+/// once per interval, normal operation resumes when it has recovered.
+pub struct MemoryPressureActor {
+    inner: Arc<AsyncActor>,
+    policy: ShedPolicy,
+    pressure: AtomicBool,
+}
+
+impl MemoryPressureActor {
+    /// Creates a new MemoryPressureActor checking the available system memory
+    /// every second. On systems without `/proc/meminfo` no tasks are shed.
+    pub fn new(inner: Arc<AsyncActor>, threshold_bytes: usize, policy: ShedPolicy) -> Arc<Self> {
+        Self::with_probe(
+            inner,
+            threshold_bytes,
+            policy,
+            Duration::from_secs(1),
+            available_memory,
+        )
+    }
+
+    /// Creates a new MemoryPressureActor checking the available memory with
+    /// the probe every interval. A probe returning `None` means no pressure.
+    pub fn with_probe<P>(
+        inner: Arc<AsyncActor>,
+        threshold_bytes: usize,
+        policy: ShedPolicy,
+        interval: Duration,
+        probe: P,
+    ) -> Arc<Self>
+    where
+        P: Fn() -> Option<usize> + Send + 'static,
+    {
+        let under_threshold = move || probe().is_some_and(|bytes| bytes < threshold_bytes);
+        let actor = Arc::new(Self {
+            inner,
+            policy,
+            pressure: AtomicBool::new(under_threshold()),
+        });
+        let weak = Arc::downgrade(&actor);
+        runtime::spawn(async move {
+            loop {
+                runtime::sleep(interval).await;
+                let Some(actor) = weak.upgrade() else {
+                    return;
+                };
+                actor.pressure.store(under_threshold(), Ordering::SeqCst);
+            }
+        });
+        actor
+    }
+
+    /// Sends a task to the inner actor, shedding tasks as defined by the
+    /// policy while the memory is low.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send<F>(&self, task: F) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        if self.is_under_pressure() {
+            match self.policy {
+                ShedPolicy::DropNew => return Err(ActorError::MemoryPressure),
+                ShedPolicy::DropOldest => {
+                    self.inner.drop_oldest();
+                }
+            }
+        }
+        self.inner.send(task).await
+    }
+
+    /// Returns true if the available memory has been below the threshold at
+    /// the last check.
+    pub fn is_under_pressure(&self) -> bool {
+        self.pressure.load(Ordering::SeqCst)
+    }
+
+    /// Returns the actor the tasks are forwarded to.
+    pub fn inner(&self) -> &Arc<AsyncActor> {
+        &self.inner
+    }
+}
+
+/// Reads the available system memory in bytes from `/proc/meminfo`.
+fn available_memory() -> Option<usize> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let line = meminfo
+        .lines()
+        .find(|line| line.starts_with("MemAvailable:"))?;
+    let kib: usize = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,82 @@
+// --------------------------------------------------------
+// Actor library - Actor proxy
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use crate::{ActorError, AsyncActor, NetworkActor, TaskSerializer};
+
+/// LocalHandler executes the tasks of a local ActorProxy.
+type LocalHandler<T> = Arc<dyn Fn(T) -> Result<(), String> + Send + Sync>;
+
+/// Target is the actor an ActorProxy sends its tasks to.
+enum Target<S: TaskSerializer> {
+    Local(Arc<AsyncActor>, LocalHandler<S::Task>),
+    Remote(Arc<NetworkActor<S>>),
+}
+
+/// ActorProxy sends the tasks of a TaskSerializer either to a local actor or
+/// via a NetworkActor to a remote one. The local actor executes them with a
+/// handler like the NetworkActorServer does, so callers get the same results
+/// without knowing where the tasks run.
+pub struct ActorProxy<S: TaskSerializer> {
+    target: Target<S>,
+}
+
+impl<S: TaskSerializer> ActorProxy<S> {
+    /// Creates a proxy executing the tasks with the handler on the actor.
+    pub fn local<H>(actor: Arc<AsyncActor>, handler: H) -> Self
+    where
+        H: Fn(S::Task) -> Result<(), String> + Send + Sync + 'static,
+    {
+        Self {
+            target: Target::Local(actor, Arc::new(handler)),
+        }
+    }
+
+    /// Creates a proxy sending the tasks to the NetworkActorServer at the
+    /// address.
+    pub async fn remote(addr: SocketAddr, serializer: S) -> io::Result<Self> {
+        Ok(Self {
+            target: Target::Remote(NetworkActor::connect(addr, serializer).await?),
+        })
+    }
+
+    /// Sends a task and waits until it has been executed. Errors returned by
+    /// the handler are returned as `ActorError::Task`.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send(&self, task: S::Task) -> Result<(), ActorError> {
+        match &self.target {
+            Target::Local(actor, handler) => {
+                let handler = handler.clone();
+                actor
+                    .ask(move || handler(task))
+                    .await?
+                    .map_err(ActorError::Task)
+            }
+            Target::Remote(network) => network.send(task).await,
+        }
+    }
+
+    /// Returns true if the tasks are executed in this process.
+    pub fn is_local(&self) -> bool {
+        matches!(self.target, Target::Local(..))
+    }
+}
+
+impl<S: TaskSerializer> Clone for ActorProxy<S> {
+    fn clone(&self) -> Self {
+        let target = match &self.target {
+            Target::Local(actor, handler) => Target::Local(actor.clone(), handler.clone()),
+            Target::Remote(network) => Target::Remote(network.clone()),
+        };
+        Self { target }
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
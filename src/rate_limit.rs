@@ -0,0 +1,175 @@
+// --------------------------------------------------------
+// Actor library - Per-sender rate limiting
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+use crate::runtime;
+use crate::{ActorError, ActorTask, AsyncActor, SenderId, Task, TaskId};
+
+/// SenderLane tracks the sends of one sender in the sliding window and holds
+/// its slow lane once the sender exceeded the limit.
+struct SenderLane {
+    sent: VecDeque<Instant>,
+    slow_lane: Option<mpsc::Sender<(TaskId, Task)>>,
+    queued: usize,
+}
+
+impl SenderLane {
+    /// Removes the sends which dropped out of the window.
+    fn prune(&mut self, now: Instant, window: Duration) {
+        while let Some(sent) = self.sent.front() {
+            if now.duration_since(*sent) < window {
+                break;
+            }
+            self.sent.pop_front();
+        }
+    }
+
+    /// Returns true if the sender has neither sends in the window nor waiting
+    /// tasks.
+    fn is_idle(&self) -> bool {
+        self.sent.is_empty() && self.queued == 0
+    }
+}
+
+/// RateLimiter limits the number of tasks each sender may enqueue per window.
+pub(crate) struct RateLimiter {
+    max: usize,
+    window: Duration,
+    slow_lane_depth: usize,
+    lanes: Mutex<HashMap<SenderId, SenderLane>>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(max: usize, window: Duration, slow_lane_depth: usize) -> Self {
+        Self {
+            max,
+            window,
+            slow_lane_depth,
+            lanes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Admits a task of the sender. It's returned if it may be enqueued directly,
+    /// otherwise it's moved into the slow lane of the sender.
+    pub(crate) fn admit(
+        &self,
+        actor: &Arc<AsyncActor>,
+        sender_id: SenderId,
+        task_id: TaskId,
+        task: Task,
+    ) -> Result<Option<Task>, ActorError> {
+        let now = Instant::now();
+        let mut lanes = self.lanes.lock().unwrap();
+        if !lanes.contains_key(&sender_id) {
+            // Lanes are only added here, so the idle ones are removed before.
+            // Dropping their slow lane also ends its forwarding.
+            lanes.retain(|_, lane| {
+                lane.prune(now, self.window);
+                !lane.is_idle()
+            });
+        }
+        let lane = lanes.entry(sender_id).or_insert_with(|| SenderLane {
+            sent: VecDeque::new(),
+            slow_lane: None,
+            queued: 0,
+        });
+        lane.prune(now, self.window);
+
+        // Tasks waiting in the slow lane keep their order, so new tasks have
+        // to queue up behind them.
+        if lane.queued == 0 && lane.sent.len() < self.max {
+            lane.sent.push_back(now);
+            return Ok(Some(task));
+        }
+
+        let slow_lane = lane.slow_lane.get_or_insert_with(|| {
+            let (slow_lane, receiver) = mpsc::channel(self.slow_lane_depth.max(1));
+            runtime::spawn(forward(Arc::downgrade(actor), sender_id, receiver));
+            slow_lane
+        });
+        slow_lane
+            .try_send((task_id, task))
+            .map_err(|_| ActorError::RateLimited)?;
+        lane.queued += 1;
+        Ok(None)
+    }
+
+    /// Reserves a send for a task of the slow lane. Returns the time to wait if
+    /// the sender still exceeds the limit.
+    fn reserve(&self, sender_id: SenderId) -> Option<Duration> {
+        let now = Instant::now();
+        let mut lanes = self.lanes.lock().unwrap();
+        let lane = lanes.get_mut(&sender_id)?;
+        lane.prune(now, self.window);
+        if lane.sent.len() < self.max {
+            lane.sent.push_back(now);
+            return None;
+        }
+        lane.sent
+            .front()
+            .map(|sent| self.window.saturating_sub(now.duration_since(*sent)))
+    }
+
+    /// Gives back the last reserved send of the sender after its task couldn't
+    /// be enqueued.
+    pub(crate) fn unreserve(&self, sender_id: SenderId) {
+        if let Some(lane) = self.lanes.lock().unwrap().get_mut(&sender_id) {
+            lane.sent.pop_back();
+        }
+    }
+
+    /// Marks a task of the slow lane as forwarded to the actor.
+    fn forwarded(&self, sender_id: SenderId) {
+        if let Some(lane) = self.lanes.lock().unwrap().get_mut(&sender_id) {
+            lane.queued -= 1;
+        }
+    }
+}
+
+/// Forwards the tasks of a slow lane to the actor as soon as the sender is
+/// within its rate limit again. Ends together with the actor.
+async fn forward(
+    actor: Weak<AsyncActor>,
+    sender_id: SenderId,
+    mut receiver: mpsc::Receiver<(TaskId, Task)>,
+) {
+    while let Some((task_id, task)) = receiver.recv().await {
+        loop {
+            let wait = match actor.upgrade() {
+                Some(actor) => actor.rate_limiter().and_then(|l| l.reserve(sender_id)),
+                None => return,
+            };
+            match wait {
+                Some(wait) => runtime::sleep(wait).await,
+                None => break,
+            }
+        }
+        let Some(actor) = actor.upgrade() else {
+            return;
+        };
+        // The sender already got the ID, so a failure is reported as drop.
+        let failed = actor
+            .enqueue(task_id, ActorTask::Closure(task))
+            .await
+            .is_err();
+        if failed {
+            actor.drop_lost(task_id);
+        }
+        if let Some(limiter) = actor.rate_limiter() {
+            if failed {
+                limiter.unreserve(sender_id);
+            }
+            limiter.forwarded(sender_id);
+        }
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,128 @@
+// --------------------------------------------------------
+// Actor library - Read/write actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+
+use crate::runtime;
+use crate::{ActorError, ActorState};
+
+/// ReadTask is a function or closure reading the state of a ReadWriteActor.
+pub type ReadTask<S> = Box<dyn FnOnce(&S) -> Result<(), ActorError> + Send>;
+
+/// WriteTask is a function or closure changing the state of a ReadWriteActor.
+pub type WriteTask<S> = Box<dyn FnOnce(&mut S) -> Result<(), ActorError> + Send>;
+
+/// Message is passed from the actor to its loop via the write lane.
+enum Message<S> {
+    Write(WriteTask<S>),
+    Stop,
+}
+
+/// ReadWriteActor owns a state like the StateActor, but has two lanes for
+/// its tasks. Reads have priority, all waiting reads are processed before
+/// the next write. So many concurrent read queries are served quickly while
+/// occasional writes wait. Reads sent continuously can starve the writes.
+///
+/// A failing task stops the processing and the actor keeps the error.
+pub struct ReadWriteActor<S: Send + 'static> {
+    reads: mpsc::Sender<ReadTask<S>>,
+    writes: mpsc::Sender<Message<S>>,
+    state: Arc<Mutex<ActorState>>,
+    error: Arc<Mutex<Option<ActorError>>>,
+}
+
+impl<S: Send + 'static> ReadWriteActor<S> {
+    /// Creates a new ReadWriteActor owning the given state.
+    pub fn new(mut owned: S) -> Arc<Self> {
+        let (reads, mut read_receiver) = mpsc::channel::<ReadTask<S>>(32);
+        let (writes, mut write_receiver) = mpsc::channel::<Message<S>>(32);
+        let state = Arc::new(Mutex::new(ActorState::Running));
+        let error = Arc::new(Mutex::new(None));
+
+        let actor = Arc::new(Self {
+            reads,
+            writes,
+            state: state.clone(),
+            error: error.clone(),
+        });
+
+        runtime::spawn(async move {
+            loop {
+                let result = tokio::select! {
+                    biased;
+                    Some(task) = read_receiver.recv() => task(&owned),
+                    msg = write_receiver.recv() => match msg {
+                        Some(Message::Write(task)) => task(&mut owned),
+                        Some(Message::Stop) | None => break,
+                    },
+                };
+                if let Err(err) = result {
+                    *state.lock().unwrap() = ActorState::Error;
+                    *error.lock().unwrap() = Some(err);
+                    return;
+                }
+            }
+            *state.lock().unwrap() = ActorState::Stopped;
+        });
+
+        actor
+    }
+
+    /// Sends a task reading the state via the priority lane.
+    pub async fn send_read<F>(&self, task: F) -> Result<(), ActorError>
+    where
+        F: FnOnce(&S) -> Result<(), ActorError> + Send + 'static,
+    {
+        self.check()?;
+        self.reads
+            .send(Box::new(task))
+            .await
+            .map_err(|err| ActorError::Send(err.to_string()))
+    }
+
+    /// Sends a task changing the state via the normal lane.
+    pub async fn send_write<F>(&self, task: F) -> Result<(), ActorError>
+    where
+        F: FnOnce(&mut S) -> Result<(), ActorError> + Send + 'static,
+    {
+        self.check()?;
+        self.writes
+            .send(Message::Write(Box::new(task)))
+            .await
+            .map_err(|err| ActorError::Send(err.to_string()))
+    }
+
+    /// Retrieves the current state of the ReadWriteActor.
+    pub fn state(&self) -> ActorState {
+        self.state.lock().unwrap().clone()
+    }
+
+    /// Retrieves the error of the failed task if the actor is in error state.
+    pub fn error(&self) -> Option<ActorError> {
+        self.error.lock().unwrap().clone()
+    }
+
+    /// Stops the actor after the writes already in the queue are processed.
+    pub async fn stop(&self) -> Result<(), ActorError> {
+        self.writes
+            .send(Message::Stop)
+            .await
+            .map_err(|err| ActorError::Send(err.to_string()))
+    }
+
+    /// Checks if the actor still accepts tasks.
+    fn check(&self) -> Result<(), ActorError> {
+        match *self.state.lock().unwrap() {
+            ActorState::Running => Ok(()),
+            ActorState::Stopped => Err(ActorError::Stopped),
+            ActorState::Error => Err(self.error().unwrap_or(ActorError::Stopped)),
+        }
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
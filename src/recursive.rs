@@ -0,0 +1,105 @@
+// --------------------------------------------------------
+// Actor library - Recursive actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::sync::Arc;
+
+use crate::{ActorError, AsyncActor, TaskId, WeakActorRef};
+
+/// RecursiveActor lets tasks send further tasks to their own actor. Tasks run
+/// synchronously inside of the actor loop, so they can't await `send()`, and
+/// capturing the `Arc<AsyncActor>` in a queued task keeps the actor alive by
+/// a reference cycle. Instead each task gets a `Recursion` holding a weak
+/// reference to the actor, sending without waiting.
+///
+/// The tasks aren't called recursively but enqueued, so the stack depth stays
+/// the same for each level. A maximum depth protects against an endless
+/// recursion, exceeding it fails with `ActorError::RecursionLimit`.
+///
+/// Async functions calling themselves outside of the actor, e.g. to walk a
+/// tree and send a task per node, need a boxed future for the recursion. The
+/// `async_recursion` crate generates it:
+///
+/// ```ignore
+/// #[async_recursion::async_recursion]
+/// async fn visit(actor: Arc<AsyncActor>, node: Node) -> Result<(), ActorError> {
+///     actor.send(move || node.process()).await?;
+///     for child in node.children {
+///         visit(actor.clone(), child).await?;
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct RecursiveActor {
+    actor: Arc<AsyncActor>,
+    max_depth: usize,
+}
+
+impl RecursiveActor {
+    /// Creates a new RecursiveActor with a maximum depth of 1024.
+    pub fn new(actor: Arc<AsyncActor>) -> Arc<Self> {
+        Self::with_max_depth(actor, 1024)
+    }
+
+    /// Creates a new RecursiveActor with the given maximum depth.
+    pub fn with_max_depth(actor: Arc<AsyncActor>, max_depth: usize) -> Arc<Self> {
+        Arc::new(Self { actor, max_depth })
+    }
+
+    /// Sends the first task of a recursion to the actor.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send<F>(&self, task: F) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce(&Recursion) -> Result<(), String> + Send + 'static,
+    {
+        let recursion = Recursion {
+            actor: self.actor.downgrade(),
+            depth: 0,
+            max_depth: self.max_depth,
+        };
+        self.actor.send(move || task(&recursion)).await
+    }
+
+    /// Returns the actor running the tasks.
+    pub fn actor(&self) -> &Arc<AsyncActor> {
+        &self.actor
+    }
+}
+
+/// Recursion is passed to the tasks of a RecursiveActor for sending the tasks
+/// of the next level.
+pub struct Recursion {
+    actor: WeakActorRef,
+    depth: usize,
+    max_depth: usize,
+}
+
+impl Recursion {
+    /// Returns the depth of the current task, the first task has depth 0.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Sends a task of the next level without waiting. Fails if the maximum
+    /// depth is reached, the queue is full, or the actor is gone.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub fn send<F>(&self, task: F) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce(&Recursion) -> Result<(), String> + Send + 'static,
+    {
+        if self.depth >= self.max_depth {
+            return Err(ActorError::RecursionLimit);
+        }
+        let next = Recursion {
+            actor: self.actor.clone(),
+            depth: self.depth + 1,
+            max_depth: self.max_depth,
+        };
+        self.actor.try_send(move || task(&next))
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,104 @@
+// --------------------------------------------------------
+// Actor library - Task registry
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use crate::{ActorId, TaskEventKind, TaskId};
+
+/// InFlightTask describes a task enqueued or running in an actor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InFlightTask {
+    pub task_id: TaskId,
+    pub actor_id: ActorId,
+    pub actor_name: Option<String>,
+    pub enqueued_at: Instant,
+    /// The time the actor loop started the task, `None` while it's waiting.
+    pub started_at: Option<Instant>,
+}
+
+/// TaskRegistry tracks the tasks in flight in all AsyncActors of the process,
+/// e.g. for debug endpoints or to find stuck tasks. As task IDs are only
+/// unique per actor, tasks are identified by both IDs.
+pub struct TaskRegistry {
+    tasks: Mutex<HashMap<(ActorId, TaskId), InFlightTask>>,
+}
+
+impl TaskRegistry {
+    /// Returns the process wide registry.
+    pub fn global() -> &'static TaskRegistry {
+        static REGISTRY: OnceLock<TaskRegistry> = OnceLock::new();
+        REGISTRY.get_or_init(|| TaskRegistry {
+            tasks: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Registers a task enqueued in the actor. The AsyncActors do it on their
+    /// own, this is for tasks of other actors.
+    pub fn register(&self, task_id: TaskId, actor_id: ActorId, enqueued_at: Instant) {
+        self.insert(task_id, actor_id, None, enqueued_at);
+    }
+
+    /// Removes a completed task of the actor.
+    pub fn complete(&self, actor_id: ActorId, task_id: TaskId) {
+        self.tasks.lock().unwrap().remove(&(actor_id, task_id));
+    }
+
+    /// Returns the tasks in flight, the longest waiting first.
+    pub fn in_flight(&self) -> Vec<InFlightTask> {
+        let mut tasks: Vec<_> = self.tasks.lock().unwrap().values().cloned().collect();
+        tasks.sort_by_key(|task| task.enqueued_at);
+        tasks
+    }
+
+    /// Tracks a task of an AsyncActor by its events.
+    pub(crate) fn track(
+        &self,
+        actor_id: ActorId,
+        actor_name: Option<&str>,
+        task_id: TaskId,
+        kind: &TaskEventKind,
+    ) {
+        match kind {
+            TaskEventKind::Enqueued => self.insert(
+                task_id,
+                actor_id,
+                actor_name.map(str::to_string),
+                Instant::now(),
+            ),
+            TaskEventKind::Started => {
+                if let Some(task) = self.tasks.lock().unwrap().get_mut(&(actor_id, task_id)) {
+                    task.started_at = Some(Instant::now());
+                }
+            }
+            TaskEventKind::Stuck { .. } => {}
+            _ => self.complete(actor_id, task_id),
+        }
+    }
+
+    fn insert(
+        &self,
+        task_id: TaskId,
+        actor_id: ActorId,
+        actor_name: Option<String>,
+        enqueued_at: Instant,
+    ) {
+        self.tasks.lock().unwrap().insert(
+            (actor_id, task_id),
+            InFlightTask {
+                task_id,
+                actor_id,
+                actor_name,
+                enqueued_at,
+                started_at: None,
+            },
+        );
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
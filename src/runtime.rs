@@ -0,0 +1,94 @@
+// --------------------------------------------------------
+// Actor library - Runtime
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::future::Future;
+use std::time::Duration;
+
+/// Spawns the loop of an actor. All functions the actors need from the async
+/// runtime are kept here, so exchanging the runtime only touches this file.
+pub(crate) fn spawn<F>(future: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    tokio::spawn(future);
+}
+
+/// Runs the future on the current thread until it completes. It must not be
+/// called from async code, as it blocks the thread of the runtime. Only
+/// futures not needing a runtime driver, like the ones of the mailbox, can
+/// be run.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    struct ThreadWaker(std::thread::Thread);
+
+    impl std::task::Wake for ThreadWaker {
+        fn wake(self: std::sync::Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = std::sync::Arc::new(ThreadWaker(std::thread::current())).into();
+    let mut cx = std::task::Context::from_waker(&waker);
+    let mut future = std::pin::pin!(future);
+    loop {
+        if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        std::thread::park();
+    }
+}
+
+/// Runs a blocking function on a thread reserved for blocking work and
+/// returns its result. Returns `None` if the function panicked.
+pub(crate) async fn spawn_blocking<F, R>(f: F) -> Option<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    tokio::task::spawn_blocking(f).await.ok()
+}
+
+/// Yields to other tasks of the runtime.
+pub(crate) async fn yield_now() {
+    tokio::task::yield_now().await;
+}
+
+/// Returns a future resolving when the process receives SIGTERM or SIGINT.
+/// The handlers are registered immediately, not when polling the future.
+#[cfg(unix)]
+pub(crate) fn shutdown_signal() -> std::io::Result<impl Future<Output = ()> + Send> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut terminate = signal(SignalKind::terminate())?;
+    let mut interrupt = signal(SignalKind::interrupt())?;
+    Ok(async move {
+        tokio::select! {
+            _ = terminate.recv() => {}
+            _ = interrupt.recv() => {}
+        }
+    })
+}
+
+/// Returns a future resolving when the process receives Ctrl-C.
+#[cfg(not(unix))]
+pub(crate) fn shutdown_signal() -> std::io::Result<impl Future<Output = ()> + Send> {
+    Ok(async {
+        let _ = tokio::signal::ctrl_c().await;
+    })
+}
+
+/// Waits for the given duration.
+pub(crate) async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+/// Runs the future until it completes or the duration has passed. Returns
+/// `None` on timeout.
+pub(crate) async fn timeout<F: Future>(duration: Duration, future: F) -> Option<F::Output> {
+    tokio::time::timeout(duration, future).await.ok()
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,64 @@
+// --------------------------------------------------------
+// Actor library - Task sampling
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashSet;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use crate::TaskId;
+
+/// TaskSampler decides randomly for each task if it's processed. It's only
+/// used by the actor loop, so the state needs no synchronized update. Tasks
+/// exempted when sending them, e.g. the ones answering an `ask()`, are always
+/// processed.
+pub(crate) struct TaskSampler {
+    threshold: u64,
+    state: AtomicU64,
+    exempt: Mutex<HashSet<TaskId>>,
+}
+
+impl TaskSampler {
+    /// Creates a sampler keeping the given part of the tasks, the rate is
+    /// clamped to 0.0 to 1.0.
+    pub(crate) fn new(rate: f64) -> Self {
+        // The seed must not be zero for xorshift.
+        let seed = RandomState::new().build_hasher().finish() | 1;
+        Self {
+            threshold: (rate.clamp(0.0, 1.0) * u64::MAX as f64) as u64,
+            state: AtomicU64::new(seed),
+            exempt: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Exempts the task from sampling.
+    pub(crate) fn exempt(&self, task_id: TaskId) {
+        self.exempt.lock().unwrap().insert(task_id);
+    }
+
+    /// Removes the exemption of a task which has not been enqueued.
+    pub(crate) fn unexempt(&self, task_id: TaskId) {
+        self.exempt.lock().unwrap().remove(&task_id);
+    }
+
+    /// Returns true if the task is processed.
+    pub(crate) fn keep(&self, task_id: TaskId) -> bool {
+        if self.exempt.lock().unwrap().remove(&task_id) {
+            return true;
+        }
+        // xorshift64* is fast and good enough for sampling.
+        let mut x = self.state.load(Ordering::Relaxed);
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.state.store(x, Ordering::Relaxed);
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D) < self.threshold
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,54 @@
+// --------------------------------------------------------
+// Actor library - Actor scope
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::sync::{Arc, Mutex};
+
+use crate::runtime;
+use crate::AsyncActor;
+
+/// Cleanup releases a resource when the actor of an ActorScope ends.
+type Cleanup = Box<dyn FnOnce() + Send>;
+
+/// Cleanups are the registered cleanups, `None` once they have been run.
+type Cleanups = Arc<Mutex<Option<Vec<Cleanup>>>>;
+
+/// ActorScope runs the registered cleanups when its actor ends, stopped or
+/// failed, e.g. to release connections or files used by the tasks. They run
+/// in the reverse order of their registration in their own task, not in the
+/// actor loop. The cleanups run even if the scope has been dropped before.
+pub struct ActorScope {
+    cleanups: Cleanups,
+}
+
+impl ActorScope {
+    /// Creates a new scope for the actor.
+    pub fn new(actor: Arc<AsyncActor>) -> Self {
+        let cleanups: Cleanups = Arc::new(Mutex::new(Some(Vec::new())));
+        let joined = actor.join();
+        let exiting = cleanups.clone();
+        runtime::spawn(async move {
+            joined.await;
+            let cleanups = exiting.lock().unwrap().take().unwrap_or_default();
+            cleanups.into_iter().rev().for_each(|cleanup| cleanup());
+        });
+        Self { cleanups }
+    }
+
+    /// Registers a cleanup. If the actor has already ended, it's run at once
+    /// in its own task.
+    pub fn on_exit<F>(&self, cleanup: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        match &mut *self.cleanups.lock().unwrap() {
+            Some(cleanups) => cleanups.push(Box::new(cleanup)),
+            None => runtime::spawn(async move { cleanup() }),
+        }
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,103 @@
+// --------------------------------------------------------
+// Actor library - Latency based load shedding
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::ActorError;
+
+/// Maximum number of task durations in the window.
+const WINDOW_SIZE: usize = 100;
+
+/// Maximum age of a task duration in the window. Old durations drop out so
+/// that shedding ends even when no more tasks are processed.
+const WINDOW_AGE: Duration = Duration::from_secs(10);
+
+/// Window holds the recent task durations and whether tasks are shed.
+struct Window {
+    durations: VecDeque<(Instant, Duration)>,
+    shedding: bool,
+}
+
+impl Window {
+    /// Removes the durations which are too old.
+    fn prune(&mut self, now: Instant) {
+        while let Some((observed, _)) = self.durations.front() {
+            if now.duration_since(*observed) < WINDOW_AGE {
+                break;
+            }
+            self.durations.pop_front();
+        }
+    }
+
+    /// Returns the 99th percentile of the durations.
+    fn p99(&self) -> Duration {
+        let mut durations: Vec<Duration> = self.durations.iter().map(|(_, d)| *d).collect();
+        if durations.is_empty() {
+            return Duration::ZERO;
+        }
+        durations.sort_unstable();
+        durations[(durations.len() * 99).div_ceil(100) - 1]
+    }
+
+    /// Starts shedding above the target and ends it below 90% of it.
+    fn update(&mut self, target: Duration) {
+        let p99 = self.p99();
+        if p99 > target {
+            self.shedding = true;
+        } else if p99 < target.mul_f64(0.9) {
+            self.shedding = false;
+        }
+    }
+}
+
+/// LatencyShedder rejects new tasks while the P99 of the recent task durations
+/// is above the target.
+pub(crate) struct LatencyShedder {
+    target: Duration,
+    window: Mutex<Window>,
+}
+
+impl LatencyShedder {
+    pub(crate) fn new(target: Duration) -> Self {
+        Self {
+            target,
+            window: Mutex::new(Window {
+                durations: VecDeque::with_capacity(WINDOW_SIZE),
+                shedding: false,
+            }),
+        }
+    }
+
+    /// Adds the duration of a processed task to the window.
+    pub(crate) fn observe(&self, duration: Duration) {
+        let now = Instant::now();
+        let mut window = self.window.lock().unwrap();
+        window.prune(now);
+        if window.durations.len() == WINDOW_SIZE {
+            window.durations.pop_front();
+        }
+        window.durations.push_back((now, duration));
+        window.update(self.target);
+    }
+
+    /// Rejects a new task with `ActorError::LoadShedding` while shedding.
+    pub(crate) fn check(&self) -> Result<(), ActorError> {
+        let mut window = self.window.lock().unwrap();
+        if window.shedding {
+            window.prune(Instant::now());
+            window.update(self.target);
+        }
+        match window.shedding {
+            true => Err(ActorError::LoadShedding),
+            false => Ok(()),
+        }
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,118 @@
+// --------------------------------------------------------
+// Actor library - Graceful shutdown coordinator
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+
+use crate::runtime;
+use crate::{ActorId, AsyncActor, StopReason};
+
+/// ShutdownReport lists the actors of a shutdown by how they stopped.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ShutdownReport {
+    /// The actors which processed their queued tasks and stopped in time.
+    pub stopped: Vec<ActorId>,
+    /// The actors stopped immediately after the timeout, dropping their
+    /// remaining tasks.
+    pub timed_out: Vec<ActorId>,
+}
+
+/// GracefulShutdownCoordinator stops the registered actors of an application
+/// in the order of their priority. The actors of the highest priority are
+/// stopped first, all actors of one priority concurrently. The next group
+/// is stopped after the former one has drained its queues, e.g. to stop the
+/// producers before their consumers.
+pub struct GracefulShutdownCoordinator {
+    actors: Mutex<Vec<(u32, Arc<AsyncActor>)>>,
+}
+
+impl GracefulShutdownCoordinator {
+    /// Creates a new coordinator without actors.
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            actors: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Registers an actor to stop with the given priority.
+    pub fn register(&self, priority: u32, actor: Arc<AsyncActor>) {
+        self.actors.lock().unwrap().push((priority, actor));
+    }
+
+    /// Stops the registered actors group by group. The timeout is for the
+    /// whole shutdown, actors not stopped in time are stopped immediately
+    /// and the following groups too.
+    pub async fn shutdown(&self, timeout: Duration) -> ShutdownReport {
+        let mut actors = std::mem::take(&mut *self.actors.lock().unwrap());
+        actors.sort_by_key(|(priority, _)| std::cmp::Reverse(*priority));
+        let deadline = Instant::now() + timeout;
+        let mut report = ShutdownReport::default();
+
+        let mut actors = actors.into_iter().peekable();
+        while let Some((priority, first)) = actors.next() {
+            let mut group = vec![first];
+            while let Some((_, actor)) = actors.next_if(|(next, _)| *next == priority) {
+                group.push(actor);
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            for (id, in_time) in drain_group(group, remaining).await {
+                if in_time {
+                    report.stopped.push(id);
+                } else {
+                    report.timed_out.push(id);
+                }
+            }
+        }
+        report
+    }
+
+    /// Runs the shutdown when the process receives SIGTERM or SIGINT. On
+    /// platforms without Unix signals only Ctrl-C is handled.
+    pub fn shutdown_on_signal(self: &Arc<Self>, timeout: Duration) -> io::Result<()> {
+        let signal = runtime::shutdown_signal()?;
+        let coordinator = Arc::downgrade(self);
+        runtime::spawn(async move {
+            signal.await;
+            if let Some(coordinator) = coordinator.upgrade() {
+                coordinator.shutdown(timeout).await;
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Stops the actors of a group concurrently and waits until they are drained
+/// or the timeout has passed. Returns the IDs of the actors and if they
+/// stopped in time.
+async fn drain_group(group: Vec<Arc<AsyncActor>>, timeout: Duration) -> Vec<(ActorId, bool)> {
+    let mut results = Vec::with_capacity(group.len());
+    for actor in group {
+        let (responder, result) = oneshot::channel();
+        let id = actor.id();
+        runtime::spawn(async move {
+            let drained = runtime::timeout(timeout, async {
+                let _ = actor.stop().await;
+                actor.join().await
+            })
+            .await;
+            if drained.is_none() {
+                let _ = actor.stop_now(StopReason::Timeout);
+            }
+            let _ = responder.send(drained.is_some());
+        });
+        results.push((id, result));
+    }
+    let mut drained = Vec::with_capacity(results.len());
+    for (id, result) in results {
+        drained.push((id, result.await.unwrap_or(false)));
+    }
+    drained
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,123 @@
+// --------------------------------------------------------
+// Actor library - Sinks
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use crate::{ActorError, ActorState, AsyncActor, TaskId};
+
+/// Sink is a target values are sent to asynchronously. Its signature is the
+/// one of `futures::Sink`, so the sinks of the actors can be adapted easily.
+pub trait Sink<Item> {
+    /// The type of errors the sink may return.
+    type Error;
+
+    /// Attempts to prepare the sink for receiving a value.
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>>;
+
+    /// Begins sending a value, `poll_ready()` has to return `Ok` before.
+    fn start_send(self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error>;
+
+    /// Flushes the values sent so far.
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>>;
+
+    /// Flushes the values sent so far and closes the sink.
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>>;
+}
+
+type Sending = Pin<Box<dyn Future<Output = Result<TaskId, ActorError>> + Send>>;
+
+type Closing = Pin<Box<dyn Future<Output = ActorState> + Send>>;
+
+/// ActorSink sends the tasks it receives to an AsyncActor. A task is flushed
+/// once it's enqueued, a full queue lets the sink wait like `send()` does.
+/// Closing stops the actor and waits until it processed its queued tasks.
+pub struct ActorSink<F> {
+    actor: Arc<AsyncActor>,
+    sending: Option<Sending>,
+    closing: Option<Closing>,
+    tasks: PhantomData<fn(F)>,
+}
+
+impl<F> ActorSink<F> {
+    pub(crate) fn new(actor: Arc<AsyncActor>) -> Self {
+        Self {
+            actor,
+            sending: None,
+            closing: None,
+            tasks: PhantomData,
+        }
+    }
+
+    /// Drives the sending of the last task until it's enqueued.
+    fn poll_sending(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), ActorError>> {
+        let Some(sending) = &mut self.sending else {
+            return Poll::Ready(Ok(()));
+        };
+        let result = match sending.as_mut().poll(cx) {
+            Poll::Ready(result) => result.map(|_| ()),
+            Poll::Pending => return Poll::Pending,
+        };
+        self.sending = None;
+        Poll::Ready(result)
+    }
+}
+
+impl<F> Sink<F> for ActorSink<F>
+where
+    F: FnOnce() -> Result<(), String> + Send + 'static,
+{
+    type Error = ActorError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), ActorError>> {
+        match self.poll_sending(cx) {
+            Poll::Ready(Ok(())) => {}
+            other => return other,
+        }
+        Poll::Ready(match self.actor.state() {
+            ActorState::Running => Ok(()),
+            ActorState::Stopped => Err(ActorError::Stopped),
+            ActorState::Error => Err(ActorError::Task(self.actor.message().unwrap_or_default())),
+        })
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, task: F) -> Result<(), ActorError> {
+        let actor = self.actor.clone();
+        self.sending = Some(Box::pin(async move { actor.send(task).await }));
+        Ok(())
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), ActorError>> {
+        self.poll_sending(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), ActorError>> {
+        match self.poll_sending(cx) {
+            Poll::Ready(Ok(())) => {}
+            other => return other,
+        }
+        let actor = self.actor.clone();
+        let closing = self.closing.get_or_insert_with(|| {
+            Box::pin(async move {
+                let _ = actor.stop().await;
+                actor.join().await
+            })
+        });
+        match closing.as_mut().poll(cx) {
+            Poll::Ready(ActorState::Error) => Poll::Ready(Err(ActorError::Task(
+                self.actor.message().unwrap_or_default(),
+            ))),
+            Poll::Ready(_) => Poll::Ready(Ok(())),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,135 @@
+// --------------------------------------------------------
+// Actor library - Sorted batch actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+use crate::runtime;
+use crate::{ActorError, ActorState, AsyncActor};
+
+/// Message is passed from the actor to its collecting loop.
+enum Message<T> {
+    Item(T),
+    Stop,
+}
+
+/// SortedBatchActor collects items until `flush_size` of them are waiting or
+/// `flush_interval` has passed since the first one. Then the batch is sorted
+/// by the key and processed by the handler as one task of its AsyncActor,
+/// e.g. to combine sorted bulk inserts into a database.
+pub struct SortedBatchActor<T: Send + 'static> {
+    sender: mpsc::Sender<Message<T>>,
+    actor: Arc<AsyncActor>,
+}
+
+impl<T: Send + 'static> SortedBatchActor<T> {
+    /// Creates a new SortedBatchActor processing the batches with the handler.
+    pub fn new<K, S, H>(
+        sort_key: S,
+        flush_size: usize,
+        flush_interval: Duration,
+        handler: H,
+    ) -> Arc<Self>
+    where
+        K: Ord,
+        S: Fn(&T) -> K + Send + Sync + 'static,
+        H: Fn(Vec<T>) -> Result<(), String> + Send + Sync + 'static,
+    {
+        let flush_size = flush_size.max(1);
+        let (sender, mut receiver) = mpsc::channel(flush_size);
+        let actor = AsyncActor::new();
+        let handler = Arc::new(handler);
+
+        let processing = actor.clone();
+        runtime::spawn(async move {
+            let mut batch = Vec::with_capacity(flush_size);
+            let mut deadline = Instant::now();
+            loop {
+                let msg = if batch.is_empty() {
+                    receiver.recv().await
+                } else {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    match runtime::timeout(remaining, receiver.recv()).await {
+                        Some(msg) => msg,
+                        None => {
+                            flush(&processing, &mut batch, &sort_key, &handler).await;
+                            continue;
+                        }
+                    }
+                };
+                match msg {
+                    Some(Message::Item(item)) => {
+                        if batch.is_empty() {
+                            deadline = Instant::now() + flush_interval;
+                        }
+                        batch.push(item);
+                        if batch.len() >= flush_size {
+                            flush(&processing, &mut batch, &sort_key, &handler).await;
+                        }
+                    }
+                    // The waiting items are processed before stopping.
+                    Some(Message::Stop) | None => {
+                        flush(&processing, &mut batch, &sort_key, &handler).await;
+                        let _ = processing.stop().await;
+                        return;
+                    }
+                }
+            }
+        });
+
+        Arc::new(Self { sender, actor })
+    }
+
+    /// Sends an item for the next batch. After a batch failed, the items
+    /// are rejected with its error.
+    pub async fn send(&self, item: T) -> Result<(), ActorError> {
+        match self.actor.state() {
+            ActorState::Running => {}
+            ActorState::Stopped => return Err(ActorError::Stopped),
+            ActorState::Error => {
+                return Err(ActorError::Task(self.actor.message().unwrap_or_default()))
+            }
+        }
+        self.sender
+            .send(Message::Item(item))
+            .await
+            .map_err(|err| ActorError::Send(err.to_string()))
+    }
+
+    /// Processes the waiting items and stops the actor afterwards.
+    pub async fn stop(&self) -> Result<(), ActorError> {
+        self.sender
+            .send(Message::Stop)
+            .await
+            .map_err(|err| ActorError::Send(err.to_string()))
+    }
+
+    /// Returns the actor processing the batches.
+    pub fn actor(&self) -> &Arc<AsyncActor> {
+        &self.actor
+    }
+}
+
+/// Sorts the batch and sends it to the actor.
+async fn flush<T, K, S, H>(actor: &AsyncActor, batch: &mut Vec<T>, sort_key: &S, handler: &Arc<H>)
+where
+    T: Send + 'static,
+    K: Ord,
+    S: Fn(&T) -> K,
+    H: Fn(Vec<T>) -> Result<(), String> + Send + Sync + 'static,
+{
+    if batch.is_empty() {
+        return;
+    }
+    let mut sorted = std::mem::take(batch);
+    sorted.sort_by_key(|item| sort_key(item));
+    let handler = handler.clone();
+    let _ = actor.send(move || handler(sorted)).await;
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,88 @@
+// --------------------------------------------------------
+// Actor library - Staggered pool
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::runtime;
+use crate::{ActorError, AsyncActor, TaskId};
+
+/// StaggeredPool is a pool of AsyncActors started one after another. The actor
+/// with index `i` is created `stagger * i` after the pool, so a burst of tasks
+/// at startup isn't processed by all actors at the same moment. Until then the
+/// tasks are distributed over the actors already started.
+pub struct StaggeredPool {
+    size: usize,
+    actors: Mutex<Vec<Arc<AsyncActor>>>,
+    next: AtomicUsize,
+}
+
+impl StaggeredPool {
+    /// Creates a new pool of `size` actors created by the factory. The first
+    /// actor is created immediately.
+    pub fn new<F>(size: usize, stagger: Duration, factory: F) -> Arc<Self>
+    where
+        F: Fn() -> Arc<AsyncActor> + Send + Sync + 'static,
+    {
+        let pool = Arc::new(Self {
+            size,
+            actors: Mutex::new(Vec::with_capacity(size)),
+            next: AtomicUsize::new(0),
+        });
+        if size == 0 {
+            return pool;
+        }
+        pool.actors.lock().unwrap().push(factory());
+
+        let weak = Arc::downgrade(&pool);
+        runtime::spawn(async move {
+            for _ in 1..size {
+                runtime::sleep(stagger).await;
+                let Some(pool) = weak.upgrade() else {
+                    return;
+                };
+                pool.actors.lock().unwrap().push(factory());
+            }
+        });
+        pool
+    }
+
+    /// Sends a task to the next started actor of the pool in round-robin order.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send<F>(&self, task: F) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        let actor = {
+            let actors = self.actors.lock().unwrap();
+            if actors.is_empty() {
+                return Err(ActorError::Stopped);
+            }
+            let index = self.next.fetch_add(1, Ordering::Relaxed) % actors.len();
+            actors[index].clone()
+        };
+        actor.send(task).await
+    }
+
+    /// Returns the actors started so far.
+    pub fn actors(&self) -> Vec<Arc<AsyncActor>> {
+        self.actors.lock().unwrap().clone()
+    }
+
+    /// Returns the number of actors the pool has when all are started.
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Returns true if the pool has no actors.
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,169 @@
+// --------------------------------------------------------
+// Actor library - State actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::io;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::runtime;
+use crate::{ActorError, ActorState};
+
+/// StateTask is a function or closure working on the state of a StateActor and
+/// returning a Result<(), ActorError>.
+pub type StateTask<S> = Box<dyn FnOnce(&mut S) -> Result<(), ActorError> + Send>;
+
+/// Checkpoint is implemented by states of a StateActor which can be written
+/// to disk and restored from it.
+pub trait Checkpoint: Sized {
+    /// Encodes the state into bytes.
+    fn encode(&self) -> Vec<u8>;
+
+    /// Decodes a state encoded before. Returns `None` if it's invalid.
+    fn decode(bytes: &[u8]) -> Option<Self>;
+}
+
+/// Message is passed from the actor to its loop.
+enum Message<S> {
+    Task(StateTask<S>),
+    Stop,
+}
+
+/// StateActor owns a state and runs tasks sequentially on it. As only the actor
+/// loop accesses the state, no mutex is needed inside the tasks.
+///
+/// Like with the AsyncActor a failing task stops the processing and the actor
+/// keeps the error.
+pub struct StateActor<S: Send + 'static> {
+    sender: mpsc::Sender<Message<S>>,
+    state: Arc<Mutex<ActorState>>,
+    error: Arc<Mutex<Option<ActorError>>>,
+}
+
+impl<S: Send + 'static> StateActor<S> {
+    /// Creates a new StateActor owning the given state.
+    pub fn new(mut owned: S) -> Arc<Self> {
+        let (sender, mut receiver) = mpsc::channel::<Message<S>>(32);
+        let state = Arc::new(Mutex::new(ActorState::Running));
+        let error = Arc::new(Mutex::new(None));
+
+        let actor = Arc::new(Self {
+            sender,
+            state: state.clone(),
+            error: error.clone(),
+        });
+
+        runtime::spawn(async move {
+            while let Some(msg) = receiver.recv().await {
+                let task = match msg {
+                    Message::Task(task) => task,
+                    Message::Stop => break,
+                };
+                if let Err(err) = task(&mut owned) {
+                    *state.lock().unwrap() = ActorState::Error;
+                    *error.lock().unwrap() = Some(err);
+                    return;
+                }
+            }
+            *state.lock().unwrap() = ActorState::Stopped;
+        });
+
+        actor
+    }
+
+    /// Sends a task working on the state to the StateActor.
+    pub async fn send<F>(&self, task: F) -> Result<(), ActorError>
+    where
+        F: FnOnce(&mut S) -> Result<(), ActorError> + Send + 'static,
+    {
+        self.check()?;
+        self.sender
+            .send(Message::Task(Box::new(task)))
+            .await
+            .map_err(|err| ActorError::Send(err.to_string()))
+    }
+
+    /// Sends a function working on the state and waits for its result.
+    pub async fn ask<F, R>(&self, f: F) -> Result<R, ActorError>
+    where
+        F: FnOnce(&mut S) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (responder, response) = oneshot::channel();
+        self.send(move |state| {
+            let _ = responder.send(f(state));
+            Ok(())
+        })
+        .await?;
+        // The responder is dropped unanswered if the actor ends before.
+        response.await.map_err(|_| match self.error() {
+            Some(err) => err,
+            None => ActorError::Stopped,
+        })
+    }
+
+    /// Retrieves the current state of the StateActor.
+    pub fn state(&self) -> ActorState {
+        self.state.lock().unwrap().clone()
+    }
+
+    /// Retrieves the error of the failed task if the actor is in error state.
+    pub fn error(&self) -> Option<ActorError> {
+        self.error.lock().unwrap().clone()
+    }
+
+    /// Stops the actor after the tasks already in the queue are processed.
+    pub async fn stop(&self) -> Result<(), ActorError> {
+        self.sender
+            .send(Message::Stop)
+            .await
+            .map_err(|err| ActorError::Send(err.to_string()))
+    }
+
+    /// Checks if the actor still accepts tasks.
+    fn check(&self) -> Result<(), ActorError> {
+        match *self.state.lock().unwrap() {
+            ActorState::Running => Ok(()),
+            ActorState::Stopped => Err(ActorError::Stopped),
+            ActorState::Error => Err(self.error().unwrap_or(ActorError::Stopped)),
+        }
+    }
+}
+
+impl<S: Checkpoint + Send + 'static> StateActor<S> {
+    /// Writes the state after the tasks sent before to the file. It's written
+    /// to a temporary file first and then renamed, so a crash leaves either
+    /// the old or the new checkpoint.
+    pub async fn checkpoint(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let encoded = self
+            .ask(|state| state.encode())
+            .await
+            .map_err(|err| io::Error::other(err.to_string()))?;
+        let path = path.as_ref().to_path_buf();
+        runtime::spawn_blocking(move || {
+            let mut temporary = path.clone().into_os_string();
+            temporary.push(".tmp");
+            std::fs::write(&temporary, encoded)?;
+            std::fs::rename(&temporary, &path)
+        })
+        .await
+        .unwrap_or_else(|| Err(io::Error::other("writing the checkpoint panicked")))
+    }
+
+    /// Creates a new StateActor owning the state restored from the file.
+    pub async fn restore_from(path: impl AsRef<Path>) -> io::Result<Arc<Self>> {
+        let path = path.as_ref().to_path_buf();
+        let bytes = runtime::spawn_blocking(move || std::fs::read(path))
+            .await
+            .unwrap_or_else(|| Err(io::Error::other("reading the checkpoint panicked")))?;
+        let owned = S::decode(&bytes)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid checkpoint"))?;
+        Ok(Self::new(owned))
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
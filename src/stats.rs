@@ -0,0 +1,82 @@
+// --------------------------------------------------------
+// Actor library - Statistics
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// ActorStats is a snapshot of the counters of an actor.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActorStats {
+    /// Number of tasks enqueued.
+    pub tasks_enqueued: u64,
+    /// Number of tasks completed successfully.
+    pub tasks_processed: u64,
+    /// Number of tasks returning an error.
+    pub tasks_failed: u64,
+    /// Number of tasks dropped due to the overflow policy.
+    pub tasks_dropped: u64,
+    /// Number of waiting tasks cancelled by their ID.
+    pub tasks_cancelled: u64,
+    /// Number of async tasks exceeding their task timeout.
+    pub tasks_timed_out: u64,
+    /// Longest duration of a task processed so far.
+    pub max_observed_duration: Duration,
+    /// Number of tasks skipped by the sample rate.
+    pub tasks_sampled_out: u64,
+    /// Number of classed tasks started after their latency budget.
+    pub sla_violations: u64,
+    /// Number of deadline tasks dropped as not started before their deadline.
+    pub tasks_expired: u64,
+}
+
+/// AtomicStats are the counters of an actor updated without locking.
+#[derive(Default)]
+pub(crate) struct AtomicStats {
+    pub(crate) tasks_enqueued: AtomicU64,
+    pub(crate) tasks_processed: AtomicU64,
+    pub(crate) tasks_failed: AtomicU64,
+    pub(crate) tasks_dropped: AtomicU64,
+    pub(crate) tasks_cancelled: AtomicU64,
+    pub(crate) tasks_timed_out: AtomicU64,
+    pub(crate) max_observed_duration: AtomicU64,
+    pub(crate) tasks_sampled_out: AtomicU64,
+    pub(crate) sla_violations: AtomicU64,
+    pub(crate) tasks_expired: AtomicU64,
+}
+
+impl AtomicStats {
+    /// Increments one of the counters.
+    pub(crate) fn incr(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Raises a maximum duration kept in nanoseconds to the given one.
+    pub(crate) fn observe(maximum: &AtomicU64, duration: Duration) {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        maximum.fetch_max(nanos, Ordering::Relaxed);
+    }
+
+    /// Returns a snapshot of the counters.
+    pub(crate) fn snapshot(&self) -> ActorStats {
+        ActorStats {
+            tasks_enqueued: self.tasks_enqueued.load(Ordering::Relaxed),
+            tasks_processed: self.tasks_processed.load(Ordering::Relaxed),
+            tasks_failed: self.tasks_failed.load(Ordering::Relaxed),
+            tasks_dropped: self.tasks_dropped.load(Ordering::Relaxed),
+            tasks_cancelled: self.tasks_cancelled.load(Ordering::Relaxed),
+            tasks_timed_out: self.tasks_timed_out.load(Ordering::Relaxed),
+            max_observed_duration: Duration::from_nanos(
+                self.max_observed_duration.load(Ordering::Relaxed),
+            ),
+            tasks_sampled_out: self.tasks_sampled_out.load(Ordering::Relaxed),
+            sla_violations: self.sla_violations.load(Ordering::Relaxed),
+            tasks_expired: self.tasks_expired.load(Ordering::Relaxed),
+        }
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
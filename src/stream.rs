@@ -0,0 +1,49 @@
+// --------------------------------------------------------
+// Actor library - Streams
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Stream is a sequence of values produced asynchronously. Its signature is the
+/// one of `futures::Stream`, so the streams of the actors can be adapted easily.
+pub trait Stream {
+    /// The type of the values yielded by the stream.
+    type Item;
+
+    /// Attempts to pull out the next value of the stream. Returns `Poll::Ready(None)`
+    /// when the stream is exhausted.
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>>;
+}
+
+/// StreamExt adds convenience methods to all streams.
+pub trait StreamExt: Stream {
+    /// Returns a future resolving to the next value of the stream.
+    fn next(&mut self) -> Next<'_, Self>
+    where
+        Self: Unpin,
+    {
+        Next { stream: self }
+    }
+}
+
+impl<S: Stream + ?Sized> StreamExt for S {}
+
+/// Next is the future returned by `StreamExt::next`.
+pub struct Next<'a, S: ?Sized> {
+    stream: &'a mut S,
+}
+
+impl<S: Stream + Unpin + ?Sized> Future for Next<'_, S> {
+    type Output = Option<S::Item>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.stream).poll_next(cx)
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
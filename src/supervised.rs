@@ -0,0 +1,118 @@
+// --------------------------------------------------------
+// Actor library - Supervised actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::runtime;
+use crate::{ActorError, ActorState, AsyncActor, AsyncActorBuilder, TaskId};
+
+/// MailboxPreservation defines what happens with the tasks waiting in the
+/// queue of a failed actor when the SupervisedActor restarts it.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum MailboxPreservation {
+    /// The waiting tasks are dropped with the failed actor.
+    #[default]
+    Discard,
+    /// The waiting tasks are enqueued on the restarted actor in their order,
+    /// only the failed task itself is lost.
+    Preserve,
+}
+
+/// SupervisedActor restarts its AsyncActor with a new one built by the factory
+/// whenever a task error stops it. Tasks sent while the restart is running may
+/// fail with the error of the old actor.
+pub struct SupervisedActor {
+    current: Mutex<Arc<AsyncActor>>,
+    restarts: AtomicUsize,
+    stopped: AtomicBool,
+}
+
+impl SupervisedActor {
+    /// Creates a new SupervisedActor dropping the waiting tasks on restarts.
+    pub fn new<F>(factory: F) -> Arc<Self>
+    where
+        F: Fn() -> AsyncActorBuilder + Send + Sync + 'static,
+    {
+        Self::with_preservation(factory, MailboxPreservation::Discard)
+    }
+
+    /// Creates a new SupervisedActor handling the waiting tasks on restarts
+    /// as defined by the preservation.
+    pub fn with_preservation<F>(factory: F, preservation: MailboxPreservation) -> Arc<Self>
+    where
+        F: Fn() -> AsyncActorBuilder + Send + Sync + 'static,
+    {
+        let build = move || {
+            let mut builder = factory();
+            builder.preserve_on_error = preservation == MailboxPreservation::Preserve;
+            builder.build()
+        };
+        let supervised = Arc::new(Self {
+            current: Mutex::new(build()),
+            restarts: AtomicUsize::new(0),
+            stopped: AtomicBool::new(false),
+        });
+
+        // Only weak references are kept while waiting, so dropping the
+        // SupervisedActor also drops its actor.
+        let weak = Arc::downgrade(&supervised);
+        runtime::spawn(async move {
+            loop {
+                let Some((actor, joined)) = weak.upgrade().map(|s| {
+                    let actor = s.actor();
+                    (Arc::downgrade(&actor), actor.join())
+                }) else {
+                    return;
+                };
+                let state = joined.await;
+                let (Some(supervised), Some(actor)) = (weak.upgrade(), actor.upgrade()) else {
+                    return;
+                };
+                if state != ActorState::Error || supervised.stopped.load(Ordering::SeqCst) {
+                    return;
+                }
+                let restarted = build();
+                for task in actor.take_preserved() {
+                    let _ = restarted.enqueue(restarted.next_task_id(), task).await;
+                }
+                *supervised.current.lock().unwrap() = restarted;
+                supervised.restarts.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+        supervised
+    }
+
+    /// Sends a task to the current actor.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send<F>(&self, task: F) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        self.actor().send(task).await
+    }
+
+    /// Returns the current actor.
+    pub fn actor(&self) -> Arc<AsyncActor> {
+        self.current.lock().unwrap().clone()
+    }
+
+    /// Returns how often the actor has been restarted.
+    pub fn restarts(&self) -> usize {
+        self.restarts.load(Ordering::SeqCst)
+    }
+
+    /// Stops the current actor after its queued tasks without restarting it
+    /// anymore.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn stop(&self) -> Result<(), ActorError> {
+        self.stopped.store(true, Ordering::SeqCst);
+        self.actor().stop().await
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
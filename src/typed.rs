@@ -0,0 +1,142 @@
+// --------------------------------------------------------
+// Actor library - Typed actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+
+use crate::runtime;
+use crate::{ActorError, ActorState};
+
+/// Handler processes the messages of a TypedActor on its state.
+pub type Handler<M, S> = Box<dyn Fn(&mut S, M) -> Result<(), ActorError> + Send + Sync>;
+
+/// Validator checks the messages of a TypedActor before they are enqueued.
+pub type Validator<M> = Box<dyn Fn(&M) -> Result<(), ActorError> + Send + Sync>;
+
+/// Message is passed from the actor to its loop.
+enum Message<M> {
+    Tell(M),
+    Stop,
+}
+
+/// TypedActor receives messages of type `M` instead of closures. They are
+/// processed one by one by a handler working on the owned state `S`. A handler
+/// returning an error stops the processing like with the AsyncActor.
+pub struct TypedActor<M: Send + 'static, S: Send + 'static = ()> {
+    sender: mpsc::Sender<Message<M>>,
+    state: Arc<Mutex<ActorState>>,
+    error: Arc<Mutex<Option<ActorError>>>,
+    validator: Option<Validator<M>>,
+    handler: Arc<Mutex<Arc<Handler<M, S>>>>,
+    owned: PhantomData<fn(S)>,
+}
+
+impl<M: Send + 'static, S: Send + 'static> TypedActor<M, S> {
+    /// Creates a new TypedActor owning the state and processing the messages
+    /// with the handler.
+    pub fn new<H>(owned: S, handler: H) -> Arc<Self>
+    where
+        H: Fn(&mut S, M) -> Result<(), ActorError> + Send + Sync + 'static,
+    {
+        Self::start(owned, handler, None)
+    }
+
+    /// Creates a new TypedActor like `new()`, but each message is checked by
+    /// the validator before it is enqueued. A rejected message is returned
+    /// with the error of the validator to the caller and doesn't change the
+    /// state of the actor.
+    pub fn with_validator<H, V>(owned: S, handler: H, validator: V) -> Arc<Self>
+    where
+        H: Fn(&mut S, M) -> Result<(), ActorError> + Send + Sync + 'static,
+        V: Fn(&M) -> Result<(), ActorError> + Send + Sync + 'static,
+    {
+        Self::start(owned, handler, Some(Box::new(validator)))
+    }
+
+    /// Creates the actor and starts its loop.
+    fn start<H>(mut owned: S, handler: H, validator: Option<Validator<M>>) -> Arc<Self>
+    where
+        H: Fn(&mut S, M) -> Result<(), ActorError> + Send + Sync + 'static,
+    {
+        let handler: Arc<Mutex<Arc<Handler<M, S>>>> =
+            Arc::new(Mutex::new(Arc::new(Box::new(handler))));
+        let (sender, mut receiver) = mpsc::channel::<Message<M>>(32);
+        let state = Arc::new(Mutex::new(ActorState::Running));
+        let error = Arc::new(Mutex::new(None));
+
+        let actor = Arc::new(Self {
+            sender,
+            state: state.clone(),
+            error: error.clone(),
+            validator,
+            handler: handler.clone(),
+            owned: PhantomData,
+        });
+
+        runtime::spawn(async move {
+            while let Some(Message::Tell(msg)) = receiver.recv().await {
+                // The lock is not held while handling, so swaps never wait
+                // for a running handler.
+                let current = handler.lock().unwrap().clone();
+                if let Err(err) = current(&mut owned, msg) {
+                    *state.lock().unwrap() = ActorState::Error;
+                    *error.lock().unwrap() = Some(err);
+                    return;
+                }
+            }
+            *state.lock().unwrap() = ActorState::Stopped;
+        });
+
+        actor
+    }
+
+    /// Tells the actor a message to process.
+    pub async fn tell(&self, msg: M) -> Result<(), ActorError> {
+        match *self.state.lock().unwrap() {
+            ActorState::Running => {}
+            ActorState::Stopped => return Err(ActorError::Stopped),
+            ActorState::Error => return Err(self.error().unwrap_or(ActorError::Stopped)),
+        }
+        if let Some(validator) = &self.validator {
+            validator(&msg)?;
+        }
+        self.sender
+            .send(Message::Tell(msg))
+            .await
+            .map_err(|err| ActorError::Send(err.to_string()))
+    }
+
+    /// Replaces the handler. The new one processes the messages dequeued after
+    /// the swap, the old one is dropped once its running message is done.
+    pub fn swap_handler<H>(&self, handler: H)
+    where
+        H: Fn(&mut S, M) -> Result<(), ActorError> + Send + Sync + 'static,
+    {
+        *self.handler.lock().unwrap() = Arc::new(Box::new(handler));
+    }
+
+    /// Retrieves the current state of the TypedActor.
+    pub fn state(&self) -> ActorState {
+        self.state.lock().unwrap().clone()
+    }
+
+    /// Retrieves the error of the handler if the actor is in error state.
+    pub fn error(&self) -> Option<ActorError> {
+        self.error.lock().unwrap().clone()
+    }
+
+    /// Stops the actor after the messages already in the queue are processed.
+    pub async fn stop(&self) -> Result<(), ActorError> {
+        self.sender
+            .send(Message::Stop)
+            .await
+            .map_err(|err| ActorError::Send(err.to_string()))
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,109 @@
+// --------------------------------------------------------
+// Actor library - Versioned state actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::{ActorError, StateActor};
+
+/// Version is implemented by the messages of a VersionedStateActor. Each
+/// schema version of a message is its own type with its own version tag.
+pub trait Version: Send + 'static {
+    /// The schema version of the message type.
+    const VERSION: u32;
+}
+
+/// VersionedHandler processes the messages of one version on the state.
+type VersionedHandler<S> =
+    Arc<dyn Fn(&mut S, Box<dyn Any + Send>) -> Result<(), ActorError> + Send + Sync>;
+
+/// VersionedStateActor runs messages of multiple schema versions on the state
+/// of a StateActor. Each version is processed by its registered handler, so
+/// senders of old and new versions can be served side by side during a
+/// rolling upgrade. The handler of a version is chosen when sending, so
+/// messages already sent are still processed after their handler has been
+/// removed.
+pub struct VersionedStateActor<S: Send + 'static> {
+    inner: Arc<StateActor<S>>,
+    handlers: Mutex<HashMap<TypeId, (u32, VersionedHandler<S>)>>,
+}
+
+impl<S: Send + 'static> VersionedStateActor<S> {
+    /// Creates a new VersionedStateActor owning the state, without handlers.
+    pub fn new(owned: S) -> Arc<Self> {
+        Arc::new(Self {
+            inner: StateActor::new(owned),
+            handlers: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Registers the handler for the messages of version `V`. It replaces a
+    /// handler registered before for the same version.
+    pub fn register_handler<V, H>(&self, handler: H)
+    where
+        V: Version,
+        H: Fn(&mut S, V) -> Result<(), ActorError> + Send + Sync + 'static,
+    {
+        let handler: VersionedHandler<S> = Arc::new(move |state, msg| match msg.downcast() {
+            Ok(msg) => handler(state, *msg),
+            Err(_) => Err(ActorError::Task(format!(
+                "invalid message of version {}",
+                V::VERSION
+            ))),
+        });
+        self.handlers
+            .lock()
+            .unwrap()
+            .insert(TypeId::of::<V>(), (V::VERSION, handler));
+    }
+
+    /// Removes the handler for the messages of version `V`, e.g. after the
+    /// migration window. Returns false if none has been registered.
+    pub fn remove_handler<V: Version>(&self) -> bool {
+        self.handlers
+            .lock()
+            .unwrap()
+            .remove(&TypeId::of::<V>())
+            .is_some()
+    }
+
+    /// Returns the versions having a handler in ascending order.
+    pub fn versions(&self) -> Vec<u32> {
+        let mut versions: Vec<u32> = self
+            .handlers
+            .lock()
+            .unwrap()
+            .values()
+            .map(|(version, _)| *version)
+            .collect();
+        versions.sort_unstable();
+        versions
+    }
+
+    /// Sends a message to be processed by the handler of its version. Returns
+    /// `ActorError::Send` if no handler is registered for the version.
+    pub async fn send<V: Version>(&self, msg: V) -> Result<(), ActorError> {
+        let handler = self
+            .handlers
+            .lock()
+            .unwrap()
+            .get(&TypeId::of::<V>())
+            .map(|(_, handler)| handler.clone())
+            .ok_or_else(|| ActorError::Send(format!("no handler for version {}", V::VERSION)))?;
+        self.inner
+            .send(move |state| handler(state, Box::new(msg)))
+            .await
+    }
+
+    /// Returns the StateActor owning the state.
+    pub fn inner(&self) -> &Arc<StateActor<S>> {
+        &self.inner
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,96 @@
+// --------------------------------------------------------
+// Actor library - Watchdog actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::runtime;
+use crate::{ActorState, AsyncActor, CancellationToken, DropGuard};
+
+/// WatchdogAction defines what a WatchdogActor does with a task running
+/// longer than allowed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WatchdogAction {
+    /// Publishes a warning as `TaskEventKind::Stuck` event of the task, see
+    /// `AsyncActor::task_stream()`.
+    Log,
+    /// Cancels the token of the task, see `AsyncActor::current_task_token()`.
+    /// The task has to check it and end by itself.
+    Cancel,
+    /// Stops the actor via `stop_immediately()` after the stuck task.
+    ForceStop,
+}
+
+/// WatchdogActor checks the running task of an actor every half of the
+/// maximum task duration. If the task runs longer than the maximum, the
+/// action is applied once for it. The watchdog ends with the actor or when
+/// it's dropped.
+pub struct WatchdogActor {
+    triggered: Arc<AtomicU64>,
+    _guard: DropGuard,
+}
+
+impl WatchdogActor {
+    /// Creates a new WatchdogActor for the watched actor.
+    pub fn new(
+        watched: Arc<AsyncActor>,
+        max_task_duration: Duration,
+        action: WatchdogAction,
+    ) -> Self {
+        let triggered = Arc::new(AtomicU64::new(0));
+        let token = CancellationToken::new();
+        let weak = Arc::downgrade(&watched);
+        let counter = triggered.clone();
+        let cancelled = token.cancelled();
+        runtime::spawn(async move {
+            let interval = max_task_duration / 2;
+            let mut handled: Option<Instant> = None;
+            let checking = async {
+                loop {
+                    runtime::sleep(interval).await;
+                    let Some(actor) = weak.upgrade() else {
+                        return;
+                    };
+                    if actor.state() != ActorState::Running {
+                        return;
+                    }
+                    let Some(started) = actor.current_task_start() else {
+                        continue;
+                    };
+                    if started.elapsed() <= max_task_duration || handled == Some(started) {
+                        continue;
+                    }
+                    handled = Some(started);
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    match action {
+                        WatchdogAction::Log => actor.report_stuck(started.elapsed()),
+                        WatchdogAction::Cancel => actor.cancel_current_task(),
+                        WatchdogAction::ForceStop => {
+                            let _ = actor.stop_immediately();
+                        }
+                    }
+                }
+            };
+            tokio::select! {
+                _ = checking => {}
+                _ = cancelled => {}
+            }
+        });
+        Self {
+            triggered,
+            _guard: token.drop_guard(),
+        }
+    }
+
+    /// Returns how often the action has been applied.
+    pub fn triggered(&self) -> u64 {
+        self.triggered.load(Ordering::SeqCst)
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
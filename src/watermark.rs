@@ -0,0 +1,114 @@
+// --------------------------------------------------------
+// Actor library - Watermark controller
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::Notify;
+
+use crate::runtime;
+use crate::{ActorError, AsyncActor, StreamExt, TaskEventKind, TaskId};
+
+/// Paused is shared between the controller and its watcher.
+struct Paused {
+    paused: AtomicBool,
+    resumed: Notify,
+}
+
+impl Paused {
+    /// Resumes the producers if the queue is down to the low watermark.
+    fn resume_below(&self, actor: &AsyncActor, low: usize) {
+        if actor.queue_len() <= low && self.paused.swap(false, Ordering::SeqCst) {
+            self.resumed.notify_waiters();
+        }
+    }
+}
+
+/// WatermarkController pauses the producers of an actor when its queue has
+/// more than `high` tasks and resumes them when it has `low` or less. While
+/// paused, sending fails with `ActorError::Watermark` and producers wait with
+/// `wait_for_capacity()`.
+pub struct WatermarkController {
+    actor: Arc<AsyncActor>,
+    low: usize,
+    high: usize,
+    paused: Arc<Paused>,
+}
+
+impl WatermarkController {
+    /// Creates a new controller for the actor.
+    pub fn new(actor: Arc<AsyncActor>, high: usize, low: usize) -> Arc<Self> {
+        let paused = Arc::new(Paused {
+            paused: AtomicBool::new(false),
+            resumed: Notify::new(),
+        });
+
+        // Each dequeued task may bring the queue down to the low watermark.
+        let mut events = actor.task_stream();
+        let weak = Arc::downgrade(&actor);
+        let watching = paused.clone();
+        runtime::spawn(async move {
+            while let Some(event) = events.next().await {
+                if event.kind == TaskEventKind::Enqueued {
+                    continue;
+                }
+                let Some(actor) = weak.upgrade() else {
+                    break;
+                };
+                watching.resume_below(&actor, low);
+            }
+            // Without actor there is nothing to wait for anymore.
+            watching.paused.store(false, Ordering::SeqCst);
+            watching.resumed.notify_waiters();
+        });
+
+        Arc::new(Self {
+            actor,
+            low,
+            high,
+            paused,
+        })
+    }
+
+    /// Sends a task to the actor unless the producers are paused.
+    #[must_use = "ignoring this Result means task errors go undetected"]
+    pub async fn send<F>(&self, task: F) -> Result<TaskId, ActorError>
+    where
+        F: FnOnce() -> Result<(), String> + Send + 'static,
+    {
+        if self.paused.paused.load(Ordering::SeqCst) {
+            return Err(ActorError::Watermark);
+        }
+        if self.actor.queue_len() > self.high {
+            self.paused.paused.store(true, Ordering::SeqCst);
+            // The queue may have drained before pausing was visible.
+            self.paused.resume_below(&self.actor, self.low);
+            return Err(ActorError::Watermark);
+        }
+        self.actor.send(task).await
+    }
+
+    /// Waits until the producers are resumed. Returns immediately if they are
+    /// not paused.
+    pub async fn wait_for_capacity(&self) {
+        loop {
+            let resumed = self.paused.resumed.notified();
+            tokio::pin!(resumed);
+            resumed.as_mut().enable();
+            if !self.paused.paused.load(Ordering::SeqCst) {
+                return;
+            }
+            resumed.await;
+        }
+    }
+
+    /// Returns true while the producers are paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused.paused.load(Ordering::SeqCst)
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,64 @@
+// --------------------------------------------------------
+// Actor library - Zero-copy actor
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use crate::{ActorError, ActorState, StateActor};
+
+/// ZeroCopyActor owns a pool of buffers, e.g. large byte buffers, and lends
+/// them to its tasks. So a task works on a buffer by reference instead of
+/// capturing the data by value. The buffers are used in turns, after a task
+/// its buffer is returned to the pool with its content, so a task may have
+/// to clear it first.
+pub struct ZeroCopyActor<T: Send + 'static> {
+    inner: Arc<StateActor<VecDeque<T>>>,
+}
+
+impl<T: Send + 'static> ZeroCopyActor<T> {
+    /// Creates a new ZeroCopyActor owning the buffers. Without buffers all
+    /// tasks fail.
+    pub fn new(buffers: Vec<T>) -> Arc<Self> {
+        Arc::new(Self {
+            inner: StateActor::new(buffers.into()),
+        })
+    }
+
+    /// Sends a task working on the next buffer of the pool.
+    pub async fn send_with_buf<F>(&self, task: F) -> Result<(), ActorError>
+    where
+        F: FnOnce(&mut T) -> Result<(), ActorError> + Send + 'static,
+    {
+        self.inner
+            .send(move |pool| {
+                let mut buf = pool
+                    .pop_front()
+                    .ok_or_else(|| ActorError::Task("no buffer in the pool".to_string()))?;
+                let result = task(&mut buf);
+                pool.push_back(buf);
+                result
+            })
+            .await
+    }
+
+    /// Retrieves the current state of the ZeroCopyActor.
+    pub fn state(&self) -> ActorState {
+        self.inner.state()
+    }
+
+    /// Retrieves the error of the failed task if the actor is in error state.
+    pub fn error(&self) -> Option<ActorError> {
+        self.inner.error()
+    }
+
+    /// Stops the actor after the tasks already in the queue are processed.
+    pub async fn stop(&self) -> Result<(), ActorError> {
+        self.inner.stop().await
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
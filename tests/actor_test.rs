@@ -3,7 +3,10 @@
 // Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
 // --------------------------------------------------------
 
-use actor::{ActorState, AsyncActor};
+use actor::{
+    ask, send, task_id, ActorError, ActorState, AsyncActor, ErrorStrategy, OverflowPolicy,
+    StopReason, Task, TaskId, TaskLocation,
+};
 use std::sync::{Arc, Mutex};
 
 #[tokio::test]
@@ -14,7 +17,17 @@ async fn test_actor() {
     // Send a task to the actor.
     let result = actor.send(|| Ok(())).await;
 
-    assert_eq!(result, Ok(()));
+    assert_eq!(result, Ok(TaskId(1)));
+}
+
+#[tokio::test]
+// Test displaying the ID of a task.
+async fn test_actor_task_id_display() {
+    let actor = AsyncActor::new();
+
+    let task_id = actor.send(|| Ok(())).await.unwrap();
+
+    assert_eq!(task_id.to_string(), "task-1");
 }
 
 #[tokio::test]
@@ -36,7 +49,65 @@ async fn test_actor_stop() {
 
     // Expect sending a task to the actor to fail.
     let result = actor.send(|| Ok(())).await;
-    assert_eq!(result, Err("Actor is stopped".to_string()));
+    assert_eq!(result, Err(ActorError::Stopped));
+}
+
+#[tokio::test]
+// Test the reasons for the different ways of ending an actor.
+async fn test_actor_stop_reason() {
+    let actor = AsyncActor::new();
+    assert_eq!(actor.stop_reason(), None);
+    let _ = actor.stop().await;
+    actor.join().await;
+    assert_eq!(actor.stop_reason(), Some(StopReason::Requested));
+    assert_eq!(actor.message(), None);
+
+    let actor = AsyncActor::new();
+    let _ = actor.send(|| Err("Ouch!".to_string())).await;
+    actor.join().await;
+    let expected = StopReason::Error(ActorError::Task("Ouch!".to_string()));
+    assert_eq!(actor.stop_reason(), Some(expected));
+}
+
+#[tokio::test]
+// Test stopping the actor without processing the queued tasks.
+async fn test_actor_stop_immediately() {
+    let actor = AsyncActor::new();
+    let processed = Arc::new(Mutex::new(Vec::new()));
+
+    // All tasks are sent before the actor loop gets the chance to run.
+    for i in 1..=3 {
+        let processed = processed.clone();
+        let _ = send!(actor, { processed.lock().unwrap().push(i) });
+    }
+    let _ = actor.stop().await;
+    assert_eq!(actor.stop_immediately(), Ok(()));
+    assert_eq!(actor.send(|| Ok(())).await, Err(ActorError::Stopped));
+
+    assert_eq!(actor.join().await, ActorState::Stopped);
+    assert_eq!(actor.stop_reason(), Some(StopReason::Forced));
+    assert!(processed.lock().unwrap().is_empty());
+    assert_eq!(actor.stop_immediately(), Err(ActorError::Stopped));
+}
+
+#[tokio::test]
+// Test waiting concurrently for the end of the actor.
+async fn test_actor_join() {
+    let actor = AsyncActor::new();
+    let joins: Vec<_> = (0..2).map(|_| tokio::spawn(actor.join())).collect();
+
+    let _ = actor.send(|| Ok(())).await;
+    let _ = actor.stop().await;
+
+    for join in joins {
+        assert_eq!(join.await.unwrap(), ActorState::Stopped);
+    }
+    assert_eq!(actor.join().await, ActorState::Stopped);
+
+    let actor = AsyncActor::new();
+    let _ = actor.send(|| Err("Ouch!".to_string())).await;
+
+    assert_eq!(actor.join().await, ActorState::Error);
 }
 
 #[tokio::test]
@@ -72,6 +143,251 @@ async fn test_actor_error() {
     )
 }
 
+#[tokio::test]
+// Test sending function pointers which don't use queue memory.
+async fn test_actor_send_fn() {
+    fn succeed() -> Result<(), String> {
+        Ok(())
+    }
+    fn fail() -> Result<(), String> {
+        Err("Ouch!".to_string())
+    }
+
+    let actor = AsyncActor::builder().memory_budget(0).build();
+
+    assert_eq!(actor.send_fn(succeed).await, Ok(TaskId(1)));
+    assert_eq!(actor.queue_memory_bytes(), 0);
+    assert_eq!(actor.send_fn(fail).await, Ok(TaskId(2)));
+    // Closures are still accounted.
+    let result = actor.send(sized_task([1u8; 40])).await;
+    assert_eq!(result, Err(ActorError::MemoryBudgetExceeded));
+
+    // Wait a bit to ensure that the actor has processed the tasks.
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+    assert_eq!(actor.state(), ActorState::Error);
+    assert_eq!(actor.message(), Some("Ouch!".to_string()));
+}
+
+#[tokio::test]
+// Test cancelling tasks which have not been started yet.
+async fn test_actor_cancel() {
+    let actor = AsyncActor::new();
+    let processed = Arc::new(Mutex::new(Vec::new()));
+
+    // All tasks are sent before the actor loop gets the chance to run.
+    let mut task_ids = Vec::new();
+    for i in 1..=3 {
+        let processed = processed.clone();
+        task_ids.push(send!(actor, { processed.lock().unwrap().push(i) }).unwrap());
+    }
+    assert!(actor.cancel(task_ids[1]));
+    assert!(!actor.cancel(task_ids[1]));
+    assert!(!actor.cancel(TaskId(42)));
+
+    // Wait a bit to ensure that the actor has processed all tasks.
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+    assert_eq!(*processed.lock().unwrap(), vec![1, 3]);
+    assert!(!actor.cancel(task_ids[0]));
+}
+
+#[tokio::test]
+// Test the send and ask macros.
+async fn test_actor_macros() {
+    let actor = AsyncActor::new();
+    let counter = Arc::new(Mutex::new(0));
+
+    for i in 1..=3 {
+        let counter = counter.clone();
+        let result = send!(actor, {
+            let mut counter = counter.lock().unwrap();
+            *counter += 1;
+        });
+        assert_eq!(result, Ok(TaskId(i)));
+    }
+    let counter_clone = counter.clone();
+    let value = ask!(actor, *counter_clone.lock().unwrap());
+
+    assert_eq!(value, Ok(3));
+}
+
+#[tokio::test]
+// Test the task_id macro counting the calls per location.
+async fn test_actor_task_id_macro() {
+    let first: Vec<TaskLocation> = (0..2).map(|_| task_id!()).collect();
+    let other = task_id!();
+
+    assert_eq!((first[0].call, first[1].call), (1, 2));
+    assert_eq!(first[0].line, first[1].line);
+    assert_eq!(other.call, 1);
+    assert_ne!(other.line, first[0].line);
+    assert_eq!(
+        other.to_string(),
+        format!("tests/actor_test.rs:{}:1", other.line)
+    );
+}
+
+#[tokio::test]
+// Test rejecting tasks exceeding the memory budget of the queue.
+async fn test_actor_memory_budget() {
+    let actor = AsyncActor::builder().memory_budget(64).build();
+    let data = [1u8; 40];
+
+    // Both tasks are sent before the actor loop gets the chance to run.
+    let result = actor.send(sized_task(data)).await;
+    assert!(result.is_ok());
+    assert_eq!(actor.queue_memory_bytes(), 40);
+    let result = actor.send(sized_task(data)).await;
+    assert_eq!(result, Err(ActorError::MemoryBudgetExceeded));
+
+    // Wait a bit to ensure that the actor has processed the task.
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    assert_eq!(actor.queue_memory_bytes(), 0);
+    let result = actor.send(sized_task(data)).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+// Test the overflow policies dropping tasks or failing when the queue is full.
+async fn test_actor_overflow_policies() {
+    let tests = [
+        (OverflowPolicy::DropOldest, vec![2, 3], 1),
+        (OverflowPolicy::DropNewest, vec![1, 2], 1),
+        (OverflowPolicy::Error, vec![1, 2], 0),
+    ];
+
+    for (policy, expected, dropped) in tests {
+        let actor = AsyncActor::builder()
+            .capacity(2)
+            .overflow_policy(policy)
+            .build();
+        let processed = Arc::new(Mutex::new(Vec::new()));
+
+        // All tasks are sent before the actor loop gets the chance to run.
+        let mut results = Vec::new();
+        for i in 1..=3 {
+            let processed = processed.clone();
+            results.push(send!(actor, { processed.lock().unwrap().push(i) }));
+        }
+        assert_eq!(actor.queue_len(), 2);
+
+        // Wait a bit to ensure that the actor has processed all tasks.
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+        assert_eq!(*processed.lock().unwrap(), expected, "{:?}", policy);
+        assert_eq!(actor.tasks_dropped(), dropped, "{:?}", policy);
+        if policy == OverflowPolicy::Error {
+            assert_eq!(results[2], Err(ActorError::Full));
+        } else {
+            assert!(results.iter().all(|result| result.is_ok()));
+        }
+    }
+}
+
+#[tokio::test]
+// Test that the actor loop yields to other tasks only if configured.
+async fn test_actor_yield_every() {
+    for (n, expected) in [(1, vec![1, 0, 2, 3]), (0, vec![1, 2, 3, 0])] {
+        let actor = AsyncActor::builder().yield_every(n).build();
+        let processed = Arc::new(Mutex::new(Vec::new()));
+
+        for i in 1..=3 {
+            let processed = processed.clone();
+            let _ = send!(actor, { processed.lock().unwrap().push(i) });
+        }
+        let other = processed.clone();
+        tokio::spawn(async move { other.lock().unwrap().push(0) });
+
+        // Wait a bit to ensure that the actor has processed all tasks.
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+        assert_eq!(*processed.lock().unwrap(), expected, "yield every {}", n);
+    }
+}
+
+#[tokio::test]
+// Test that the actor loop yields only between batches of tasks.
+async fn test_actor_batch_size() {
+    for (n, expected) in [
+        (1, vec![1, 0, 2, 3]),
+        (2, vec![1, 2, 0, 3]),
+        (8, vec![1, 2, 3, 0]),
+    ] {
+        let actor = AsyncActor::builder().batch_size(n).build();
+        let processed = Arc::new(Mutex::new(Vec::new()));
+
+        for i in 1..=3 {
+            let processed = processed.clone();
+            let _ = send!(actor, { processed.lock().unwrap().push(i) });
+        }
+        let other = processed.clone();
+        tokio::spawn(async move { other.lock().unwrap().push(0) });
+
+        // Wait a bit to ensure that the actor has processed all tasks.
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+        assert_eq!(*processed.lock().unwrap(), expected, "batch size {}", n);
+    }
+}
+
+#[tokio::test]
+// Test sampling the health of a running and a stopped actor.
+async fn test_actor_health() {
+    let actor = AsyncActor::builder()
+        .error_strategy(ErrorStrategy::Continue)
+        .build();
+
+    let _ = actor.send(|| Ok(())).await;
+    let _ = actor.send(|| Err("Ouch!".to_string())).await;
+    let _ = actor.send(|| Ok(())).await;
+    let health = actor.health().await;
+
+    assert_eq!(health.state, ActorState::Running);
+    assert_eq!(health.tasks_processed, 2);
+    assert_eq!(
+        health.last_error,
+        Some(ActorError::Task("Ouch!".to_string()))
+    );
+    assert!(health.is_idle);
+    let line = health.to_string();
+    assert!(
+        line.starts_with("state=Running queue=0 processed=2 uptime="),
+        "{}",
+        line
+    );
+    assert!(line.ends_with("idle=true last_error=\"Ouch!\""), "{}", line);
+
+    let _ = actor.stop().await;
+    actor.join().await;
+    assert_eq!(actor.health().await.state, ActorState::Stopped);
+}
+
+#[tokio::test]
+// Test that the statistics count the tasks of the actor.
+async fn test_actor_stats() {
+    let actor = AsyncActor::builder()
+        .capacity(2)
+        .overflow_policy(OverflowPolicy::DropNewest)
+        .error_strategy(ErrorStrategy::Continue)
+        .build();
+
+    // Sending without waiting fills the queue, so the last task is dropped.
+    let _ = actor.send(|| Err("failed".to_string())).await;
+    let _ = actor.send(|| Ok(())).await;
+    let _ = actor.send(|| Ok(())).await;
+
+    // Wait a bit to ensure that the actor has processed all tasks.
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+    let stats = actor.stats();
+    assert_eq!(stats.tasks_enqueued, 2);
+    assert_eq!(stats.tasks_processed, 1);
+    assert_eq!(stats.tasks_failed, 1);
+    assert_eq!(stats.tasks_dropped, 1);
+}
+
 #[tokio::test]
 // Test an actor as field of a struct.
 async fn test_shared_async_actor() {
@@ -94,6 +410,15 @@ async fn test_shared_async_actor() {
 // TEST HELPER
 // --------------------------------------------------------
 
+// sized_task returns a task capturing the given data, so its size is
+// the one of the data.
+fn sized_task(data: [u8; 40]) -> impl FnOnce() -> Result<(), String> + Send + 'static {
+    move || match data[0] {
+        1 => Ok(()),
+        _ => Err("Unexpected data".to_string()),
+    }
+}
+
 // AsyncCounter helps testing using the AsyncActor inside a struct as
 // a field.
 struct AsyncCounter {
@@ -112,26 +437,14 @@ impl AsyncCounter {
         let value = self.value.clone();
         let actor = self.actor.clone();
 
-        let _ = actor
-            .send(move || {
-                let mut value = value.lock().unwrap();
-                *value += 1;
-                Ok(())
-            })
-            .await;
+        let _ = send!(actor, { *value.lock().unwrap() += 1 });
     }
 
     async fn decr(&self) {
         let value = self.value.clone();
         let actor = self.actor.clone();
 
-        let _ = actor
-            .send(move || {
-                let mut value = value.lock().unwrap();
-                *value -= 1;
-                Ok(())
-            })
-            .await;
+        let _ = send!(actor, { *value.lock().unwrap() -= 1 });
     }
 
     async fn read_value(&self) -> i32 {
@@ -206,6 +519,456 @@ impl Counter {
     }
 }
 */
+
+#[tokio::test]
+// Test retrieving an injected dependency inside of a task.
+async fn test_dependency() {
+    struct Greeter(&'static str);
+
+    let actor = AsyncActor::builder()
+        .with_dependency(Greeter("hello"))
+        .with_dependency(42_u32)
+        .build();
+
+    let task_actor = actor.clone();
+    let greeting = actor
+        .ask(move || task_actor.dependency::<Greeter>().0)
+        .await;
+    assert_eq!(greeting, Ok("hello"));
+    assert_eq!(*actor.dependency::<u32>(), 42);
+}
+
+#[tokio::test]
+#[should_panic(expected = "no dependency of type")]
+// Test retrieving a dependency which has not been registered.
+async fn test_dependency_missing() {
+    let actor = AsyncActor::new();
+    let _ = actor.dependency::<String>();
+}
+
+#[tokio::test]
+// Test enqueuing a batch of tasks as a whole or not at all.
+async fn test_send_batch() {
+    let actor = AsyncActor::builder()
+        .capacity(2)
+        .overflow_policy(OverflowPolicy::Error)
+        .build();
+    let batch = || -> Vec<Task> { (0..2).map(|_| Box::new(|| Ok(())) as Task).collect() };
+
+    // The loop doesn't run before the test awaits a result.
+    assert!(actor.send(|| Ok(())).await.is_ok());
+    assert_eq!(actor.send_batch(batch()).await, Err(ActorError::Full));
+    assert_eq!(actor.queue_len(), 1);
+
+    // The IDs of the rejected batch are not reused.
+    actor.ask(|| ()).await.unwrap();
+    assert_eq!(
+        actor.send_batch(batch()).await,
+        Ok(vec![TaskId(5), TaskId(6)])
+    );
+}
+
+#[tokio::test]
+// Test setting the capacity of the queue as a constant.
+async fn test_fixed_capacity() {
+    let actor = AsyncActor::builder().fixed_capacity::<4>().build();
+    assert_eq!(actor.capacity(), 4);
+}
+
+#[tokio::test]
+// Test running the actor loop on another runtime.
+async fn test_runtime() {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let handle = runtime.handle().clone();
+    let (shutdown, shutdown_received) = tokio::sync::oneshot::channel::<()>();
+    let driver = std::thread::spawn(move || {
+        runtime.block_on(async {
+            let _ = shutdown_received.await;
+        })
+    });
+
+    let actor = AsyncActor::builder().runtime(handle).build();
+    let worker = actor.ask(|| std::thread::current().id()).await.unwrap();
+    assert_ne!(worker, std::thread::current().id());
+    assert_eq!(worker, driver.thread().id());
+
+    shutdown.send(()).unwrap();
+    driver.join().unwrap();
+}
+
+#[tokio::test]
+// Test calling the overflow callback for dropped tasks.
+async fn test_on_overflow() {
+    let overflows = Arc::new(Mutex::new(Vec::new()));
+    let recorded = overflows.clone();
+    let actor = AsyncActor::builder()
+        .name("dropper")
+        .capacity(1)
+        .overflow_policy(OverflowPolicy::DropNewest)
+        .on_overflow(move |info| {
+            recorded
+                .lock()
+                .unwrap()
+                .push((info.queue_depth, info.actor_name));
+        })
+        .build();
+
+    // The loop doesn't run before the test awaits a result.
+    assert!(actor.send(|| Ok(())).await.is_ok());
+    assert!(actor.send(|| Ok(())).await.is_ok());
+    assert!(actor.try_send(|| Ok(())).is_ok());
+
+    let expected = (1, Some("dropper".to_string()));
+    assert_eq!(*overflows.lock().unwrap(), vec![expected.clone(), expected]);
+    assert_eq!(actor.tasks_dropped(), 2);
+}
+
+#[tokio::test]
+// Test calling the overflow callback when rejecting tasks as the queue is full.
+async fn test_on_overflow_error() {
+    let overflows = Arc::new(Mutex::new(0));
+    let counter = overflows.clone();
+    let actor = AsyncActor::builder()
+        .capacity(1)
+        .overflow_policy(OverflowPolicy::Error)
+        .on_overflow(move |_| *counter.lock().unwrap() += 1)
+        .build();
+
+    assert!(actor.send(|| Ok(())).await.is_ok());
+    assert_eq!(actor.send(|| Ok(())).await, Err(ActorError::Full));
+    assert_eq!(*overflows.lock().unwrap(), 1);
+    assert_eq!(actor.try_send(|| Ok(())), Err(ActorError::Full));
+    assert_eq!(*overflows.lock().unwrap(), 2);
+}
+
+#[tokio::test]
+// Test dropping a task with send_or_drop on a blocking actor.
+async fn test_send_or_drop() {
+    let overflows = Arc::new(Mutex::new(0));
+    let counter = overflows.clone();
+    let actor = AsyncActor::builder()
+        .capacity(1)
+        .on_overflow(move |_| *counter.lock().unwrap() += 1)
+        .build();
+
+    assert!(actor.send_or_drop(|| Ok(())).is_some());
+    assert_eq!(actor.send_or_drop(|| Ok(())), None);
+    assert_eq!(actor.try_send(|| Ok(())), Err(ActorError::Full));
+    assert_eq!(*overflows.lock().unwrap(), 2);
+}
+
+#[tokio::test]
+// Test extending an actor by an iterator of tasks.
+async fn test_extend() {
+    let actor = AsyncActor::builder().capacity(3).build();
+    let processed = Arc::new(Mutex::new(Vec::new()));
+
+    let tasks = (0..5).map(|i| {
+        let processed = processed.clone();
+        Box::new(move || {
+            processed.lock().unwrap().push(i);
+            Ok(())
+        }) as Task
+    });
+    (&*actor).extend(tasks);
+
+    // The tasks beyond the capacity of the queue are dropped.
+    actor.ask(|| ()).await.unwrap();
+    assert_eq!(*processed.lock().unwrap(), vec![0, 1, 2]);
+}
+
+#[test]
+// Test extending an actor from synchronous code waiting for room.
+fn test_extend_blocking() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let actor = runtime.block_on(async { AsyncActor::builder().capacity(2).build() });
+    let processed = Arc::new(Mutex::new(Vec::new()));
+
+    let tasks = (0..10).map(|i| {
+        let processed = processed.clone();
+        Box::new(move || {
+            processed.lock().unwrap().push(i);
+            Ok(())
+        }) as Task
+    });
+    let task_ids = actor.extend_blocking(tasks).unwrap();
+    assert_eq!(task_ids.len(), 10);
+
+    runtime.block_on(actor.ask(|| ())).unwrap();
+    assert_eq!(*processed.lock().unwrap(), (0..10).collect::<Vec<_>>());
+}
+#[tokio::test]
+// Test awaiting a future as task before processing the following tasks.
+async fn test_send_future() {
+    let actor = AsyncActor::new();
+    let order = Arc::new(Mutex::new(Vec::new()));
+
+    let first = order.clone();
+    actor
+        .send_future(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            first.lock().unwrap().push(1);
+            Ok(())
+        })
+        .await
+        .unwrap();
+    let second = order.clone();
+    actor
+        .send(move || {
+            second.lock().unwrap().push(2);
+            Ok(())
+        })
+        .await
+        .unwrap();
+    actor
+        .send_future(async { Err(ActorError::Task("failed".to_string())) })
+        .await
+        .unwrap();
+
+    assert_eq!(actor.join().await, ActorState::Error);
+    assert_eq!(*order.lock().unwrap(), vec![1, 2]);
+    assert_eq!(actor.stats().tasks_failed, 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+// Test limiting the tasks in flight with a backpressure semaphore.
+async fn test_backpressure_semaphore() {
+    let actor = AsyncActor::builder().backpressure_semaphore(2).build();
+    let (release, released) = std::sync::mpsc::channel::<()>();
+    let released = Arc::new(Mutex::new(released));
+
+    // Two blocked tasks use both permits although the queue has room.
+    for _ in 0..2 {
+        let released = released.clone();
+        actor
+            .send(move || {
+                let _ = released.lock().unwrap().recv();
+                Ok(())
+            })
+            .await
+            .unwrap();
+    }
+    assert_eq!(actor.try_send(|| Ok(())), Err(ActorError::Full));
+
+    // Finishing a task releases its permit for the waiting sender.
+    release.send(()).unwrap();
+    let sent = tokio::time::timeout(std::time::Duration::from_secs(1), actor.send(|| Ok(())));
+    assert!(sent.await.unwrap().is_ok());
+    release.send(()).unwrap();
+    assert_eq!(
+        actor
+            .send_batch((0..3).map(|_| Box::new(|| Ok(())) as Task).collect())
+            .await,
+        Err(ActorError::Full)
+    );
+}
+
+#[tokio::test]
+// Test sending a batch of tasks with sequence numbers.
+async fn test_send_ordered_batch() {
+    let actor = AsyncActor::new();
+    let seqs = Arc::new(Mutex::new(Vec::new()));
+
+    let tasks = (0..3)
+        .map(|_| {
+            let seqs = seqs.clone();
+            move |seq| {
+                seqs.lock().unwrap().push(seq);
+                Ok(())
+            }
+        })
+        .collect();
+    actor.send_ordered_batch(tasks, 10).await.unwrap();
+    let _ = actor.ask(|| ()).await;
+
+    assert_eq!(*seqs.lock().unwrap(), vec![10, 11, 12]);
+}
+
+#[tokio::test]
+// Test failing async tasks which exceed the task timeout.
+async fn test_task_timeout() {
+    let timeout = std::time::Duration::from_millis(20);
+    let actor = AsyncActor::builder().task_timeout(timeout).build();
+
+    // The own timeout of a task overrides the one of the actor.
+    let _ = actor
+        .send_with_task_timeout(
+            async {
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                Ok(())
+            },
+            std::time::Duration::from_secs(1),
+        )
+        .await;
+    let _ = actor
+        .send_future(async {
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            Ok(())
+        })
+        .await;
+
+    assert_eq!(actor.join().await, ActorState::Error);
+    let error = ActorError::TaskTimeout { elapsed: timeout };
+    assert_eq!(actor.stop_reason(), Some(StopReason::Error(error)));
+    let stats = actor.stats();
+    assert_eq!((stats.tasks_processed, stats.tasks_timed_out), (1, 1));
+    assert!(stats.max_observed_duration >= std::time::Duration::from_millis(50));
+}
+
+#[tokio::test]
+// Test stopping while a long running task is in flight, synchronized without
+// sleeping. The running and the queued tasks complete, later ones are rejected.
+async fn test_stop_during_running_task() {
+    let actor = AsyncActor::new();
+    let started = Arc::new(tokio::sync::Barrier::new(2));
+    let (release, released) = tokio::sync::oneshot::channel::<()>();
+    let completed = Arc::new(Mutex::new(Vec::new()));
+
+    let task_started = started.clone();
+    let task_completed = completed.clone();
+    actor
+        .send_future(async move {
+            task_started.wait().await;
+            let _ = released.await;
+            task_completed.lock().unwrap().push("long");
+            Ok(())
+        })
+        .await
+        .unwrap();
+    let queued = completed.clone();
+    actor
+        .send(move || {
+            queued.lock().unwrap().push("queued");
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+    // Stop while the long running task is known to be in flight.
+    started.wait().await;
+    actor.stop().await.unwrap();
+    let late = completed.clone();
+    let result = actor
+        .send(move || {
+            late.lock().unwrap().push("late");
+            Ok(())
+        })
+        .await;
+    assert_eq!(result, Err(ActorError::Stopped));
+
+    release.send(()).unwrap();
+    assert_eq!(actor.join().await, ActorState::Stopped);
+    assert_eq!(*completed.lock().unwrap(), vec!["long", "queued"]);
+}
+
+#[tokio::test]
+// Test shedding new tasks while the recent tasks are too slow.
+async fn test_shed_under_latency() {
+    let slow = || {
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        Ok(())
+    };
+    let actor = AsyncActor::builder()
+        .shed_under_latency(std::time::Duration::from_millis(1))
+        .build();
+    actor.send(slow).await.unwrap();
+    assert_eq!(actor.ask(|| ()).await, Ok(()));
+    assert_eq!(actor.send(|| Ok(())).await, Err(ActorError::LoadShedding));
+
+    // Fast tasks already queued push the slow one out of the window.
+    let actor = AsyncActor::builder()
+        .capacity(128)
+        .shed_under_latency(std::time::Duration::from_millis(1))
+        .build();
+    actor.send(slow).await.unwrap();
+    for _ in 0..100 {
+        actor.send(|| Ok(())).await.unwrap();
+    }
+    assert_eq!(actor.ask(|| ()).await, Ok(()));
+    assert!(actor.send(|| Ok(())).await.is_ok());
+}
+
+#[tokio::test]
+// Test processing only a random part of the tasks.
+async fn test_sample_rate() {
+    for (rate, range) in [(0.0, 0..=0), (0.5, 350..=650), (1.0, 1000..=1000)] {
+        let actor = AsyncActor::builder().sample_rate(rate).build();
+        let counter = Arc::new(Mutex::new(0));
+        for _ in 0..1000 {
+            let counter = counter.clone();
+            let sent = actor
+                .send(move || {
+                    *counter.lock().unwrap() += 1;
+                    Ok(())
+                })
+                .await;
+            assert!(sent.is_ok());
+        }
+        actor.stop().await.unwrap();
+        actor.join().await;
+
+        let processed = *counter.lock().unwrap();
+        assert!(range.contains(&processed), "{} at rate {}", processed, rate);
+        assert_eq!(actor.stats().tasks_sampled_out, 1000 - processed);
+    }
+}
+
+#[tokio::test]
+// Test never skipping the tasks answering an ask by the sample rate.
+async fn test_sample_rate_ask() {
+    let actor = AsyncActor::builder().sample_rate(0.0).build();
+    for i in 0..10 {
+        assert_eq!(actor.ask(move || i).await, Ok(i));
+    }
+    assert_eq!(actor.health().await.state, ActorState::Running);
+    assert_eq!(actor.stats().tasks_sampled_out, 0);
+}
+
+#[tokio::test]
+// Test waiting until the statistics reach a condition.
+async fn test_await_condition() {
+    let actor = AsyncActor::new();
+    for _ in 0..5 {
+        actor.send(|| Ok(())).await.unwrap();
+    }
+
+    let interval = std::time::Duration::from_millis(5);
+    let stats = actor
+        .await_condition(
+            |stats| stats.tasks_processed == 5,
+            interval,
+            std::time::Duration::from_secs(1),
+        )
+        .await;
+    assert_eq!(stats.map(|stats| stats.tasks_processed), Ok(5));
+
+    let stats = actor
+        .await_condition(
+            |stats| stats.tasks_failed > 0,
+            interval,
+            std::time::Duration::from_millis(20),
+        )
+        .await;
+    assert_eq!(stats, Err(ActorError::Timeout));
+}
+
+#[tokio::test]
+// Test retrieving the ID of the running task from within the task.
+async fn test_current_task_id() {
+    let actor = AsyncActor::new();
+    assert_eq!(actor.current_task_id(), None);
+
+    let running = actor.clone();
+    actor.send(|| Ok(())).await.unwrap();
+    let task_id = actor.ask(move || running.current_task_id()).await;
+
+    assert_eq!(task_id, Ok(Some(TaskId(2))));
+    assert_eq!(actor.current_task_id(), None);
+}
+
 // --------------------------------------------------------
 // EOF
 // --------------------------------------------------------
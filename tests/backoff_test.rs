@@ -0,0 +1,47 @@
+// --------------------------------------------------------
+// Actor library - Backoff sender tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, AsyncActor, BackoffSender, CancellationToken, OverflowPolicy, TaskId};
+use std::time::Duration;
+
+#[tokio::test]
+// Test retrying a task until the queue has room again.
+async fn test_backoff_retry() {
+    let actor = AsyncActor::builder()
+        .capacity(1)
+        .overflow_policy(OverflowPolicy::Error)
+        .build();
+    let sender = BackoffSender::new(actor.clone())
+        .initial(Duration::from_millis(5))
+        .max(Duration::from_millis(20))
+        .jitter(0.5);
+
+    // The loop runs not before the sender waits for the first retry.
+    assert!(actor.send(|| Ok(())).await.is_ok());
+    assert_eq!(sender.send(|| Ok(())).await, Ok(TaskId(3)));
+    assert_eq!(actor.stats().tasks_enqueued, 2);
+}
+
+#[tokio::test]
+// Test stopping the retries when the token is cancelled.
+async fn test_backoff_cancelled() {
+    let actor = AsyncActor::builder()
+        .capacity(1)
+        .overflow_policy(OverflowPolicy::Error)
+        .build();
+    let token = CancellationToken::new();
+    let sender = BackoffSender::new(actor.clone()).cancel_on(token.clone());
+
+    assert!(actor.send(|| Ok(())).await.is_ok());
+    token.cancel();
+    assert_eq!(sender.send(|| Ok(())).await, Err(ActorError::Full));
+
+    actor.stop().await.unwrap();
+    assert_eq!(sender.send(|| Ok(())).await, Err(ActorError::Stopped));
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,49 @@
+// --------------------------------------------------------
+// Actor library - Backpressure monitor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{AsyncActorBuilder, BackpressureMonitor};
+use std::sync::mpsc;
+use std::time::Duration;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+// Test publishing the queue depth while tasks are waiting.
+async fn test_backpressure_monitor() {
+    let actor = AsyncActorBuilder::new().capacity(10).build();
+    let mut states = BackpressureMonitor::attach(actor.clone(), Duration::from_millis(5));
+    assert_eq!(states.borrow().capacity, 10);
+
+    let (release, released) = mpsc::channel::<()>();
+    actor
+        .send(move || {
+            let _ = released.recv();
+            Ok(())
+        })
+        .await
+        .unwrap();
+    for _ in 0..5 {
+        actor.send(|| Ok(())).await.unwrap();
+    }
+
+    let state = *states
+        .wait_for(|state| state.queue_depth == 5)
+        .await
+        .unwrap();
+    assert_eq!(state.fill_ratio, 0.5);
+
+    release.send(()).unwrap();
+    let state = *states
+        .wait_for(|state| state.queue_depth == 0)
+        .await
+        .unwrap();
+    assert_eq!(state.fill_ratio, 0.0);
+
+    // The monitor ends with the actor and closes the channel.
+    actor.stop().await.unwrap();
+    while states.changed().await.is_ok() {}
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,53 @@
+// --------------------------------------------------------
+// Actor library - Blocking actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, ActorState, BlockingActor};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[tokio::test]
+// Test running the tasks sequentially outside of the Tokio worker thread.
+async fn test_blocking_actor() {
+    let actor = BlockingActor::new();
+    let worker = thread::current().id();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+
+    for i in 1..=3 {
+        let seen = seen.clone();
+        let result = actor
+            .send(move || {
+                assert_ne!(thread::current().id(), worker);
+                seen.lock().unwrap().push(i);
+                Ok(())
+            })
+            .await;
+        assert_eq!(result, Ok(()));
+    }
+
+    assert_eq!(*seen.lock().unwrap(), vec![1, 2, 3]);
+}
+
+#[tokio::test]
+// Test that failing and panicking tasks stop the actor.
+async fn test_blocking_actor_error() {
+    let expected = ActorError::Task("Ouch!".to_string());
+    let actor = BlockingActor::new();
+    let failing = expected.clone();
+
+    assert_eq!(
+        actor.send(move || Err(failing)).await,
+        Err(expected.clone())
+    );
+    assert_eq!(actor.state(), ActorState::Error);
+    assert_eq!(actor.send(|| Ok(())).await, Err(expected));
+
+    let actor = BlockingActor::new();
+    let result = actor.send(|| panic!("Ouch!")).await;
+    assert_eq!(result, Err(ActorError::Task("Task panicked".to_string())));
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
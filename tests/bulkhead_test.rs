@@ -0,0 +1,64 @@
+// --------------------------------------------------------
+// Actor library - Bulkhead actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, AsyncActor, BulkheadActor};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+use tokio::sync::Semaphore;
+
+#[tokio::test]
+// Test sharing the permits between two bulkheads.
+async fn test_bulkhead_shared_permits() {
+    let semaphore = Arc::new(Semaphore::new(1));
+    let first = BulkheadActor::new(AsyncActor::new(), semaphore.clone());
+    let second = BulkheadActor::new(AsyncActor::new(), semaphore.clone());
+    let (release, wait) = mpsc::channel::<()>();
+
+    assert!(first
+        .send(move || {
+            let _ = wait.recv();
+            Ok(())
+        })
+        .await
+        .is_ok());
+    assert_eq!(second.send(|| Ok(())).await, Err(ActorError::BulkheadFull));
+
+    // The permit is returned as soon as the task is done.
+    release.send(()).unwrap();
+    first.inner().stop().await.unwrap();
+    first.inner().join().await;
+    assert_eq!(semaphore.available_permits(), 1);
+    assert!(second.send(|| Ok(())).await.is_ok());
+}
+
+#[tokio::test]
+// Test waiting for a permit up to the timeout.
+async fn test_bulkhead_timeout() {
+    let semaphore = Arc::new(Semaphore::new(1));
+    let bulkhead = BulkheadActor::with_timeout(
+        AsyncActor::new(),
+        semaphore.clone(),
+        Duration::from_millis(500),
+    );
+    let permit = semaphore.clone().acquire_owned().await.unwrap();
+
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        drop(permit);
+    });
+    assert!(bulkhead.send(|| Ok(())).await.is_ok());
+
+    let _held = semaphore.clone().acquire_owned().await;
+    let bulkhead =
+        BulkheadActor::with_timeout(AsyncActor::new(), semaphore, Duration::from_millis(20));
+    assert_eq!(
+        bulkhead.send(|| Ok(())).await,
+        Err(ActorError::BulkheadFull)
+    );
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,57 @@
+// --------------------------------------------------------
+// Actor library - Call graph tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+#![cfg(feature = "callgraph")]
+
+use actor::{AsyncActor, CallGraphRecorder};
+
+#[tokio::test]
+// Test recording the sends between actors and rendering them.
+async fn test_call_graph() {
+    let caller = AsyncActor::new();
+    let callee = AsyncActor::new();
+
+    // Sends before enabling the recorder are not counted.
+    let _ = callee.send(|| Ok(())).await;
+    CallGraphRecorder::enable();
+    let _ = callee.send(|| Ok(())).await;
+    CallGraphRecorder::scope(caller.id(), async {
+        let _ = callee.send(|| Ok(())).await;
+        let _ = callee.send(|| Ok(())).await;
+    })
+    .await;
+
+    // Sends from the tasks of an actor are recorded without a scope.
+    let sender = AsyncActor::new();
+    let sending = callee.clone();
+    let _ = sender
+        .send_future(async move {
+            let _ = sending.send(|| Ok(())).await;
+            Ok(())
+        })
+        .await;
+    let _ = sender.ask(|| ()).await;
+    CallGraphRecorder::disable();
+
+    let recorder = CallGraphRecorder::global();
+    assert_eq!(recorder.calls(None, callee.id()), 1);
+    assert_eq!(recorder.calls(Some(caller.id()), callee.id()), 2);
+    assert_eq!(recorder.calls(Some(sender.id()), callee.id()), 1);
+
+    let dot = recorder.to_dot();
+    let external = format!("\"external\" -> \"actor-{}\" [label=\"1\"];", callee.id().0);
+    let internal = format!(
+        "\"actor-{}\" -> \"actor-{}\" [label=\"2\"];",
+        caller.id().0,
+        callee.id().0
+    );
+    assert!(dot.starts_with("digraph actors {\n"), "{}", dot);
+    assert!(dot.contains(&external), "{}", dot);
+    assert!(dot.contains(&internal), "{}", dot);
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
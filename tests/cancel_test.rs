@@ -0,0 +1,44 @@
+// --------------------------------------------------------
+// Actor library - Cancellation tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorState, AsyncActor, CancellationToken, StopReason};
+
+#[tokio::test]
+// Test stopping an actor when the scope owning the parent token ends.
+async fn test_cancel_on_scope_end() {
+    let actor = {
+        let parent = CancellationToken::new();
+        let actor = AsyncActor::builder()
+            .cancel_on(parent.child_token())
+            .build();
+        let _guard = parent.drop_guard();
+        let _ = actor.send(|| Ok(())).await;
+        assert_eq!(actor.state(), ActorState::Running);
+        actor
+    };
+
+    assert_eq!(actor.join().await, ActorState::Stopped);
+    assert_eq!(actor.stop_reason(), Some(StopReason::Forced));
+}
+
+#[tokio::test]
+// Test that cancelling a child token leaves the parent untouched.
+async fn test_cancel_child_token() {
+    let parent = CancellationToken::new();
+    let child = parent.child_token();
+
+    child.cancel();
+    child.cancelled().await;
+    assert!(!parent.is_cancelled());
+
+    parent.cancel();
+    assert!(parent.child_token().is_cancelled());
+    let guard = CancellationToken::new().drop_guard();
+    assert!(!guard.disarm().is_cancelled());
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,147 @@
+// --------------------------------------------------------
+// Actor library - Chained actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, AsyncActor, StopReason};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+
+#[tokio::test]
+// Test switching to the continuation once the first actor stopped.
+async fn test_chained_actor() {
+    let first = AsyncActor::new();
+    let reasons = Arc::new(Mutex::new(Vec::new()));
+    let seen = reasons.clone();
+    let chained = first.then(move |reason| {
+        seen.lock().unwrap().push(reason);
+        AsyncActor::new()
+    });
+    let phases = Arc::new(Mutex::new(Vec::new()));
+
+    let loading = phases.clone();
+    let _ = chained
+        .send(move || {
+            loading.lock().unwrap().push("loading");
+            Ok(())
+        })
+        .await;
+    assert!(Arc::ptr_eq(&chained.current(), &first));
+    let _ = first.stop().await;
+
+    // Sending after the stop waits for the continuation.
+    let serving = phases.clone();
+    let _ = chained
+        .send(move || {
+            serving.lock().unwrap().push("serving");
+            Ok(())
+        })
+        .await;
+    let _ = chained.current().ask(|| ()).await;
+
+    assert!(chained.is_switched());
+    assert!(!Arc::ptr_eq(&chained.current(), &first));
+    assert_eq!(*phases.lock().unwrap(), vec!["loading", "serving"]);
+    assert_eq!(*reasons.lock().unwrap(), vec![StopReason::Requested]);
+}
+
+#[tokio::test]
+// Test passing the error of the first actor to the continuation.
+async fn test_chained_actor_error() {
+    let first = AsyncActor::new();
+    let reasons = Arc::new(Mutex::new(Vec::new()));
+    let seen = reasons.clone();
+    let chained = first.then(move |reason| {
+        seen.lock().unwrap().push(reason);
+        AsyncActor::new()
+    });
+
+    let _ = chained.send(|| Err("Ouch!".to_string())).await;
+    let _ = first.join().await;
+    assert!(chained.send(|| Ok(())).await.is_ok());
+
+    let expected = StopReason::Error(ActorError::Task("Ouch!".to_string()));
+    assert_eq!(*reasons.lock().unwrap(), vec![expected]);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+// Test routing a send to the continuation if the first actor ends while the
+// send is waiting.
+async fn test_chained_actor_send_waiting() {
+    let first = AsyncActor::builder().capacity(1).build();
+    let chained = first.then(|_| AsyncActor::new());
+    let (started, running) = mpsc::channel::<()>();
+    let (release, released) = mpsc::channel::<()>();
+    let _ = first
+        .send(move || {
+            let _ = started.send(());
+            let _ = released.recv();
+            Ok(())
+        })
+        .await;
+    running.recv_timeout(Duration::from_secs(1)).unwrap();
+    let _ = first.send(|| Ok(())).await;
+
+    // The queue is full, so the send waits until the first actor ends.
+    let processed = Arc::new(Mutex::new(false));
+    let sending = chained.clone();
+    let marker = processed.clone();
+    let sender = tokio::spawn(async move {
+        sending
+            .send(move || {
+                *marker.lock().unwrap() = true;
+                Ok(())
+            })
+            .await
+    });
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    let _ = first.stop_immediately();
+    release.send(()).unwrap();
+
+    assert!(sender.await.unwrap().is_ok());
+    let _ = chained.current().ask(|| ()).await;
+    assert!(!Arc::ptr_eq(&chained.current(), &first));
+    assert!(*processed.lock().unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+// Test routing a send to the continuation if the first actor fails while the
+// send is waiting.
+async fn test_chained_actor_send_waiting_error() {
+    let first = AsyncActor::builder().backpressure_semaphore(1).build();
+    let chained = first.then(|_| AsyncActor::new());
+    let (started, running) = mpsc::channel::<()>();
+    let (release, released) = mpsc::channel::<()>();
+    let _ = first
+        .send(move || {
+            let _ = started.send(());
+            let _ = released.recv();
+            Err("Ouch!".to_string())
+        })
+        .await;
+    running.recv_timeout(Duration::from_secs(1)).unwrap();
+
+    // The only permit is taken, so the send waits until the first actor failed.
+    let processed = Arc::new(Mutex::new(false));
+    let sending = chained.clone();
+    let marker = processed.clone();
+    let sender = tokio::spawn(async move {
+        sending
+            .send(move || {
+                *marker.lock().unwrap() = true;
+                Ok(())
+            })
+            .await
+    });
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    release.send(()).unwrap();
+
+    assert!(sender.await.unwrap().is_ok());
+    let _ = chained.current().ask(|| ()).await;
+    assert!(!Arc::ptr_eq(&chained.current(), &first));
+    assert!(*processed.lock().unwrap());
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
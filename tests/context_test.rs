@@ -0,0 +1,88 @@
+// --------------------------------------------------------
+// Actor library - Context actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, ActorState, ContextActor};
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+// Test passing the context to the tasks.
+async fn test_context_actor() {
+    let actor = ContextActor::<String>::new();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+
+    for request_id in ["req-1", "req-2", "req-3"] {
+        let seen = seen.clone();
+        let result = actor
+            .send_with_context(
+                move |ctx: String| {
+                    seen.lock().unwrap().push(ctx);
+                    Ok(())
+                },
+                request_id.to_string(),
+            )
+            .await;
+        assert_eq!(result, Ok(()));
+    }
+
+    // Wait a bit to ensure that the actor has processed all tasks.
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    assert_eq!(*seen.lock().unwrap(), vec!["req-1", "req-2", "req-3"]);
+}
+
+#[tokio::test]
+// Test that a failing task stops the actor and its error is kept.
+async fn test_context_actor_error() {
+    let actor = ContextActor::<u32>::new();
+
+    let _ = actor
+        .send_with_context(
+            |tenant| Err(ActorError::Task(format!("tenant {}", tenant))),
+            7,
+        )
+        .await;
+
+    // Wait a bit to ensure that the actor has processed the task.
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    let expected = ActorError::Task("tenant 7".to_string());
+    assert_eq!(actor.state(), ActorState::Error);
+    assert_eq!(actor.error(), Some(expected.clone()));
+    assert_eq!(actor.send_with_context(|_| Ok(()), 8).await, Err(expected));
+}
+
+#[tokio::test]
+// Test stopping the actor after the queued tasks.
+async fn test_context_actor_stop() {
+    let actor = ContextActor::<u32>::new();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+
+    let recorded = seen.clone();
+    let result = actor
+        .send_with_context(
+            move |ctx| {
+                recorded.lock().unwrap().push(ctx);
+                Ok(())
+            },
+            1,
+        )
+        .await;
+    assert_eq!(result, Ok(()));
+    assert_eq!(actor.stop().await, Ok(()));
+
+    // Wait a bit to ensure that the actor has processed the stop.
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    assert_eq!(*seen.lock().unwrap(), vec![1]);
+    assert_eq!(actor.state(), ActorState::Stopped);
+    assert_eq!(
+        actor.send_with_context(|_| Ok(()), 2).await,
+        Err(ActorError::Stopped)
+    );
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
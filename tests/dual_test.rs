@@ -0,0 +1,99 @@
+// --------------------------------------------------------
+// Actor library - Dual actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, ActorState, DualActor, StateActor};
+use std::sync::mpsc;
+use std::time::Duration;
+
+#[tokio::test]
+// Test applying coordinated tasks on both states or compensating them.
+async fn test_dual_actor() {
+    // A transfer between two accounts.
+    let dual = DualActor::new(StateActor::new(100), StateActor::new(0));
+
+    assert_eq!(transfer(&dual, 30).await, Ok(()));
+    assert_eq!(
+        transfer(&dual, 80).await,
+        Err(ActorError::Task("insufficient funds".to_string()))
+    );
+
+    assert_eq!(dual.a().ask(|from| *from).await, Ok(70));
+    assert_eq!(dual.b().ask(|to| *to).await, Ok(30));
+    assert_eq!(dual.a().state(), ActorState::Running);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+// Test keeping the changes of tasks sent in between when compensating.
+async fn test_dual_actor_interleaved() {
+    let dual = DualActor::new(StateActor::new(100), StateActor::new(0));
+    let (started, running) = mpsc::channel::<()>();
+    let (release, released) = mpsc::channel::<()>();
+
+    let coordinated = tokio::spawn({
+        let dual = dual.clone();
+        async move {
+            dual.send_coordinated(
+                |from: &mut i32| {
+                    *from -= 10;
+                    Ok(())
+                },
+                |from: &mut i32| *from += 10,
+                move |_: &mut i32| {
+                    let _ = started.send(());
+                    let _ = released.recv();
+                    Err(ActorError::Task("rejected".to_string()))
+                },
+                |to: &mut i32| *to -= 10,
+            )
+            .await
+        }
+    });
+    running.recv_timeout(Duration::from_secs(1)).unwrap();
+    assert_eq!(dual.a().ask(|from| *from).await, Ok(90));
+    let _ = dual
+        .a()
+        .send(|from| {
+            *from += 1000;
+            Ok(())
+        })
+        .await;
+    release.send(()).unwrap();
+
+    assert_eq!(
+        coordinated.await.unwrap(),
+        Err(ActorError::Task("rejected".to_string()))
+    );
+    assert_eq!(dual.a().ask(|from| *from).await, Ok(1100));
+    assert_eq!(dual.b().ask(|to| *to).await, Ok(0));
+}
+
+// --------------------------------------------------------
+// TEST HELPER
+// --------------------------------------------------------
+
+// transfer moves the amount from the first to the second account. It fails
+// if the first account has insufficient funds.
+async fn transfer(dual: &DualActor<i32, i32>, amount: i32) -> Result<(), ActorError> {
+    dual.send_coordinated(
+        move |from: &mut i32| match *from < amount {
+            true => Err(ActorError::Task("insufficient funds".to_string())),
+            false => {
+                *from -= amount;
+                Ok(())
+            }
+        },
+        move |from: &mut i32| *from += amount,
+        move |to: &mut i32| {
+            *to += amount;
+            Ok(())
+        },
+        move |to: &mut i32| *to -= amount,
+    )
+    .await
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,164 @@
+// --------------------------------------------------------
+// Actor library - Task event tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, ActorState, AsyncActor, ErrorStrategy, StreamExt, TaskEventKind, TaskId};
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+// Test the lifecycle events of a successful task.
+async fn test_task_stream_completed() {
+    let actor = AsyncActor::new();
+    let mut events = actor.task_stream();
+
+    let task_id = actor.send(|| Ok(())).await.unwrap();
+
+    let event = events.next().await.unwrap();
+    assert_eq!(event.task_id, task_id);
+    assert_eq!(event.kind, TaskEventKind::Enqueued);
+    let event = events.next().await.unwrap();
+    assert_eq!(event.kind, TaskEventKind::Started);
+    let event = events.next().await.unwrap();
+    assert!(matches!(event.kind, TaskEventKind::Completed { .. }));
+}
+
+#[tokio::test]
+// Test the failing of a task and the dropping of the following one.
+async fn test_task_stream_failed() {
+    let actor = AsyncActor::builder().event_capacity(16).build();
+    let mut events = actor.task_stream();
+
+    // Both tasks are enqueued before the actor loop gets the chance to run.
+    let _ = actor.send(|| Err("Ouch!".to_string())).await;
+    let _ = actor.send(|| Ok(())).await;
+
+    let mut kinds = Vec::new();
+    while kinds.len() < 5 {
+        let event = events.next().await.unwrap();
+        kinds.push((event.task_id, event.kind));
+    }
+    kinds.sort_by_key(|(task_id, _)| *task_id);
+
+    assert!(kinds.contains(&(
+        TaskId(1),
+        TaskEventKind::Failed {
+            error: ActorError::Task("Ouch!".to_string())
+        }
+    )));
+    assert!(kinds.contains(&(TaskId(2), TaskEventKind::Dropped)));
+}
+
+#[tokio::test]
+// Test monitoring errors while the actor continues processing tasks.
+async fn test_error_stream_continue() {
+    let actor = AsyncActor::builder()
+        .error_strategy(ErrorStrategy::Continue)
+        .build();
+    let mut errors = actor.error_stream();
+    let counter = Arc::new(Mutex::new(0));
+
+    for i in 0..4 {
+        let counter = counter.clone();
+        let result = actor
+            .send(move || {
+                *counter.lock().unwrap() += 1;
+                if i % 2 == 0 {
+                    return Err(format!("Ouch #{}!", i));
+                }
+                Ok(())
+            })
+            .await;
+        assert!(result.is_ok());
+    }
+
+    assert_eq!(
+        errors.recv().await,
+        Ok(ActorError::Task("Ouch #0!".to_string()))
+    );
+    assert_eq!(
+        errors.recv().await,
+        Ok(ActorError::Task("Ouch #2!".to_string()))
+    );
+    // Asking the actor ensures that all tasks before have been processed.
+    let _ = actor.ask(|| ()).await;
+    assert_eq!(*counter.lock().unwrap(), 4);
+    assert_eq!(actor.state(), ActorState::Running);
+}
+
+#[tokio::test]
+// Test that the error is broadcast when the actor halts.
+async fn test_error_stream_halt() {
+    let actor = AsyncActor::new();
+    let mut errors = actor.error_stream();
+
+    let _ = actor.send(|| Err("Ouch!".to_string())).await;
+
+    assert_eq!(
+        errors.recv().await,
+        Ok(ActorError::Task("Ouch!".to_string()))
+    );
+
+    // Wait a bit to ensure that the actor changed its state.
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    assert_eq!(actor.state(), ActorState::Error);
+}
+
+#[tokio::test]
+// Test that a task not started before its deadline expires.
+async fn test_deadline_send() {
+    let actor = AsyncActor::builder().event_capacity(16).build();
+    let mut events = actor.task_stream();
+    let runs = Arc::new(Mutex::new(0));
+
+    // The first task blocks the actor beyond the deadline of the second one.
+    let _ = actor
+        .send(|| {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            Ok(())
+        })
+        .await;
+    let deadline = std::time::Instant::now() + std::time::Duration::from_millis(10);
+    let counter = runs.clone();
+    let expired = actor
+        .deadline_send(
+            move || {
+                *counter.lock().unwrap() += 1;
+                Ok(())
+            },
+            deadline,
+        )
+        .await
+        .unwrap();
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    let counter = runs.clone();
+    let _ = actor
+        .deadline_send(
+            move || {
+                *counter.lock().unwrap() += 1;
+                Ok(())
+            },
+            deadline,
+        )
+        .await;
+    let _ = actor.ask(|| ()).await;
+
+    let mut expired_kinds = Vec::new();
+    let wait = std::time::Duration::from_millis(50);
+    while let Ok(Some(event)) = tokio::time::timeout(wait, events.next()).await {
+        if event.task_id == expired {
+            expired_kinds.push(event.kind);
+        }
+    }
+    assert_eq!(
+        expired_kinds,
+        vec![TaskEventKind::Enqueued, TaskEventKind::Expired]
+    );
+    assert_eq!(*runs.lock().unwrap(), 1);
+    assert_eq!(actor.stats().tasks_expired, 1);
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
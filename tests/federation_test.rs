@@ -0,0 +1,45 @@
+// --------------------------------------------------------
+// Actor library - Federated sender tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{AsyncActor, FederatedSender};
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+// Test sending tasks to an actor on another runtime.
+async fn test_federated_send() {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let handle = runtime.handle().clone();
+    let (shutdown, shutdown_received) = tokio::sync::oneshot::channel::<()>();
+    let driver = std::thread::spawn(move || {
+        runtime.block_on(async {
+            let _ = shutdown_received.await;
+        })
+    });
+
+    let actor = AsyncActor::builder().runtime(handle.clone()).build();
+    let sender = FederatedSender::new(handle, actor.clone());
+    let worker = Arc::new(Mutex::new(None));
+    let recorded = worker.clone();
+    sender
+        .send(move || {
+            *recorded.lock().unwrap() = Some(std::thread::current().id());
+            Ok(())
+        })
+        .await
+        .unwrap();
+    let _ = actor.ask(|| ()).await;
+
+    assert_eq!(*worker.lock().unwrap(), Some(driver.thread().id()));
+
+    shutdown.send(()).unwrap();
+    driver.join().unwrap();
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,46 @@
+// --------------------------------------------------------
+// Actor library - Fused actor handle tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, ActorState, AsyncActor, ErrorStrategy};
+
+#[tokio::test]
+// Test stopping the actor after exactly n successful tasks.
+async fn test_fused_stop() {
+    let actor = AsyncActor::new();
+    let fused = actor.fuse(3);
+
+    for _ in 0..3 {
+        assert!(fused.send(|| Ok(())).await.is_ok());
+    }
+    assert_eq!(fused.send(|| Ok(())).await, Err(ActorError::Fused));
+    assert_eq!(fused.ask(|| 1).await, Err(ActorError::Fused));
+
+    assert_eq!(actor.join().await, ActorState::Stopped);
+    assert_eq!(fused.tasks_remaining(), 0);
+    assert_eq!(actor.stats().tasks_processed, 3);
+}
+
+#[tokio::test]
+// Test that failed tasks don't count against the limit.
+async fn test_fused_failed_tasks() {
+    let actor = AsyncActor::builder()
+        .error_strategy(ErrorStrategy::Continue)
+        .build();
+    let fused = actor.fuse(2);
+
+    assert_eq!(fused.ask(|| 42).await, Ok(42));
+    assert!(fused.send(|| Err("Ouch!".to_string())).await.is_ok());
+    assert_eq!(fused.tasks_remaining(), 1);
+
+    // The slot of the failed task is free again once it has been processed.
+    actor.ask(|| ()).await.unwrap();
+    assert_eq!(fused.ask(|| 7).await, Ok(7));
+    assert_eq!(fused.tasks_remaining(), 0);
+    assert_eq!(actor.join().await, ActorState::Stopped);
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
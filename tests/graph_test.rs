@@ -0,0 +1,42 @@
+// --------------------------------------------------------
+// Actor library - Task graph tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{AsyncActor, ErrorStrategy, TaskGraph, TaskStatus};
+
+#[tokio::test]
+// Test recording the status and dependencies of the tasks as DOT.
+async fn test_task_graph_dot() {
+    let actor = AsyncActor::builder()
+        .error_strategy(ErrorStrategy::Continue)
+        .build();
+    let graph = TaskGraph::new(actor.clone());
+
+    let fetch = graph.send(|| Ok(()), &[]).await.unwrap();
+    let parse = graph
+        .send(|| Err("bad".to_string()), &[fetch])
+        .await
+        .unwrap();
+    let store = graph.send(|| Ok(()), &[fetch, parse]).await.unwrap();
+    assert_eq!(graph.status(store), Some(TaskStatus::Pending));
+
+    actor.ask(|| ()).await.unwrap();
+    assert_eq!(graph.status(fetch), Some(TaskStatus::Done));
+    assert_eq!(graph.status(parse), Some(TaskStatus::Failed));
+    assert_eq!(
+        graph.to_dot(),
+        "digraph tasks {\n\
+         \x20   \"task-1\" [label=\"1\\ndone\", style=filled, fillcolor=palegreen];\n\
+         \x20   \"task-2\" [label=\"2\\nfailed\", style=filled, fillcolor=salmon];\n\
+         \x20   \"task-3\" [label=\"3\\ndone\", style=filled, fillcolor=palegreen];\n\
+         \x20   \"task-1\" -> \"task-2\";\n\
+         \x20   \"task-1\" -> \"task-3\";\n\
+         \x20   \"task-2\" -> \"task-3\";\n\
+         }\n"
+    );
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,63 @@
+// --------------------------------------------------------
+// Actor library - Task group tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{AsyncActor, TaskGroup, TaskId};
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+// Test submitting the tasks of a group in the order they were added.
+async fn test_group_submit() {
+    let actor = AsyncActor::new();
+    let processed = Arc::new(Mutex::new(Vec::new()));
+    let group = TaskGroup::new(actor.clone());
+
+    for i in 0..3 {
+        let processed = processed.clone();
+        group.add(move || {
+            processed.lock().unwrap().push(i);
+            Ok(())
+        });
+    }
+    assert_eq!(group.len(), 3);
+
+    let task_ids = group.submit().await.unwrap();
+    assert_eq!(task_ids, vec![TaskId(1), TaskId(2), TaskId(3)]);
+    assert!(group.is_empty());
+
+    actor.ask(|| ()).await.unwrap();
+    assert_eq!(*processed.lock().unwrap(), vec![0, 1, 2]);
+}
+
+#[tokio::test]
+// Test discarding the tasks of a cancelled and of a dropped group.
+async fn test_group_cancel_pending() {
+    let actor = AsyncActor::new();
+    let processed = Arc::new(Mutex::new(0));
+
+    let group = TaskGroup::new(actor.clone());
+    let counter = processed.clone();
+    group.add(move || {
+        *counter.lock().unwrap() += 1;
+        Ok(())
+    });
+    group.cancel_pending();
+    assert_eq!(group.submit().await, Ok(vec![]));
+
+    let group = TaskGroup::new(actor.clone());
+    let counter = processed.clone();
+    group.add(move || {
+        *counter.lock().unwrap() += 1;
+        Ok(())
+    });
+    drop(group);
+
+    actor.ask(|| ()).await.unwrap();
+    assert_eq!(*processed.lock().unwrap(), 0);
+    assert_eq!(actor.stats().tasks_enqueued, 1);
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
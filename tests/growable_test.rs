@@ -0,0 +1,51 @@
+// --------------------------------------------------------
+// Actor library - Growable pool tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorState, AsyncActor, GrowablePool};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[tokio::test]
+// Test growing the pool and shrinking it by its least loaded actors.
+async fn test_growable_pool() {
+    let pool = GrowablePool::new(2, AsyncActor::new);
+    pool.grow(2);
+    assert_eq!(pool.len(), 4);
+    assert_eq!(pool.target_len(), 4);
+
+    // All tasks are sent before the actor loops get the chance to run.
+    let processed = Arc::new(AtomicUsize::new(0));
+    let actors = pool.pool().actors();
+    for actor in &actors[..2] {
+        for _ in 0..3 {
+            let processed = processed.clone();
+            let sent = actor
+                .send(move || {
+                    processed.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                })
+                .await;
+            assert!(sent.is_ok());
+        }
+    }
+
+    pool.shrink(2).await;
+    assert_eq!(pool.len(), 2);
+    assert_eq!(pool.target_len(), 2);
+    assert_eq!(actors[2].state(), ActorState::Stopped);
+    assert_eq!(actors[3].state(), ActorState::Stopped);
+    let ids: Vec<_> = pool.pool().actors().iter().map(|a| a.id()).collect();
+    assert_eq!(ids, vec![actors[0].id(), actors[1].id()]);
+
+    // Removed actors drain their queued tasks before they stop.
+    pool.shrink(5).await;
+    assert!(pool.is_empty());
+    assert_eq!(processed.load(Ordering::SeqCst), 6);
+    assert!(pool.send(|| Ok(())).await.is_err());
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
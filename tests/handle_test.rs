@@ -0,0 +1,151 @@
+// --------------------------------------------------------
+// Actor library - Actor handle tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{
+    ActorError, ActorState, AsyncActor, OverflowPolicy, StopReason, StreamExt, TaskEventKind,
+    TaskId, UniqueActorHandle,
+};
+use std::sync::{Arc, Mutex};
+use tokio::time::{sleep, Duration};
+
+#[tokio::test]
+// Test that the tasks of a rate limited sender are delayed while other
+// senders are unaffected.
+async fn test_per_sender_rate_limit() {
+    let actor = AsyncActor::builder()
+        .per_sender_rate_limit(2, Duration::from_millis(200))
+        .build();
+    let busy = actor.handle();
+    let calm = actor.handle();
+    let processed = Arc::new(Mutex::new(Vec::new()));
+
+    assert_ne!(busy.sender_id(), calm.sender_id());
+
+    for i in 0..4 {
+        let processed = processed.clone();
+        let result = busy
+            .send(move || {
+                processed.lock().unwrap().push(format!("busy-{}", i));
+                Ok(())
+            })
+            .await;
+        // Delayed tasks get their ID when being sent too.
+        assert_eq!(result, Ok(TaskId(i + 1)));
+    }
+    let calm_processed = processed.clone();
+    let result = calm
+        .send(move || {
+            calm_processed.lock().unwrap().push("calm".to_string());
+            Ok(())
+        })
+        .await;
+    assert_eq!(result, Ok(TaskId(5)));
+
+    // Wait a bit, only the tasks within the limits are processed yet.
+    sleep(Duration::from_millis(50)).await;
+    assert_eq!(*processed.lock().unwrap(), vec!["busy-0", "busy-1", "calm"]);
+
+    // Wait for the window to pass, now the slow lane has been processed.
+    sleep(Duration::from_millis(250)).await;
+    assert_eq!(
+        *processed.lock().unwrap(),
+        vec!["busy-0", "busy-1", "calm", "busy-2", "busy-3"]
+    );
+}
+
+#[tokio::test]
+// Test rejecting tasks if the slow lane is full.
+async fn test_per_sender_rate_limit_slow_lane_full() {
+    let actor = AsyncActor::builder()
+        .per_sender_rate_limit(1, Duration::from_secs(10))
+        .slow_lane_depth(1)
+        .build();
+    let handle = actor.handle();
+
+    assert_eq!(handle.send(|| Ok(())).await, Ok(TaskId(1)));
+    assert_eq!(handle.send(|| Ok(())).await, Ok(TaskId(2)));
+    assert_eq!(handle.send(|| Ok(())).await, Err(ActorError::RateLimited));
+}
+
+#[tokio::test]
+// Test reporting tasks of the slow lane as dropped if forwarding fails.
+async fn test_per_sender_rate_limit_forward_failed() {
+    let actor = AsyncActor::builder()
+        .per_sender_rate_limit(1, Duration::from_millis(50))
+        .build();
+    let handle = actor.handle();
+    let mut events = actor.task_stream();
+
+    assert_eq!(handle.send(|| Ok(())).await, Ok(TaskId(1)));
+    assert_eq!(handle.send(|| Ok(())).await, Ok(TaskId(2)));
+    let _ = actor.stop().await;
+    actor.join().await;
+    sleep(Duration::from_millis(100)).await;
+
+    assert_eq!(actor.stats().tasks_dropped, 1);
+    let mut dropped = None;
+    while let Ok(Some(event)) = tokio::time::timeout(Duration::from_millis(10), events.next()).await
+    {
+        if event.kind == TaskEventKind::Dropped {
+            dropped = Some(event.task_id);
+        }
+    }
+    assert_eq!(dropped, Some(TaskId(2)));
+}
+
+#[tokio::test]
+// Test giving the send back to the sender if its task couldn't be enqueued.
+async fn test_per_sender_rate_limit_enqueue_failed() {
+    let actor = AsyncActor::builder()
+        .capacity(1)
+        .overflow_policy(OverflowPolicy::Error)
+        .per_sender_rate_limit(1, Duration::from_secs(10))
+        .build();
+    let handle = actor.handle();
+
+    // The loop doesn't run before the test waits, so the queue stays full.
+    assert!(actor.send(|| Ok(())).await.is_ok());
+    assert_eq!(handle.send(|| Ok(())).await, Err(ActorError::Full));
+    sleep(Duration::from_millis(20)).await;
+
+    let processed = Arc::new(Mutex::new(false));
+    let marker = processed.clone();
+    let result = handle
+        .send(move || {
+            *marker.lock().unwrap() = true;
+            Ok(())
+        })
+        .await;
+    assert!(result.is_ok());
+    sleep(Duration::from_millis(20)).await;
+    assert!(*processed.lock().unwrap());
+}
+
+#[tokio::test]
+// Test that dropping the unique handle stops the shared actor.
+async fn test_unique_handle_drop() {
+    let handle = UniqueActorHandle::new(AsyncActor::new());
+    let shared = handle.share();
+    let processed = Arc::new(Mutex::new(0));
+
+    let counter = processed.clone();
+    let result = handle
+        .send(move || {
+            *counter.lock().unwrap() += 1;
+            Ok(())
+        })
+        .await;
+    assert!(result.is_ok());
+    drop(handle);
+
+    assert_eq!(shared.send(|| Ok(())).await, Err(ActorError::Stopped));
+    assert_eq!(shared.join().await, ActorState::Stopped);
+    assert_eq!(shared.stop_reason(), Some(StopReason::Requested));
+    assert_eq!(*processed.lock().unwrap(), 1);
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,70 @@
+// --------------------------------------------------------
+// Actor library - Health tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, ActorHealth, ActorState, AsyncActor, ErrorStrategy};
+use std::time::Duration;
+
+#[test]
+// Test the HTTP status and JSON body of the health samples.
+fn test_health_http_response() {
+    let mut health = ActorHealth {
+        state: ActorState::Running,
+        queue_depth: 2,
+        last_error: None,
+        uptime: Duration::from_millis(1500),
+        tasks_processed: 7,
+        is_idle: false,
+    };
+
+    assert_eq!(health.http_status(), 200);
+    assert_eq!(
+        health.to_json(),
+        "{\"state\":\"Running\",\"queue_depth\":2,\"last_error\":null,\"uptime_ms\":1500,\
+         \"tasks_processed\":7,\"is_idle\":false,\"draining\":false}"
+    );
+
+    health.state = ActorState::Stopped;
+    assert_eq!(health.http_status(), 200);
+    assert!(health.to_json().ends_with("\"draining\":true}"));
+
+    health.state = ActorState::Error;
+    health.last_error = Some(ActorError::Task("say \"Ouch!\"".to_string()));
+    assert_eq!(health.http_status(), 503);
+    assert!(health
+        .to_json()
+        .contains("\"last_error\":\"say \\\"Ouch!\\\"\""));
+}
+
+#[tokio::test]
+// Test rendering the diagnostics of an actor as JSON.
+async fn test_diagnostic_json() {
+    let actor = AsyncActor::builder()
+        .name("worker")
+        .capacity(8)
+        .error_strategy(ErrorStrategy::Continue)
+        .build();
+
+    let _ = actor.send(|| Err("Ouch!".to_string())).await;
+    let task_id = actor.send(|| Ok(())).await.unwrap();
+    assert!(actor.cancel(task_id));
+    actor.ask(|| ()).await.unwrap();
+
+    let json = actor.diagnostic_json();
+    let id = actor.id().0;
+    assert!(json.starts_with(&format!(
+        "{{\"id\":{},\"name\":\"worker\",\"state\":\"Running\",\"uptime_secs\":",
+        id
+    )));
+    assert!(json.ends_with(
+        ",\"queue_depth\":0,\"capacity\":8,\"tasks_total\":3,\"tasks_failed\":1,\
+         \"tasks_cancelled\":1,\"last_error\":\"Ouch!\"}"
+    ));
+    assert!(AsyncActor::new()
+        .diagnostic_json()
+        .contains("\"name\":null"));
+}
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
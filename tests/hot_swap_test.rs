@@ -0,0 +1,32 @@
+// --------------------------------------------------------
+// Actor library - Hot swap actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{AsyncActor, HotSwapActor};
+use std::sync::Arc;
+
+#[tokio::test]
+// Test sending new tasks to the replacement while the old actor keeps its.
+async fn test_hot_swap_replace() {
+    let old = AsyncActor::new();
+    let actor = HotSwapActor::new(old.clone());
+    for _ in 0..2 {
+        let _ = actor.send(|| Ok(())).await;
+    }
+
+    let new = AsyncActor::new();
+    let replaced = actor.replace(new.clone());
+    let _ = actor.send(|| Ok(())).await;
+    let _ = replaced.stop().await;
+    replaced.join().await;
+
+    assert!(Arc::ptr_eq(&replaced, &old));
+    assert!(Arc::ptr_eq(&actor.actor(), &new));
+    assert_eq!(old.stats().tasks_processed, 2);
+    assert_eq!(new.stats().tasks_enqueued, 1);
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
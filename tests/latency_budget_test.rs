@@ -0,0 +1,50 @@
+// --------------------------------------------------------
+// Actor library - Latency budget actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{AsyncActor, LatencyBudgetActor};
+use std::sync::mpsc;
+use std::time::Duration;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+// Test counting the tasks started after the budget of their class.
+async fn test_latency_budget_actor() {
+    let actor = LatencyBudgetActor::new(AsyncActor::new());
+    actor.register_class("fast", Duration::from_millis(10));
+    actor.register_class("slow", Duration::from_secs(10));
+
+    actor
+        .send_classed(
+            || {
+                std::thread::sleep(Duration::from_millis(100));
+                Ok(())
+            },
+            "other",
+        )
+        .await
+        .unwrap();
+    actor.send_classed(|| Ok(()), "fast").await.unwrap();
+    actor.send_classed(|| Ok(()), "slow").await.unwrap();
+    let (done, finished) = mpsc::channel::<()>();
+    actor
+        .send_classed(
+            move || {
+                let _ = done.send(());
+                Ok(())
+            },
+            "fast",
+        )
+        .await
+        .unwrap();
+    finished.recv_timeout(Duration::from_secs(1)).unwrap();
+
+    assert_eq!(actor.violations("fast"), 2);
+    assert_eq!(actor.violations("slow"), 0);
+    assert_eq!(actor.violations("other"), 0);
+    assert_eq!(actor.inner().stats().sla_violations, 2);
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
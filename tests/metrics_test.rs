@@ -0,0 +1,50 @@
+// --------------------------------------------------------
+// Actor library - Task metrics tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+#![cfg(feature = "task-metrics")]
+
+use actor::{AsyncActor, TaskMetrics};
+use std::time::Duration;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+// Test recording the polls of the actor loop.
+async fn test_task_metrics() {
+    let actor = AsyncActor::new();
+
+    actor
+        .ask(|| std::thread::sleep(Duration::from_millis(1)))
+        .await
+        .unwrap();
+
+    // The answer is sent within the poll, it's recorded shortly after.
+    let mut metrics = actor.task_metrics();
+    for _ in 0..100 {
+        if metrics.total_slow_poll_count > 0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        metrics = actor.task_metrics();
+    }
+    assert!(metrics.first_poll_delay.is_some());
+    assert!(metrics.total_poll_count >= 1);
+    assert!(metrics.total_slow_poll_count >= 1);
+    assert!(metrics.total_poll_duration >= Duration::from_millis(1));
+    assert!(metrics.mean_poll_duration() > Duration::ZERO);
+}
+
+#[test]
+// Test the mean poll duration with more polls than fit into an u32.
+fn test_task_metrics_mean_poll_duration() {
+    let metrics = TaskMetrics {
+        total_poll_count: 1 << 32,
+        total_poll_duration: Duration::from_secs(1 << 32),
+        ..Default::default()
+    };
+    assert_eq!(metrics.mean_poll_duration(), Duration::from_secs(1));
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
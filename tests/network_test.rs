@@ -0,0 +1,154 @@
+// --------------------------------------------------------
+// Actor library - Network actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, NetworkActor, NetworkActorServer, TaskSerializer};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Serializes numbers to add as decimal text.
+struct AddSerializer;
+
+impl TaskSerializer for AddSerializer {
+    type Task = i64;
+
+    fn serialize(&self, task: &i64) -> Vec<u8> {
+        task.to_string().into_bytes()
+    }
+
+    fn deserialize(&self, bytes: &[u8]) -> Result<i64, String> {
+        let text = std::str::from_utf8(bytes).map_err(|err| err.to_string())?;
+        text.parse()
+            .map_err(|_| format!("invalid number {:?}", text))
+    }
+}
+
+/// Passes the bytes of the tasks unchanged.
+struct BytesSerializer;
+
+impl TaskSerializer for BytesSerializer {
+    type Task = Vec<u8>;
+
+    fn serialize(&self, task: &Vec<u8>) -> Vec<u8> {
+        task.clone()
+    }
+
+    fn deserialize(&self, bytes: &[u8]) -> Result<Vec<u8>, String> {
+        Ok(bytes.to_vec())
+    }
+}
+
+/// Starts a server adding the received numbers to the sum.
+async fn start_server(addr: SocketAddr, sum: Arc<Mutex<i64>>) -> Arc<NetworkActorServer> {
+    NetworkActorServer::bind(addr, AddSerializer, move |n| {
+        if n < 0 {
+            return Err("negative".to_string());
+        }
+        *sum.lock().unwrap() += n;
+        Ok(())
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+// Test executing tasks from multiple senders on the remote actor.
+async fn test_network_actor_send() {
+    let sum = Arc::new(Mutex::new(0));
+    let server = start_server("127.0.0.1:0".parse().unwrap(), sum.clone()).await;
+    let client = NetworkActor::connect(server.local_addr(), AddSerializer)
+        .await
+        .unwrap();
+
+    let mut senders = Vec::new();
+    for n in 1..=10 {
+        let client = client.clone();
+        senders.push(tokio::spawn(async move { client.send(n).await }));
+    }
+    for sender in senders {
+        assert_eq!(sender.await.unwrap(), Ok(()));
+    }
+    assert_eq!(*sum.lock().unwrap(), 55);
+
+    assert_eq!(
+        client.send(-1).await,
+        Err(ActorError::Task("negative".to_string()))
+    );
+}
+
+#[tokio::test]
+// Test reconnecting after the server has been restarted.
+async fn test_network_actor_reconnect() {
+    let sum = Arc::new(Mutex::new(0));
+    let server = start_server("127.0.0.1:0".parse().unwrap(), sum.clone()).await;
+    let addr = server.local_addr();
+    let client =
+        NetworkActor::connect_with_retry(addr, AddSerializer, 10, Duration::from_millis(20))
+            .await
+            .unwrap();
+    assert_eq!(client.send(1).await, Ok(()));
+
+    server.shutdown();
+    drop(server);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let _server = start_server(addr, sum.clone()).await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    assert_eq!(client.send(2).await, Ok(()));
+    assert_eq!(*sum.lock().unwrap(), 3);
+}
+
+#[tokio::test]
+// Test closing connections sending frames above the maximum size.
+async fn test_network_actor_server_frame_size() {
+    let sum = Arc::new(Mutex::new(0));
+    let server = start_server("127.0.0.1:0".parse().unwrap(), sum.clone()).await;
+    let mut stream = tokio::net::TcpStream::connect(server.local_addr())
+        .await
+        .unwrap();
+
+    stream.write_u64(1).await.unwrap();
+    stream.write_u32(u32::MAX).await.unwrap();
+    let mut buf = [0; 16];
+    let read = tokio::time::timeout(Duration::from_secs(1), stream.read(&mut buf)).await;
+    assert!(matches!(read, Ok(Ok(0)) | Ok(Err(_))));
+    assert_eq!(*sum.lock().unwrap(), 0);
+}
+
+#[tokio::test]
+// Test rejecting oversized tasks and truncating oversized error messages.
+async fn test_network_actor_oversized() {
+    const MIB: usize = 1024 * 1024;
+    let server =
+        NetworkActorServer::bind("127.0.0.1:0".parse().unwrap(), BytesSerializer, |bytes| {
+            match bytes.as_slice() {
+                b"huge" => Err("x".repeat(17 * MIB)),
+                _ => Ok(()),
+            }
+        })
+        .await
+        .unwrap();
+    let client = NetworkActor::connect(server.local_addr(), BytesSerializer)
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        client.send(vec![0; 17 * MIB]).await,
+        Err(ActorError::Send(_))
+    ));
+    assert_eq!(client.send(b"ok".to_vec()).await, Ok(()));
+
+    let sent = tokio::time::timeout(Duration::from_secs(5), client.send(b"huge".to_vec())).await;
+    match sent {
+        Ok(Err(ActorError::Task(msg))) => assert_eq!(msg.len(), 16 * MIB),
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert_eq!(client.send(b"ok".to_vec()).await, Ok(()));
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,50 @@
+// --------------------------------------------------------
+// Actor library - Output actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, OutputActor, StreamExt};
+
+#[tokio::test]
+// Test streaming the outputs of the tasks in order.
+async fn test_output_actor_stream() {
+    let actor = OutputActor::new();
+
+    for i in 1..=5 {
+        let result = actor.send(move || Ok(i * 10)).await;
+        assert_eq!(result, Ok(()));
+    }
+
+    let mut stream = actor.into_stream();
+    let mut outputs = Vec::new();
+    while let Some(output) = stream.next().await {
+        outputs.push(output.unwrap());
+    }
+
+    assert_eq!(outputs, vec![10, 20, 30, 40, 50]);
+}
+
+#[tokio::test]
+// Test that an error terminates the stream.
+async fn test_output_actor_error() {
+    let actor = OutputActor::new();
+
+    let _ = actor.send(|| Ok(1)).await;
+    let _ = actor
+        .send(|| Err(ActorError::Task("Ouch!".to_string())))
+        .await;
+    let _ = actor.send(|| Ok(3)).await;
+
+    let mut stream = actor.into_stream();
+
+    assert_eq!(stream.next().await, Some(Ok(1)));
+    assert_eq!(
+        stream.next().await,
+        Some(Err(ActorError::Task("Ouch!".to_string())))
+    );
+    assert_eq!(stream.next().await, None);
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,207 @@
+// --------------------------------------------------------
+// Actor library - Persistent actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, DeliverySemantics, PersistentActor, Replay, StateActor};
+use std::fs;
+use std::path::PathBuf;
+
+#[tokio::test]
+// Test logging and committing the executed tasks.
+async fn test_persistent_actor() {
+    let wal_path = wal_path("commit");
+    let actor = PersistentActor::new(StateActor::new(0), wal_path.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(actor.send(Add(1)).await, Ok(()));
+    assert_eq!(actor.send(Add(2)).await, Ok(()));
+    assert_eq!(actor.inner().ask(|sum| *sum).await, Ok(3));
+    assert_eq!(actor.flush().await, Ok(()));
+
+    let wal = fs::read_to_string(&wal_path).unwrap();
+    let tasks = wal.lines().filter(|line| line.starts_with("task ")).count();
+    let commits = wal
+        .lines()
+        .filter(|line| line.starts_with("commit "))
+        .count();
+    assert_eq!((tasks, commits), (2, 2));
+    fs::remove_file(wal_path).unwrap();
+}
+
+#[tokio::test]
+// Test replaying the uncommitted tasks of the log.
+async fn test_persistent_actor_replay() {
+    let wal_path = wal_path("replay");
+    fs::write(
+        &wal_path,
+        "task 1 0 1\ncommit 1\ntask 2 0 2\ntask 3 0 4\ncom",
+    )
+    .unwrap();
+
+    let actor = PersistentActor::new(StateActor::new(0), wal_path.clone())
+        .await
+        .unwrap();
+    assert_eq!(actor.send(Add(8)).await, Ok(()));
+    assert_eq!(actor.inner().ask(|sum| *sum).await, Ok(14));
+    assert_eq!(actor.flush().await, Ok(()));
+
+    // Once replayed the tasks are committed.
+    let actor = PersistentActor::<i32, Add>::new(StateActor::new(0), wal_path.clone())
+        .await
+        .unwrap();
+    assert_eq!(actor.inner().ask(|sum| *sum).await, Ok(0));
+    fs::remove_file(wal_path).unwrap();
+}
+
+#[tokio::test]
+// Test committing the tasks when they start with at-most-once delivery.
+async fn test_persistent_actor_at_most_once() {
+    let wal_path = wal_path("at-most-once");
+    let actor = PersistentActor::with_semantics(
+        StateActor::new(0),
+        wal_path.clone(),
+        DeliverySemantics::AtMostOnce,
+    )
+    .await
+    .unwrap();
+
+    // The failing task is committed anyway and so not replayed later.
+    let _ = actor.send(Add(-1)).await;
+    assert_eq!(actor.flush().await, Ok(()));
+    let actor = PersistentActor::<i32, Add>::with_semantics(
+        StateActor::new(0),
+        wal_path.clone(),
+        DeliverySemantics::AtMostOnce,
+    )
+    .await
+    .unwrap();
+    assert_eq!(actor.inner().ask(|sum| *sum).await, Ok(0));
+    fs::remove_file(wal_path).unwrap();
+}
+
+#[tokio::test]
+// Test quarantining replayed tasks which fail or cannot be decoded.
+async fn test_persistent_actor_quarantine() {
+    let wal_path = wal_path("quarantine");
+    fs::write(
+        &wal_path,
+        "task 1 0 1\ntask 2 0 -1\ntask 3 0 x\ntask 4 0 4\n",
+    )
+    .unwrap();
+
+    let actor = PersistentActor::<i32, Add>::new(StateActor::new(0), wal_path.clone())
+        .await
+        .unwrap();
+    assert_eq!(actor.inner().ask(|sum| *sum).await, Ok(5));
+    let ids: Vec<u64> = actor.quarantined().into_iter().map(|(id, _)| id).collect();
+    assert_eq!(ids, vec![2, 3]);
+
+    // The quarantined tasks are committed and not replayed again.
+    let actor = PersistentActor::<i32, Add>::new(StateActor::new(0), wal_path.clone())
+        .await
+        .unwrap();
+    assert!(actor.quarantined().is_empty());
+    assert_eq!(actor.inner().ask(|sum| *sum).await, Ok(0));
+    fs::remove_file(wal_path).unwrap();
+}
+
+#[tokio::test]
+// Test syncing the commit of an at-most-once task before it's applied.
+async fn test_persistent_actor_at_most_once_synced() {
+    let wal_path = wal_path("at-most-once-synced");
+    let actor = PersistentActor::with_semantics(
+        StateActor::new(Vec::new()),
+        wal_path.clone(),
+        DeliverySemantics::AtMostOnce,
+    )
+    .await
+    .unwrap();
+
+    // The task records whether its commit is in the log when it's applied.
+    assert_eq!(actor.send(Probe(wal_path.clone())).await, Ok(()));
+    assert_eq!(actor.inner().ask(|seen| seen.clone()).await, Ok(vec![true]));
+
+    // Dropping the actor without flushing doesn't replay the task.
+    drop(actor);
+    let actor = PersistentActor::<Vec<bool>, Probe>::with_semantics(
+        StateActor::new(Vec::new()),
+        wal_path.clone(),
+        DeliverySemantics::AtMostOnce,
+    )
+    .await
+    .unwrap();
+    assert_eq!(actor.inner().ask(|seen| seen.clone()).await, Ok(vec![]));
+    fs::remove_file(wal_path).unwrap();
+}
+
+#[tokio::test]
+// Test rejecting tasks encoded with line breaks.
+async fn test_persistent_actor_line_break() {
+    let wal_path = wal_path("line-break");
+    let actor = PersistentActor::new(StateActor::new(Vec::new()), wal_path.clone())
+        .await
+        .unwrap();
+
+    let rejected = actor.send(Probe(PathBuf::from("a\ncommit 1"))).await;
+    assert!(matches!(rejected, Err(ActorError::Persistence(_))));
+    assert_eq!(actor.inner().ask(|seen| seen.len()).await, Ok(0));
+    assert_eq!(fs::read_to_string(&wal_path).unwrap(), "");
+    fs::remove_file(wal_path).unwrap();
+}
+
+// --------------------------------------------------------
+// TEST HELPER
+// --------------------------------------------------------
+
+// Add adds its value to the state. Negative values fail.
+struct Add(i32);
+
+impl Replay<i32> for Add {
+    fn encode(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn decode(encoded: &str) -> Option<Self> {
+        encoded.parse().ok().map(Add)
+    }
+
+    fn apply(self, sum: &mut i32) -> Result<(), ActorError> {
+        if self.0 < 0 {
+            return Err(ActorError::Task("negative value".to_string()));
+        }
+        *sum += self.0;
+        Ok(())
+    }
+}
+
+// Probe records if its commit is in the log at the given path.
+struct Probe(PathBuf);
+
+impl Replay<Vec<bool>> for Probe {
+    fn encode(&self) -> String {
+        self.0.display().to_string()
+    }
+
+    fn decode(encoded: &str) -> Option<Self> {
+        Some(Probe(PathBuf::from(encoded)))
+    }
+
+    fn apply(self, seen: &mut Vec<bool>) -> Result<(), ActorError> {
+        let wal = fs::read_to_string(&self.0).unwrap_or_default();
+        seen.push(wal.lines().any(|line| line == "commit 1"));
+        Ok(())
+    }
+}
+
+// wal_path returns a log file path unique for the test.
+fn wal_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("actor-wal-{}-{}", name, std::process::id()));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,59 @@
+// --------------------------------------------------------
+// Actor library - Pipeline actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, PipelineActor, StreamExt};
+
+#[tokio::test]
+// Test passing the inputs through all stages in order.
+async fn test_pipeline() {
+    let (sink, output) = PipelineActor::new()
+        .stage(|s: &'static str| {
+            s.parse::<i32>()
+                .map_err(|e| ActorError::Task(e.to_string()))
+        })
+        .stage(|n| Ok(n * 2))
+        .stage(|n| Ok(format!("<{}>", n)))
+        .build();
+
+    for input in ["1", "2", "3"] {
+        assert!(sink.send(input).await.is_ok());
+    }
+    drop(sink);
+
+    let mut stream = output.into_stream();
+    let mut outputs = Vec::new();
+    while let Some(output) = stream.next().await {
+        outputs.push(output.unwrap());
+    }
+    assert_eq!(outputs, vec!["<2>", "<4>", "<6>"]);
+}
+
+#[tokio::test]
+// Test that a failing stage ends the pipeline with its error.
+async fn test_pipeline_error() {
+    let (sink, output) = PipelineActor::new()
+        .stage(|n: i32| match n {
+            0 => Err(ActorError::Task("zero".to_string())),
+            n => Ok(10 / n),
+        })
+        .stage(|n| Ok(n + 1))
+        .build();
+
+    for input in [5, 0, 2] {
+        let _ = sink.send(input).await;
+    }
+
+    let mut stream = output.into_stream();
+    assert_eq!(stream.next().await, Some(Ok(3)));
+    assert_eq!(
+        stream.next().await,
+        Some(Err(ActorError::Task("zero".to_string())))
+    );
+    assert_eq!(stream.next().await, None);
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,54 @@
+// --------------------------------------------------------
+// Actor library - Poll actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, ActorState, PollActor};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+fn incr() -> Result<(), &'static str> {
+    COUNTER.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+}
+
+fn fail() -> Result<(), &'static str> {
+    Err("Ouch!")
+}
+
+#[test]
+// Test running queued tasks by polling the actor.
+fn test_poll_actor() {
+    let mut actor = PollActor::<2>::new();
+
+    assert_eq!(actor.send(incr), Ok(()));
+    assert_eq!(actor.send(incr), Ok(()));
+    assert_eq!(actor.send(incr), Err(ActorError::Full));
+
+    while actor.poll() {}
+    assert_eq!(COUNTER.load(Ordering::SeqCst), 2);
+
+    actor.stop();
+    assert_eq!(actor.state(), ActorState::Stopped);
+    assert_eq!(actor.send(incr), Err(ActorError::Stopped));
+}
+
+#[test]
+// Test that a failing task drops the remaining ones.
+fn test_poll_actor_error() {
+    let mut actor = PollActor::<4>::default();
+
+    assert_eq!(actor.send(fail), Ok(()));
+    assert_eq!(actor.send(fail), Ok(()));
+    assert!(actor.poll());
+    assert!(!actor.poll());
+
+    assert_eq!(actor.state(), ActorState::Error);
+    assert_eq!(actor.message(), Some("Ouch!"));
+    assert_eq!(actor.send(fail), Err(ActorError::Task("Ouch!".into())));
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
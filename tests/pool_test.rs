@@ -0,0 +1,419 @@
+// --------------------------------------------------------
+// Actor library - Actor pool tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{
+    spawn_supervised_pool, ActorError, AsyncActor, AsyncActorPool, StopReason, StreamExt,
+    TaskEventKind, TaskId,
+};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[tokio::test]
+// Test distributing the tasks in round-robin order.
+async fn test_pool_send() {
+    let pool = AsyncActorPool::new(3);
+
+    for _ in 0..4 {
+        assert!(pool.send(|| Ok(())).await.is_ok());
+    }
+
+    let depths: Vec<_> = pool.actors().iter().map(|a| a.queue_len()).collect();
+    assert_eq!(depths, vec![2, 1, 1]);
+    assert_eq!(pool.send(|| Ok(())).await, Ok(TaskId(2)));
+}
+
+#[tokio::test]
+// Test moving the waiting tasks of a hot actor to the cold ones.
+async fn test_pool_rebalance() {
+    let pool = AsyncActorPool::new(3);
+    let processed = Arc::new(Mutex::new(Vec::new()));
+
+    // All tasks are sent before the actor loops get the chance to run.
+    for i in 0..6 {
+        let processed = processed.clone();
+        let result = pool.actors()[0]
+            .send(move || {
+                processed.lock().unwrap().push(i);
+                Ok(())
+            })
+            .await;
+        assert!(result.is_ok());
+    }
+
+    assert_eq!(pool.rebalance().await, 4);
+    let depths: Vec<_> = pool.actors().iter().map(|a| a.queue_len()).collect();
+    assert_eq!(depths, vec![2, 2, 2]);
+    assert_eq!(pool.rebalance().await, 0);
+
+    for actor in pool.actors() {
+        let _ = actor.ask(|| ()).await;
+    }
+    let mut processed = processed.lock().unwrap().clone();
+    processed.sort();
+    assert_eq!(processed, vec![0, 1, 2, 3, 4, 5]);
+}
+
+#[tokio::test]
+// Test dropping a task rejected by a cold actor and keeping the others.
+async fn test_pool_rebalance_rejected() {
+    // The memory budget of the second actor rejects all tasks.
+    let pool = AsyncActorPool::from_actors(vec![
+        AsyncActor::new(),
+        AsyncActor::builder().memory_budget(1).build(),
+        AsyncActor::new(),
+    ]);
+    let processed = Arc::new(Mutex::new(Vec::new()));
+    let hot = pool.actors()[0].clone();
+    for i in 0..6 {
+        let processed = processed.clone();
+        let result = hot
+            .send(move || {
+                processed.lock().unwrap().push(i);
+                Ok(())
+            })
+            .await;
+        assert!(result.is_ok());
+    }
+
+    let mut events = hot.task_stream();
+    assert_eq!(pool.rebalance().await, 2);
+    let depths: Vec<_> = pool.actors().iter().map(|a| a.queue_len()).collect();
+    assert_eq!(depths, vec![3, 0, 2]);
+    assert_eq!(hot.stats().tasks_dropped, 1);
+    let mut dropped = None;
+    while let Ok(Some(event)) = tokio::time::timeout(Duration::from_millis(10), events.next()).await
+    {
+        if event.kind == TaskEventKind::Dropped {
+            dropped = Some(event.task_id);
+        }
+    }
+    assert_eq!(dropped, Some(TaskId(5)));
+
+    for actor in pool.actors() {
+        let _ = actor.ask(|| ()).await;
+    }
+    let mut processed = processed.lock().unwrap().clone();
+    processed.sort();
+    assert_eq!(processed, vec![0, 1, 2, 3, 5]);
+}
+
+#[tokio::test]
+// Test ending the rebalance when the hot actor drains its queue meanwhile.
+async fn test_pool_rebalance_drained() {
+    let pool = AsyncActorPool::from_actors(vec![
+        AsyncActor::new(),
+        AsyncActor::builder().backpressure_semaphore(1).build(),
+        AsyncActor::new(),
+    ]);
+    let processed = Arc::new(Mutex::new(0));
+    let hot = pool.actors()[0].clone();
+
+    // The only permit of the first cold actor is released by the last task
+    // staying on the hot actor, so its queue is drained before moving on.
+    let (release, released) = tokio::sync::oneshot::channel::<()>();
+    let _ = pool.actors()[1]
+        .send_future(async move {
+            let _ = released.await;
+            Ok(())
+        })
+        .await;
+    let mut release = Some(release);
+    for i in 0..6 {
+        let processed = processed.clone();
+        let release = if i == 3 { release.take() } else { None };
+        let result = hot
+            .send(move || {
+                *processed.lock().unwrap() += 1;
+                if let Some(release) = release {
+                    let _ = release.send(());
+                }
+                Ok(())
+            })
+            .await;
+        assert!(result.is_ok());
+    }
+
+    let rebalanced = tokio::time::timeout(Duration::from_secs(1), pool.rebalance()).await;
+    assert_eq!(rebalanced, Ok(2));
+    for actor in pool.actors() {
+        let _ = actor.ask(|| ()).await;
+    }
+    assert_eq!(*processed.lock().unwrap(), 6);
+}
+
+#[tokio::test]
+// Test mapping the inputs on the pool and reducing the results.
+async fn test_pool_map_reduce() {
+    let pool = AsyncActorPool::new(4);
+
+    let inputs: Vec<u64> = (1..=100).collect();
+    let sum = pool
+        .map_reduce(inputs, |x| x * x, |squares| squares.into_iter().sum())
+        .await;
+    assert_eq!(sum, Ok(338_350));
+
+    let order = pool
+        .map_reduce(vec![3, 1, 2], |x| vec![x], |parts| parts.concat())
+        .await;
+    assert_eq!(order, Ok(vec![3, 1, 2]));
+}
+
+#[tokio::test]
+// Test routing the tasks with the same key to the same actor.
+async fn test_pool_send_sticky() {
+    let pool = AsyncActorPool::new(4);
+
+    for _ in 0..3 {
+        assert!(pool.send_sticky(|| Ok(()), "user-1").await.is_ok());
+    }
+    let depths: Vec<_> = pool.actors().iter().map(|a| a.queue_len()).collect();
+    assert_eq!(depths.iter().sum::<usize>(), 3);
+    assert!(depths.contains(&3));
+
+    let keys: Vec<u32> = (0..40).collect();
+    for key in &keys {
+        assert!(pool.send_sticky(|| Ok(()), key).await.is_ok());
+    }
+    let used = pool.actors().iter().filter(|a| a.queue_len() > 0).count();
+    assert!(used > 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+// Test draining all actors and forcing the ones exceeding the timeout.
+async fn test_pool_drain_and_stop() {
+    let pool = AsyncActorPool::new(3);
+    let processed = Arc::new(Mutex::new(0));
+
+    for _ in 0..6 {
+        let processed = processed.clone();
+        let result = pool
+            .send(move || {
+                *processed.lock().unwrap() += 1;
+                Ok(())
+            })
+            .await;
+        assert!(result.is_ok());
+    }
+    // The last actor is busy longer than the timeout.
+    let slow = pool.actors()[2].clone();
+    assert!(slow
+        .send(|| {
+            std::thread::sleep(Duration::from_millis(300));
+            Ok(())
+        })
+        .await
+        .is_ok());
+    assert!(slow.send(|| Ok(())).await.is_ok());
+
+    let results = pool.drain_and_stop(Duration::from_millis(100)).await;
+    assert_eq!(results, vec![Ok(()), Ok(()), Err(ActorError::Timeout)]);
+    assert_eq!(*processed.lock().unwrap(), 6);
+
+    slow.join().await;
+    assert_eq!(slow.stop_reason(), Some(StopReason::Timeout));
+    assert_eq!(slow.stats().tasks_processed, 3);
+}
+
+#[tokio::test]
+// Test replacing failed actors of the pool by the health monitor.
+async fn test_health_monitor() {
+    let pool = AsyncActorPool::new(3);
+    pool.enable_health_monitor(Duration::from_millis(10), AsyncActor::new);
+
+    let failing = pool.actors()[1].clone();
+    let _ = failing.send(|| Err("Ouch!".to_string())).await;
+    failing.join().await;
+    assert_eq!(pool.healthy_count(), 2);
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(pool.healthy_count(), 3);
+    assert_eq!(pool.replace_count(), 1);
+    assert!(!Arc::ptr_eq(&pool.actors()[1], &failing));
+
+    // Actors stopped on purpose are not replaced.
+    let _ = pool.drain_and_stop(Duration::from_millis(100)).await;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(pool.healthy_count(), 0);
+    assert_eq!(pool.replace_count(), 1);
+}
+
+#[tokio::test]
+// Test restarting the failed members of a supervised pool.
+async fn test_supervised_pool() {
+    let pool = spawn_supervised_pool(2, AsyncActor::new);
+
+    let failing = pool.actors()[0].clone();
+    let _ = failing.send(|| Err("Ouch!".to_string())).await;
+    failing.join().await;
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    assert_eq!(pool.replace_count(), 1);
+    assert_eq!(pool.restarting_count(), 0);
+    assert_eq!(pool.healthy_count(), 2);
+    for _ in 0..4 {
+        assert!(pool.send(|| Ok(())).await.is_ok());
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+// Test counting the failed members until their restart replaced them.
+async fn test_supervised_pool_restarting() {
+    let (release, released) = std::sync::mpsc::channel::<()>();
+    let released = Mutex::new(released);
+    let created = std::sync::atomic::AtomicUsize::new(0);
+    let pool = spawn_supervised_pool(2, move || {
+        // The restarts wait for the release.
+        if created.fetch_add(1, std::sync::atomic::Ordering::SeqCst) >= 2 {
+            let _ = released.lock().unwrap().recv();
+        }
+        AsyncActor::new()
+    });
+
+    let _ = pool.actors()[0].send(|| Err("Ouch!".to_string())).await;
+    pool.actors()[0].join().await;
+    assert_eq!(pool.restarting_count(), 1);
+    assert_eq!(pool.healthy_count(), 1);
+
+    release.send(()).unwrap();
+    for _ in 0..100 {
+        if pool.replace_count() == 1 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    assert_eq!(pool.replace_count(), 1);
+    assert_eq!(pool.restarting_count(), 0);
+}
+
+#[tokio::test]
+// Test dropping the members together with a supervised pool.
+async fn test_supervised_pool_drop() {
+    let pool = spawn_supervised_pool(2, AsyncActor::new);
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    let members: Vec<_> = pool.actors().iter().map(Arc::downgrade).collect();
+
+    drop(pool);
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert!(members.iter().all(|member| member.upgrade().is_none()));
+}
+
+#[tokio::test]
+// Test skipping members which are not running when sending round-robin.
+async fn test_pool_send_skips_dead() {
+    let stopped = AsyncActor::new();
+    let _ = stopped.stop().await;
+    stopped.join().await;
+    let running = AsyncActor::new();
+    let pool = AsyncActorPool::from_actors(vec![stopped, running.clone()]);
+
+    for _ in 0..4 {
+        assert!(pool.send(|| Ok(())).await.is_ok());
+    }
+    assert_eq!(running.stats().tasks_enqueued, 4);
+}
+
+#[tokio::test]
+// Test finding the least and the most loaded actor of the pool.
+async fn test_pool_load_introspection() {
+    let pool = AsyncActorPool::new(3);
+    assert!(AsyncActorPool::new(0).least_loaded().is_none());
+
+    // All tasks are sent before the actor loops get the chance to run.
+    let actors = pool.actors();
+    for _ in 0..3 {
+        actors[1].send(|| Ok(())).await.unwrap();
+    }
+    actors[2].send(|| Ok(())).await.unwrap();
+
+    assert_eq!(pool.least_loaded().map(|a| a.id()), Some(actors[0].id()));
+    assert_eq!(pool.most_loaded().map(|a| a.id()), Some(actors[1].id()));
+    let distribution: Vec<_> = actors.iter().map(|a| a.id()).zip([0, 3, 1]).collect();
+    assert_eq!(pool.load_distribution(), distribution);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+// Test limiting the tasks in flight over all actors of the pool.
+async fn test_pool_shared_semaphore() {
+    let pool = AsyncActorPool::with_shared_semaphore(3, 2);
+    let (release, released) = std::sync::mpsc::channel::<()>();
+    let released = Arc::new(Mutex::new(released));
+
+    // Two blocked tasks on two actors use both permits of the pool.
+    for _ in 0..2 {
+        let released = released.clone();
+        pool.send(move || {
+            let _ = released.lock().unwrap().recv();
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+    assert_eq!(pool.actors()[2].try_send(|| Ok(())), Err(ActorError::Full));
+
+    // Finishing a task releases its permit for the waiting sender.
+    release.send(()).unwrap();
+    let sent = tokio::time::timeout(Duration::from_secs(1), pool.send(|| Ok(())));
+    assert!(sent.await.unwrap().is_ok());
+    release.send(()).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+// Test taking the result of the fastest actor and cancelling the others. The
+// two busy actors block a worker each, so a third one is needed.
+async fn test_pool_ask_first() {
+    let pool = AsyncActorPool::new(3);
+    let (release, released) = std::sync::mpsc::channel::<()>();
+    let released = Arc::new(Mutex::new(released));
+
+    // Two of the actors are busy, the free one answers.
+    let actors = pool.actors();
+    for actor in &actors[..2] {
+        let released = released.clone();
+        actor
+            .send(move || {
+                let _ = released.lock().unwrap().recv();
+                Ok(())
+            })
+            .await
+            .unwrap();
+    }
+    let first = tokio::time::timeout(Duration::from_secs(1), pool.ask_first(|| || 42));
+    assert_eq!(first.await.unwrap(), Ok(42));
+    for actor in &actors[..2] {
+        assert_eq!(actor.stats().tasks_cancelled, 1);
+    }
+    release.send(()).unwrap();
+    release.send(()).unwrap();
+
+    assert_eq!(
+        AsyncActorPool::new(0).ask_first(|| || 42).await,
+        Err(ActorError::Stopped)
+    );
+}
+
+#[tokio::test]
+// Test mapping the inputs on the pool keeping their order.
+async fn test_pool_map() {
+    let pool = AsyncActorPool::new(3);
+
+    let results = pool.map((1..=6).collect(), |x: u64| x * 10).await;
+    assert_eq!(results, (1..=6).map(|x| Ok(x * 10)).collect::<Vec<_>>());
+
+    let results = AsyncActorPool::new(0).map(vec![1], |x: u64| x).await;
+    assert_eq!(results, vec![Err(ActorError::Stopped)]);
+
+    // The sample rate of the actors doesn't skip the mapper tasks.
+    let sampled = (0..2)
+        .map(|_| AsyncActor::builder().sample_rate(0.0).build())
+        .collect();
+    let results = AsyncActorPool::from_actors(sampled)
+        .map(vec![1, 2], |x: u64| x)
+        .await;
+    assert_eq!(results, vec![Ok(1), Ok(2)]);
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
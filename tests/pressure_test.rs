@@ -0,0 +1,83 @@
+// --------------------------------------------------------
+// Actor library - Memory pressure actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, AsyncActor, MemoryPressureActor, ShedPolicy};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[tokio::test]
+// Test rejecting new tasks under memory pressure until it recovers.
+async fn test_pressure_drop_new() {
+    let available = Arc::new(AtomicUsize::new(100));
+    let probed = available.clone();
+    let actor = MemoryPressureActor::with_probe(
+        AsyncActor::new(),
+        1000,
+        ShedPolicy::DropNew,
+        Duration::from_millis(10),
+        move || Some(probed.load(Ordering::SeqCst)),
+    );
+
+    assert_eq!(actor.send(|| Ok(())).await, Err(ActorError::MemoryPressure));
+
+    available.store(5000, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!actor.is_under_pressure());
+    assert!(actor.send(|| Ok(())).await.is_ok());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+// Test dropping the oldest waiting task under memory pressure.
+async fn test_pressure_drop_oldest() {
+    let available = Arc::new(AtomicUsize::new(5000));
+    let probed = available.clone();
+    let actor = MemoryPressureActor::with_probe(
+        AsyncActor::new(),
+        1000,
+        ShedPolicy::DropOldest,
+        Duration::from_millis(10),
+        move || Some(probed.load(Ordering::SeqCst)),
+    );
+    let (release, released) = std::sync::mpsc::channel::<()>();
+    let processed = Arc::new(Mutex::new(Vec::new()));
+
+    // The first task blocks the actor while the others wait.
+    let _ = actor
+        .send(move || {
+            let _ = released.recv();
+            Ok(())
+        })
+        .await;
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    for i in 0..2 {
+        let processed = processed.clone();
+        let _ = actor
+            .send(move || {
+                processed.lock().unwrap().push(i);
+                Ok(())
+            })
+            .await;
+    }
+
+    available.store(100, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let recorded = processed.clone();
+    let _ = actor
+        .send(move || {
+            recorded.lock().unwrap().push(2);
+            Ok(())
+        })
+        .await;
+    release.send(()).unwrap();
+    let _ = actor.inner().ask(|| ()).await;
+
+    assert_eq!(*processed.lock().unwrap(), vec![1, 2]);
+    assert_eq!(actor.inner().tasks_dropped(), 1);
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
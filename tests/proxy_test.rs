@@ -0,0 +1,71 @@
+// --------------------------------------------------------
+// Actor library - Actor proxy tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, ActorProxy, AsyncActor, NetworkActorServer, TaskSerializer};
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+// Test getting the same results from a local and a remote proxy.
+async fn test_actor_proxy() {
+    let sum = Arc::new(Mutex::new(0));
+    let server = NetworkActorServer::bind("127.0.0.1:0".parse().unwrap(), AddSerializer, {
+        let sum = sum.clone();
+        move |n| add(&sum, n)
+    })
+    .await
+    .unwrap();
+    let local = ActorProxy::<AddSerializer>::local(AsyncActor::new(), {
+        let sum = sum.clone();
+        move |n| add(&sum, n)
+    });
+    let remote = ActorProxy::remote(server.local_addr(), AddSerializer)
+        .await
+        .unwrap();
+    assert!(local.is_local());
+    assert!(!remote.is_local());
+
+    for proxy in [local, remote] {
+        assert_eq!(proxy.send(20).await, Ok(()));
+        assert_eq!(
+            proxy.send(-1).await,
+            Err(ActorError::Task("negative".to_string()))
+        );
+    }
+    assert_eq!(*sum.lock().unwrap(), 40);
+}
+
+// --------------------------------------------------------
+// TEST HELPER
+// --------------------------------------------------------
+
+/// Serializes numbers to add as decimal text.
+struct AddSerializer;
+
+impl TaskSerializer for AddSerializer {
+    type Task = i64;
+
+    fn serialize(&self, task: &i64) -> Vec<u8> {
+        task.to_string().into_bytes()
+    }
+
+    fn deserialize(&self, bytes: &[u8]) -> Result<i64, String> {
+        let text = std::str::from_utf8(bytes).map_err(|err| err.to_string())?;
+        text.parse()
+            .map_err(|_| format!("invalid number {:?}", text))
+    }
+}
+
+/// Adds non-negative numbers to the sum.
+fn add(sum: &Mutex<i64>, n: i64) -> Result<(), String> {
+    if n < 0 {
+        return Err("negative".to_string());
+    }
+    *sum.lock().unwrap() += n;
+    Ok(())
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
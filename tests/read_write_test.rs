@@ -0,0 +1,42 @@
+// --------------------------------------------------------
+// Actor library - Read/write actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorState, ReadWriteActor};
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+// Test processing the waiting reads before the next write.
+async fn test_read_write_priority() {
+    let actor = ReadWriteActor::new(0);
+    let seen = Arc::new(Mutex::new(Vec::new()));
+
+    // All tasks are enqueued before the actor loop gets the chance to run.
+    for value in [1, 2] {
+        let _ = actor
+            .send_write(move |state| {
+                *state = value;
+                Ok(())
+            })
+            .await;
+    }
+    for _ in 0..2 {
+        let seen = seen.clone();
+        let _ = actor
+            .send_read(move |state| {
+                seen.lock().unwrap().push(*state);
+                Ok(())
+            })
+            .await;
+    }
+    let _ = actor.stop().await;
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+    assert_eq!(*seen.lock().unwrap(), vec![0, 0]);
+    assert_eq!(actor.state(), ActorState::Stopped);
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,66 @@
+// --------------------------------------------------------
+// Actor library - Recursive actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, AsyncActor, Recursion, RecursiveActor};
+use tokio::sync::mpsc;
+
+/// Counts down by sending the next level until zero is reached.
+fn countdown(
+    n: usize,
+    done: mpsc::UnboundedSender<usize>,
+) -> impl FnOnce(&Recursion) -> Result<(), String> + Send {
+    move |recursion| {
+        if n == 0 {
+            let _ = done.send(recursion.depth());
+            return Ok(());
+        }
+        recursion
+            .send(countdown(n - 1, done))
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+    }
+}
+
+#[tokio::test]
+// Test a deep recursion without growing the stack.
+async fn test_recursive_deep() {
+    let actor = RecursiveActor::with_max_depth(AsyncActor::new(), 100_000);
+    let (done, mut depths) = mpsc::unbounded_channel();
+
+    assert!(actor.send(countdown(50_000, done)).await.is_ok());
+    assert_eq!(depths.recv().await, Some(50_000));
+
+    // The queued tasks only hold a weak reference to the actor.
+    let weak = actor.actor().downgrade();
+    drop(actor);
+    assert!(weak.upgrade().is_none());
+    assert_eq!(weak.try_send(|| Ok(())), Err(ActorError::Stopped));
+}
+
+#[tokio::test]
+// Test stopping an endless recursion at the maximum depth.
+async fn test_recursive_limit() {
+    fn endless(
+        errors: mpsc::UnboundedSender<(usize, ActorError)>,
+    ) -> impl FnOnce(&Recursion) -> Result<(), String> + Send {
+        move |recursion| {
+            let depth = recursion.depth();
+            if let Err(err) = recursion.send(endless(errors.clone())) {
+                let _ = errors.send((depth, err));
+            }
+            Ok(())
+        }
+    }
+
+    let actor = RecursiveActor::with_max_depth(AsyncActor::new(), 8);
+    let (errors, mut received) = mpsc::unbounded_channel();
+
+    assert!(actor.send(endless(errors)).await.is_ok());
+    assert_eq!(received.recv().await, Some((8, ActorError::RecursionLimit)));
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
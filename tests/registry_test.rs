@@ -0,0 +1,75 @@
+// --------------------------------------------------------
+// Actor library - Task registry tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+#![cfg(feature = "registry")]
+
+use actor::{ActorId, AsyncActor, InFlightTask, TaskId, TaskRegistry};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+// Test tracking the waiting and running tasks of an actor.
+async fn test_task_registry() {
+    let actor = AsyncActor::builder().name("registered").build();
+    let (started, running) = mpsc::channel::<()>();
+    let (release, released) = mpsc::channel::<()>();
+    let blocking = actor
+        .send(move || {
+            let _ = started.send(());
+            let _ = released.recv();
+            Ok(())
+        })
+        .await
+        .unwrap();
+    let waiting = actor.send(|| Ok(())).await.unwrap();
+    running.recv_timeout(Duration::from_secs(1)).unwrap();
+
+    let tasks = in_flight(actor.id());
+    assert_eq!(tasks.len(), 2);
+    assert_eq!(tasks[0].task_id, blocking);
+    assert!(tasks[0].started_at.is_some());
+    assert_eq!(tasks[1].task_id, waiting);
+    assert_eq!(tasks[1].actor_name.as_deref(), Some("registered"));
+    assert!(tasks[1].started_at.is_none());
+
+    // The answer of the ask is sent before its task is completed.
+    release.send(()).unwrap();
+    actor.ask(|| ()).await.unwrap();
+    for _ in 0..100 {
+        if in_flight(actor.id()).is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert!(in_flight(actor.id()).is_empty());
+}
+
+#[tokio::test]
+// Test registering and completing tasks of other actors.
+async fn test_task_registry_manual() {
+    let registry = TaskRegistry::global();
+    let actor_id = ActorId(u64::MAX);
+
+    registry.register(TaskId(1), actor_id, Instant::now());
+    assert_eq!(in_flight(actor_id).len(), 1);
+    registry.complete(actor_id, TaskId(1));
+    assert!(in_flight(actor_id).is_empty());
+}
+
+// --------------------------------------------------------
+// TEST HELPER
+// --------------------------------------------------------
+
+fn in_flight(actor_id: ActorId) -> Vec<InFlightTask> {
+    let tasks = TaskRegistry::global().in_flight();
+    tasks
+        .into_iter()
+        .filter(|t| t.actor_id == actor_id)
+        .collect()
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
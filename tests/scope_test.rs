@@ -0,0 +1,52 @@
+// --------------------------------------------------------
+// Actor library - Actor scope tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorScope, AsyncActor};
+use std::time::Duration;
+
+#[tokio::test]
+// Test running the cleanups in reverse order when the actor ends.
+async fn test_actor_scope() {
+    let actor = AsyncActor::new();
+    let scope = ActorScope::new(actor.clone());
+    let (released, mut done) = tokio::sync::mpsc::unbounded_channel();
+    for name in ["database", "file", "socket"] {
+        let released = released.clone();
+        scope.on_exit(move || {
+            let _ = released.send(name);
+        });
+    }
+    drop(scope);
+
+    let _ = actor.send(|| Err("ouch".to_string())).await;
+    actor.join().await;
+
+    let mut order = Vec::new();
+    for _ in 0..3 {
+        let name = tokio::time::timeout(Duration::from_secs(1), done.recv()).await;
+        order.push(name.unwrap().unwrap());
+    }
+    assert_eq!(order, vec!["socket", "file", "database"]);
+}
+
+#[tokio::test]
+// Test running a cleanup registered after the actor ended.
+async fn test_actor_scope_late() {
+    let actor = AsyncActor::new();
+    let scope = ActorScope::new(actor.clone());
+    actor.stop().await.unwrap();
+    actor.join().await;
+
+    let (done, finished) = tokio::sync::oneshot::channel();
+    scope.on_exit(move || {
+        let _ = done.send(());
+    });
+    let finished = tokio::time::timeout(Duration::from_secs(1), finished).await;
+    assert!(finished.is_ok());
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,51 @@
+// --------------------------------------------------------
+// Actor library - Graceful shutdown coordinator tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorState, AsyncActor, GracefulShutdownCoordinator, StopReason};
+use std::time::Duration;
+
+#[tokio::test]
+// Test stopping the actors in the order of their priority.
+async fn test_shutdown_order() {
+    let coordinator = GracefulShutdownCoordinator::new();
+    let actors: Vec<_> = (0..3).map(|_| AsyncActor::new()).collect();
+    for (actor, priority) in actors.iter().zip([1, 3, 2]) {
+        coordinator.register(priority, actor.clone());
+    }
+
+    let report = coordinator.shutdown(Duration::from_secs(1)).await;
+
+    let ids: Vec<_> = [1, 2, 0].iter().map(|&i| actors[i].id()).collect();
+    assert_eq!(report.stopped, ids);
+    assert!(report.timed_out.is_empty());
+    for actor in &actors {
+        assert_eq!(actor.state(), ActorState::Stopped);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+// Test stopping actors immediately which don't drain in time.
+async fn test_shutdown_timeout() {
+    let coordinator = GracefulShutdownCoordinator::new();
+    let slow = AsyncActor::new();
+    coordinator.register(1, slow.clone());
+    let _ = slow
+        .send(|| {
+            std::thread::sleep(Duration::from_millis(100));
+            Ok(())
+        })
+        .await;
+    let _ = slow.send(|| Ok(())).await;
+
+    let report = coordinator.shutdown(Duration::from_millis(20)).await;
+
+    assert_eq!(report.timed_out, vec![slow.id()]);
+    assert_eq!(slow.join().await, ActorState::Stopped);
+    assert_eq!(slow.stop_reason(), Some(StopReason::Timeout));
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
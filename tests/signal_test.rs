@@ -0,0 +1,33 @@
+// --------------------------------------------------------
+// Actor library - Signal tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+#![cfg(unix)]
+
+use actor::{ActorState, AsyncActor, StopReason};
+use std::process::Command;
+
+#[tokio::test]
+// Test stopping all registered actors when receiving SIGTERM. The test has
+// its own file as the signal is sent to the whole test process.
+async fn test_shutdown_on_signal() {
+    let first = AsyncActor::builder().shutdown_on_signal().build();
+    let second = AsyncActor::builder().shutdown_on_signal().build();
+    let unregistered = AsyncActor::new();
+
+    let status = Command::new("kill")
+        .args(["-TERM", &std::process::id().to_string()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    assert_eq!(first.join().await, ActorState::Stopped);
+    assert_eq!(second.join().await, ActorState::Stopped);
+    assert_eq!(first.stop_reason(), Some(StopReason::Requested));
+    assert_eq!(unregistered.state(), ActorState::Running);
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
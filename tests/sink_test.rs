@@ -0,0 +1,56 @@
+// --------------------------------------------------------
+// Actor library - Sink tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, ActorState, AsyncActor, Sink, Task};
+use std::future::poll_fn;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+// Test sending tasks into the actor sink and closing it.
+async fn test_actor_sink() {
+    let actor = AsyncActor::builder().capacity(2).build();
+    let mut sink = actor.clone().into_sink::<Task>();
+    let processed = Arc::new(Mutex::new(Vec::new()));
+
+    // More tasks than the queue holds let the sink wait for room.
+    for i in 0..5 {
+        let processed = processed.clone();
+        let task: Task = Box::new(move || {
+            processed.lock().unwrap().push(i);
+            Ok(())
+        });
+        assert_eq!(feed(&mut sink, task).await, Ok(()));
+    }
+    assert_eq!(
+        poll_fn(|cx| Pin::new(&mut sink).poll_close(cx)).await,
+        Ok(())
+    );
+
+    assert_eq!(*processed.lock().unwrap(), vec![0, 1, 2, 3, 4]);
+    assert_eq!(actor.state(), ActorState::Stopped);
+    assert_eq!(
+        poll_fn(|cx| Pin::new(&mut sink).poll_ready(cx)).await,
+        Err(ActorError::Stopped)
+    );
+}
+
+// --------------------------------------------------------
+// TEST HELPER
+// --------------------------------------------------------
+
+/// Sends the item into the sink and flushes it.
+async fn feed<S, T>(sink: &mut S, item: T) -> Result<(), S::Error>
+where
+    S: Sink<T> + Unpin,
+{
+    poll_fn(|cx| Pin::new(&mut *sink).poll_ready(cx)).await?;
+    Pin::new(&mut *sink).start_send(item)?;
+    poll_fn(|cx| Pin::new(&mut *sink).poll_flush(cx)).await
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
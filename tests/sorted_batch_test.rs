@@ -0,0 +1,45 @@
+// --------------------------------------------------------
+// Actor library - Sorted batch actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::SortedBatchActor;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[tokio::test]
+// Test flushing sorted batches by size, interval and stop.
+async fn test_sorted_batch_actor() {
+    let batches = Arc::new(Mutex::new(Vec::new()));
+    let processed = batches.clone();
+    let actor = SortedBatchActor::new(
+        |n: &i32| *n,
+        3,
+        Duration::from_millis(50),
+        move |batch| {
+            processed.lock().unwrap().push(batch);
+            Ok(())
+        },
+    );
+
+    for n in [3, 1, 2, 5, 4] {
+        actor.send(n).await.unwrap();
+    }
+    for _ in 0..100 {
+        if batches.lock().unwrap().len() == 2 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(*batches.lock().unwrap(), vec![vec![1, 2, 3], vec![4, 5]]);
+
+    // Stopping processes the waiting items first.
+    actor.send(7).await.unwrap();
+    actor.stop().await.unwrap();
+    actor.actor().join().await;
+    assert_eq!(batches.lock().unwrap().last(), Some(&vec![7]));
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
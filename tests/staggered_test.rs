@@ -0,0 +1,44 @@
+// --------------------------------------------------------
+// Actor library - Staggered pool tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, AsyncActor, StaggeredPool};
+use std::time::Duration;
+
+#[tokio::test]
+// Test starting the actors one after another.
+async fn test_staggered_pool_start() {
+    let pool = StaggeredPool::new(3, Duration::from_millis(30), AsyncActor::new);
+    assert_eq!(pool.len(), 3);
+    assert_eq!(pool.actors().len(), 1);
+
+    // Tasks go to the started actors only.
+    for _ in 0..4 {
+        assert!(pool.send(|| Ok(())).await.is_ok());
+    }
+    assert_eq!(pool.actors()[0].stats().tasks_enqueued, 4);
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let actors = pool.actors();
+    assert_eq!(actors.len(), 3);
+
+    for _ in 0..3 {
+        assert!(pool.send(|| Ok(())).await.is_ok());
+    }
+    let enqueued: u64 = actors.iter().map(|a| a.stats().tasks_enqueued).sum();
+    assert_eq!(enqueued, 7);
+    assert!(actors[1..].iter().all(|a| a.stats().tasks_enqueued > 0));
+}
+
+#[tokio::test]
+// Test sending to an empty pool.
+async fn test_staggered_pool_empty() {
+    let pool = StaggeredPool::new(0, Duration::from_millis(10), AsyncActor::new);
+    assert!(pool.is_empty());
+    assert_eq!(pool.send(|| Ok(())).await, Err(ActorError::Stopped));
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,109 @@
+// --------------------------------------------------------
+// Actor library - State actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use std::fs;
+
+use actor::{actor, ActorError, ActorState, Checkpoint, StateActor};
+
+#[tokio::test]
+// Test working on the owned state and asking for it.
+async fn test_state_actor() {
+    let actor = StateActor::new(Vec::<i32>::new());
+
+    for i in 1..=3 {
+        let result = actor
+            .send(move |state| {
+                state.push(i);
+                Ok(())
+            })
+            .await;
+        assert_eq!(result, Ok(()));
+    }
+    let sum = actor.ask(|state| state.iter().sum::<i32>()).await;
+
+    assert_eq!(sum, Ok(6));
+}
+
+#[tokio::test]
+// Test that a failing task stops the actor and fails open asks.
+async fn test_state_actor_error() {
+    let actor = StateActor::new(0);
+
+    let _ = actor
+        .send(|_| Err(ActorError::Task("Ouch!".to_string())))
+        .await;
+    let result = actor.ask(|state| *state).await;
+
+    assert_eq!(result, Err(ActorError::Task("Ouch!".to_string())));
+    assert_eq!(actor.state(), ActorState::Error);
+}
+
+#[tokio::test]
+// Test the struct generated by the actor macro.
+async fn test_actor_macro() {
+    let counter = Counter::new(CounterState { value: 0 });
+
+    counter.incr().await.unwrap();
+    counter.incr().await.unwrap();
+    counter.incr().await.unwrap();
+    counter.decr().await.unwrap();
+    counter.add(5).await.unwrap();
+
+    assert_eq!(counter.read(|state| state.value).await, Ok(7));
+}
+
+#[tokio::test]
+// Test writing a checkpoint and restoring a new actor from it.
+async fn test_checkpoint_restore() {
+    let path = std::env::temp_dir().join(format!("actor-checkpoint-{}", std::process::id()));
+    let actor = StateActor::new(Total(0));
+
+    actor
+        .send(|total| {
+            total.0 += 42;
+            Ok(())
+        })
+        .await
+        .unwrap();
+    actor.checkpoint(&path).await.unwrap();
+
+    let restored = StateActor::<Total>::restore_from(&path).await.unwrap();
+    assert_eq!(restored.ask(|total| total.0).await, Ok(42));
+
+    fs::write(&path, "garbage").unwrap();
+    let result = StateActor::<Total>::restore_from(&path).await;
+    assert_eq!(
+        result.err().map(|err| err.kind()),
+        Some(std::io::ErrorKind::InvalidData)
+    );
+
+    fs::remove_file(path).unwrap();
+}
+
+// --------------------------------------------------------
+// TEST HELPER
+// --------------------------------------------------------
+
+actor!(Counter: CounterState { value: i32 } impl {
+    fn incr(&mut self) { self.value += 1 }
+    fn decr(&mut self) { self.value -= 1 }
+    fn add(&mut self, n: i32) { self.value += n }
+});
+
+struct Total(i32);
+
+impl Checkpoint for Total {
+    fn encode(&self) -> Vec<u8> {
+        self.0.to_string().into_bytes()
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        std::str::from_utf8(bytes).ok()?.parse().ok().map(Total)
+    }
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,66 @@
+// --------------------------------------------------------
+// Actor library - Supervised actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{AsyncActor, MailboxPreservation, SupervisedActor};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Sends a failing task followed by three counting ones and returns the
+/// processed ones after the restart.
+async fn fail_and_count(supervised: &SupervisedActor) -> Vec<i32> {
+    let processed = Arc::new(Mutex::new(Vec::new()));
+    assert!(supervised.send(|| Err("Ouch!".to_string())).await.is_ok());
+    for i in 1..=3 {
+        let processed = processed.clone();
+        let result = supervised
+            .send(move || {
+                processed.lock().unwrap().push(i);
+                Ok(())
+            })
+            .await;
+        assert!(result.is_ok());
+    }
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(supervised.restarts(), 1);
+    supervised.actor().ask(|| ()).await.unwrap();
+    let processed = processed.lock().unwrap().clone();
+    processed
+}
+
+#[tokio::test]
+// Test restarting the actor and dropping the waiting tasks.
+async fn test_supervised_discard() {
+    let supervised = SupervisedActor::new(AsyncActor::builder);
+    assert!(fail_and_count(&supervised).await.is_empty());
+}
+
+#[tokio::test]
+// Test restarting the actor with the waiting tasks of the failed one.
+async fn test_supervised_preserve() {
+    let supervised =
+        SupervisedActor::with_preservation(AsyncActor::builder, MailboxPreservation::Preserve);
+    assert_eq!(fail_and_count(&supervised).await, vec![1, 2, 3]);
+
+    assert!(supervised.stop().await.is_ok());
+    supervised.actor().join().await;
+    assert_eq!(supervised.restarts(), 1);
+}
+
+#[tokio::test]
+// Test dropping the actor together with the SupervisedActor.
+async fn test_supervised_drop() {
+    let supervised = SupervisedActor::new(AsyncActor::builder);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let actor = Arc::downgrade(&supervised.actor());
+
+    drop(supervised);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(actor.upgrade().is_none());
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,86 @@
+// --------------------------------------------------------
+// Actor library - Typed actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, ActorState, TypedActor};
+use tokio::sync::oneshot;
+
+#[tokio::test]
+// Test that a failing handler stops the actor.
+async fn test_typed_actor_error() {
+    let actor = TypedActor::new(0, |sum: &mut i32, n: i32| {
+        if n < 0 {
+            return Err(ActorError::Task(format!("negative value {}", n)));
+        }
+        *sum += n;
+        Ok(())
+    });
+
+    assert_eq!(actor.tell(1).await, Ok(()));
+    assert_eq!(actor.tell(-1).await, Ok(()));
+
+    // Wait a bit to ensure that the actor has processed all messages.
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    let expected = ActorError::Task("negative value -1".to_string());
+    assert_eq!(actor.state(), ActorState::Error);
+    assert_eq!(actor.tell(2).await, Err(expected));
+}
+
+#[tokio::test]
+// Test rejecting invalid messages before they are enqueued.
+async fn test_typed_actor_validator() {
+    let actor = TypedActor::with_validator(
+        0,
+        |sum: &mut i32, n: i32| {
+            *sum += n;
+            Ok(())
+        },
+        |n: &i32| {
+            if *n < 0 {
+                return Err(ActorError::Task(format!("negative value {}", n)));
+            }
+            Ok(())
+        },
+    );
+
+    assert_eq!(actor.tell(1).await, Ok(()));
+    assert_eq!(
+        actor.tell(-1).await,
+        Err(ActorError::Task("negative value -1".to_string()))
+    );
+
+    // The rejected message neither reached the handler nor changed the state.
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    assert_eq!(actor.state(), ActorState::Running);
+    assert_eq!(actor.error(), None);
+    assert_eq!(actor.tell(2).await, Ok(()));
+}
+
+#[tokio::test]
+// Test replacing the handler of a running actor.
+async fn test_typed_actor_swap_handler() {
+    type Reply = (i32, oneshot::Sender<i32>);
+    let actor = TypedActor::new(0, |_: &mut i32, (n, reply): Reply| {
+        let _ = reply.send(n);
+        Ok(())
+    });
+
+    let (reply, response) = oneshot::channel();
+    actor.tell((4, reply)).await.unwrap();
+    assert_eq!(response.await, Ok(4));
+
+    actor.swap_handler(|_: &mut i32, (n, reply): Reply| {
+        let _ = reply.send(n * 10);
+        Ok(())
+    });
+    let (reply, response) = oneshot::channel();
+    actor.tell((4, reply)).await.unwrap();
+    assert_eq!(response.await, Ok(40));
+    assert_eq!(actor.state(), ActorState::Running);
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
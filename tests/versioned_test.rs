@@ -0,0 +1,75 @@
+// --------------------------------------------------------
+// Actor library - Versioned state actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, Version, VersionedStateActor};
+
+#[tokio::test]
+// Test dispatching the messages to the handlers of their versions.
+async fn test_versioned_dispatch() {
+    let actor = VersionedStateActor::new(0);
+    actor.register_handler(|sum: &mut i32, msg: AddV1| {
+        *sum += msg.0;
+        Ok(())
+    });
+    actor.register_handler(|sum: &mut i32, msg: AddV2| {
+        *sum += msg.value * msg.factor;
+        Ok(())
+    });
+    assert_eq!(actor.versions(), vec![1, 2]);
+
+    assert_eq!(actor.send(AddV1(1)).await, Ok(()));
+    assert_eq!(
+        actor
+            .send(AddV2 {
+                value: 2,
+                factor: 3
+            })
+            .await,
+        Ok(())
+    );
+    assert_eq!(actor.inner().ask(|sum| *sum).await, Ok(7));
+}
+
+#[tokio::test]
+// Test rejecting messages of a removed version.
+async fn test_versioned_remove_handler() {
+    let actor = VersionedStateActor::new(0);
+    actor.register_handler(|sum: &mut i32, msg: AddV1| {
+        *sum += msg.0;
+        Ok(())
+    });
+
+    assert!(actor.remove_handler::<AddV1>());
+    assert_eq!(
+        actor.send(AddV1(1)).await,
+        Err(ActorError::Send("no handler for version 1".to_string()))
+    );
+    assert!(actor.versions().is_empty());
+}
+
+// --------------------------------------------------------
+// TEST HELPER
+// --------------------------------------------------------
+
+// AddV1 adds its value to the state.
+struct AddV1(i32);
+
+impl Version for AddV1 {
+    const VERSION: u32 = 1;
+}
+
+// AddV2 adds its value multiplied by its factor to the state.
+struct AddV2 {
+    value: i32,
+    factor: i32,
+}
+
+impl Version for AddV2 {
+    const VERSION: u32 = 2;
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,106 @@
+// --------------------------------------------------------
+// Actor library - Watchdog actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{
+    ActorState, AsyncActor, StopReason, StreamExt, TaskEventKind, WatchdogAction, WatchdogActor,
+};
+use std::time::Duration;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+// Test cancelling the token of a stuck task.
+async fn test_watchdog_cancel() {
+    let actor = AsyncActor::new();
+    let watchdog = WatchdogActor::new(
+        actor.clone(),
+        Duration::from_millis(20),
+        WatchdogAction::Cancel,
+    );
+
+    let running = actor.clone();
+    let cancelled = actor
+        .ask(move || {
+            let token = running.current_task_token();
+            let started = std::time::Instant::now();
+            while !token.is_cancelled() && started.elapsed() < Duration::from_secs(5) {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            token.is_cancelled()
+        })
+        .await
+        .unwrap();
+
+    assert!(cancelled);
+    assert_eq!(watchdog.triggered(), 1);
+    assert_eq!(actor.state(), ActorState::Running);
+
+    // The next task gets a new token.
+    let running = actor.clone();
+    let cancelled = actor
+        .ask(move || running.current_task_token().is_cancelled())
+        .await;
+    assert_eq!(cancelled, Ok(false));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+// Test publishing a stuck task as event.
+async fn test_watchdog_log() {
+    let actor = AsyncActor::new();
+    let watchdog = WatchdogActor::new(
+        actor.clone(),
+        Duration::from_millis(20),
+        WatchdogAction::Log,
+    );
+    let mut events = actor.task_stream();
+
+    let stuck = actor
+        .send(|| {
+            std::thread::sleep(Duration::from_millis(60));
+            Ok(())
+        })
+        .await
+        .unwrap();
+    let _ = actor.ask(|| ()).await;
+
+    let mut running = None;
+    let wait = Duration::from_millis(50);
+    while let Ok(Some(event)) = tokio::time::timeout(wait, events.next()).await {
+        match event.kind {
+            TaskEventKind::Stuck { running: duration } if event.task_id == stuck => {
+                running = Some(duration)
+            }
+            _ => {}
+        }
+    }
+    assert!(running.is_some_and(|running| running > Duration::from_millis(20)));
+    assert_eq!(watchdog.triggered(), 1);
+    assert_eq!(actor.state(), ActorState::Running);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+// Test stopping an actor with a stuck task.
+async fn test_watchdog_force_stop() {
+    let actor = AsyncActor::new();
+    let _watchdog = WatchdogActor::new(
+        actor.clone(),
+        Duration::from_millis(20),
+        WatchdogAction::ForceStop,
+    );
+
+    let _ = actor
+        .send(|| {
+            std::thread::sleep(Duration::from_millis(100));
+            Ok(())
+        })
+        .await;
+    let _ = actor.send(|| Ok(())).await;
+
+    assert_eq!(actor.join().await, ActorState::Stopped);
+    assert_eq!(actor.stop_reason(), Some(StopReason::Forced));
+    assert_eq!(actor.stats().tasks_processed, 1);
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
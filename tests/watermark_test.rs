@@ -0,0 +1,69 @@
+// --------------------------------------------------------
+// Actor library - Watermark controller tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, AsyncActor, WatermarkController};
+use std::sync::mpsc;
+use std::time::Duration;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+// Test pausing the producers above the high and resuming at the low mark.
+async fn test_watermark_controller() {
+    let controller = WatermarkController::new(AsyncActor::new(), 3, 2);
+    let (started, running) = mpsc::channel::<()>();
+    let (release, released) = mpsc::channel::<()>();
+    controller
+        .send(move || {
+            let _ = started.send(());
+            let _ = released.recv();
+            Ok(())
+        })
+        .await
+        .unwrap();
+    running.recv_timeout(Duration::from_secs(1)).unwrap();
+
+    for _ in 0..4 {
+        controller.send(|| Ok(())).await.unwrap();
+    }
+    assert_eq!(controller.send(|| Ok(())).await, Err(ActorError::Watermark));
+    assert!(controller.is_paused());
+    assert_eq!(controller.send(|| Ok(())).await, Err(ActorError::Watermark));
+
+    release.send(()).unwrap();
+    let resumed = tokio::time::timeout(Duration::from_secs(1), controller.wait_for_capacity());
+    assert!(resumed.await.is_ok());
+    assert!(!controller.is_paused());
+    assert!(controller.send(|| Ok(())).await.is_ok());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+// Test resuming the producers with a low mark of zero once the queue is empty.
+async fn test_watermark_controller_low_zero() {
+    let controller = WatermarkController::new(AsyncActor::new(), 1, 0);
+    let (started, running) = mpsc::channel::<()>();
+    let (release, released) = mpsc::channel::<()>();
+    controller
+        .send(move || {
+            let _ = started.send(());
+            let _ = released.recv();
+            Ok(())
+        })
+        .await
+        .unwrap();
+    running.recv_timeout(Duration::from_secs(1)).unwrap();
+
+    for _ in 0..2 {
+        controller.send(|| Ok(())).await.unwrap();
+    }
+    assert_eq!(controller.send(|| Ok(())).await, Err(ActorError::Watermark));
+
+    release.send(()).unwrap();
+    let resumed = tokio::time::timeout(Duration::from_secs(1), controller.wait_for_capacity());
+    assert!(resumed.await.is_ok());
+    assert!(controller.send(|| Ok(())).await.is_ok());
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------
@@ -0,0 +1,52 @@
+// --------------------------------------------------------
+// Actor library - Zero-copy actor tests
+// Copyright (C) 2024 Frank Mueller / Oldenburg / Europe / World
+// --------------------------------------------------------
+
+use actor::{ActorError, ActorState, ZeroCopyActor};
+use std::sync::{Arc, Mutex};
+
+#[tokio::test]
+// Test lending the buffers of the pool to the tasks in turns.
+async fn test_zero_copy_buffers() {
+    let actor = ZeroCopyActor::new(vec![Vec::with_capacity(1024), Vec::with_capacity(1024)]);
+    let lengths = Arc::new(Mutex::new(Vec::new()));
+
+    for i in 0..4 {
+        let lengths = lengths.clone();
+        let _ = actor
+            .send_with_buf(move |buf: &mut Vec<u8>| {
+                buf.push(i);
+                lengths.lock().unwrap().push((buf.len(), buf.capacity()));
+                Ok(())
+            })
+            .await;
+    }
+    let _ = actor.stop().await;
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+    // Each buffer is used twice and keeps its content and capacity.
+    assert_eq!(
+        *lengths.lock().unwrap(),
+        vec![(1, 1024), (1, 1024), (2, 1024), (2, 1024)]
+    );
+    assert_eq!(actor.state(), ActorState::Stopped);
+}
+
+#[tokio::test]
+// Test failing tasks without buffers.
+async fn test_zero_copy_no_buffers() {
+    let actor = ZeroCopyActor::<Vec<u8>>::new(Vec::new());
+
+    let _ = actor.send_with_buf(|_| Ok(())).await;
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+    assert_eq!(
+        actor.error(),
+        Some(ActorError::Task("no buffer in the pool".to_string()))
+    );
+}
+
+// --------------------------------------------------------
+// EOF
+// --------------------------------------------------------